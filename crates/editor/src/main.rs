@@ -1,11 +1,25 @@
 use std::env;
+use std::process;
+
+use engine_core::project::{ProjectManifest, USAGE};
 use engine_core::App;
 
 fn main() {
-    
-    let args: Vec<String> = env::args().collect();
-    let default_path = "target/debug/game_plugin.dll";
-    let plugin_path = args.get(1).map(|s| s.as_str()).unwrap_or(default_path);
+    // Sandboxed plugins re-launch this executable as their child process.
+    engine_core::plugin_sandbox::run_child_if_requested();
+
+    // Project settings come from engine.toml (or --project); arguments override
+    // them, and any plugin sources given replace the project's list.
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return;
+    }
+
+    let project = ProjectManifest::from_args(args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
 
-    App::new(plugin_path).run();
-}
\ No newline at end of file
+    App::new(project).run();
+}
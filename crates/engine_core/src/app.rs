@@ -1,12 +1,25 @@
 // crates/engine_core/src/app.rs
 
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
+use crate::bindings_ui::BindingsUi;
+use crate::commands;
+use crate::console::ConsoleState;
+use crate::cvars::{self, CVarValue, CVARS_FILE};
 use crate::gui::GuiSystem;
-use crate::input::{self, ActionRegistry, Arbiter, InputMap};
+use crate::logging;
+use crate::input::{self, ActionRegistry, Arbiter, BindingSpec, InputContexts, InputMap, TriggerStage};
 use crate::input::config::InputDefaults;
+use crate::input::contexts::{ContextDef, TEXT_ENTRY};
+use crate::input::gamepad::Gamepads;
+use crate::input::injected::InjectedSignals;
+use crate::input::profile::{InputProfile, ProfileFile, DEFAULT_PROFILE_FILE};
 use crate::platform_runner::PlatformRunner;
+use crate::project::ProjectManifest;
 use engine_shared::input_types::{ActionId, InputState};
+use engine_shared::plugin_api::CVAR_PERSIST;
 use winit::keyboard::KeyCode;
 
 /// High-level engine state container.
@@ -14,23 +27,54 @@ use winit::keyboard::KeyCode;
 /// Those are delegated to PlatformRunner / EngineLoop / InputPoller.
 pub struct App {
     // Exposed within crate so PlatformRunner can orchestrate.
-    pub(crate) registry: ActionRegistry,
     pub(crate) input_map: InputMap,
+    /// Engine and plugin default bindings, without the profile; reloads start from it.
+    pub(crate) base_map: InputMap,
+    pub(crate) profile_file: ProfileFile,
+    pub(crate) bindings_ui: BindingsUi,
     pub(crate) arbiter: Arbiter,
-    pub(crate) window_title: String,
+    pub(crate) gamepads: Gamepads,
+    pub(crate) triggers: TriggerStage,
+    pub(crate) injected: InjectedSignals,
+    pub(crate) contexts: InputContexts,
+    /// Engine and plugin input contexts, without the profile (like `base_map`).
+    pub(crate) base_contexts: BTreeMap<String, ContextDef>,
+    pub(crate) project: ProjectManifest,
     pub(crate) gui: GuiSystem,
+    pub(crate) console: ConsoleState,
 
     pub(crate) engine_toggle_inspector: ActionId,
     pub(crate) engine_request_hot_reload: ActionId,
+    pub(crate) engine_toggle_console: ActionId,
+    pub(crate) engine_toggle_plugin_panels: ActionId,
+    pub(crate) engine_toggle_bindings: ActionId,
 
     pub(crate) last_input_state: InputState,
 
-    pub(crate) plugin_path: String,
+    /// Actions bound by the input profile; plugin default bindings skip them.
+    pub(crate) profile_actions: HashSet<ActionId>,
+    /// The same for each input context's actions.
+    pub(crate) profile_context_actions: HashSet<(String, ActionId)>,
+
+    /// Resolved `[plugins] load` entries (see `PluginManager::new`).
+    pub(crate) plugin_paths: Vec<String>,
 }
 
 impl App {
-    /// Create a new App with a configurable plugin path.
-    pub fn new(plugin_path: &str) -> Self {
+    /// Create a new App from a project manifest (see `ProjectManifest::from_args`).
+    pub fn new(project: ProjectManifest) -> Self {
+        logging::init();
+
+        // Console: persisted cvar values first, so registrations below pick them up.
+        cvars::load(Path::new(CVARS_FILE));
+        cvars::register(
+            "input.deadzone",
+            CVarValue::Float(0.1),
+            "Movement axes below this magnitude are zeroed",
+            CVAR_PERSIST,
+        );
+        commands::register_builtins();
+
         let mut registry = ActionRegistry::default();
         let mut input_map = InputMap::default();
 
@@ -38,31 +82,138 @@ impl App {
         InputDefaults::setup(&mut registry, &mut input_map);
 
         // 2. Register engine-level actions as first-class actions.
-        let engine_toggle_inspector = registry.register("Engine.ToggleInspector");
-        let engine_request_hot_reload = registry.register("Engine.RequestHotReload");
-
-        // Bind F1/F5 to these actions (no hard-coded branches in the loop).
+        let mut register = |name| registry.register(name).expect("engine actions fit in a fresh registry");
+        let engine_toggle_inspector = register("Engine.ToggleInspector");
+        let engine_request_hot_reload = register("Engine.RequestHotReload");
+        let engine_toggle_console = register("Engine.ToggleConsole");
+        let engine_toggle_plugin_panels = register("Engine.TogglePluginPanels");
+        let engine_toggle_bindings = register("Engine.ToggleBindings");
+
+        // Bind F1/F2/F3/F5/` to these actions (no hard-coded branches in the loop).
         input_map.bind_logical(KeyCode::F1, engine_toggle_inspector);
+        input_map.bind_logical(KeyCode::F2, engine_toggle_plugin_panels);
+        input_map.bind_logical(KeyCode::F3, engine_toggle_bindings);
         input_map.bind_logical(KeyCode::F5, engine_request_hot_reload);
-
-        // 3. Publish registry globally for tools / plugins.
-        let _ = input::GLOBAL_REGISTRY.set(Mutex::new(registry.clone()));
+        input_map.bind_logical(KeyCode::Backquote, engine_toggle_console);
+        let base_map = input_map.clone();
+
+        // Project key bindings win over everything above. Without a profile in
+        // the project, the Key Bindings window saves to (and we load) ./input.toml.
+        let profile_path = project
+            .input
+            .profile
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_PROFILE_FILE));
+        let mut profile_file = ProfileFile::new(profile_path);
+        let profile = profile_file.load().unwrap_or_else(|e| {
+            tracing::error!("❌ {e}");
+            InputProfile::default()
+        });
+        let profile_actions = profile.apply(&mut registry, &mut input_map);
+        let gamepads = profile.gamepads();
+
+        // Text fields take the keyboard from every binding while focused.
+        let mut base_contexts = BTreeMap::new();
+        base_contexts.insert(TEXT_ENTRY.to_string(), ContextDef {
+            map: InputMap::default(),
+            blocks_keyboard: true,
+        });
+        let mut context_defs = base_contexts.clone();
+        let profile_context_actions = profile.apply_contexts(&mut registry, &mut context_defs);
+        let mut contexts = InputContexts::default();
+        contexts.set_definitions(context_defs);
+
+        // 3. Publish registry globally; from here on it is the source of truth
+        //    (plugins add their own actions to it through HostInterface).
+        let _ = input::GLOBAL_REGISTRY.set(Mutex::new(registry));
 
         // 4. Configure Arbiter layers from centralized defaults.
-        let arbiter = Arbiter::new(InputDefaults::default_arbiter_layers(), 0.1);
+        let deadzone = cvars::get_f32("input.deadzone").unwrap_or(0.1);
+        let arbiter = Arbiter::new(InputDefaults::default_arbiter_layers(), deadzone);
 
         Self {
-            registry,
             input_map,
+            base_map,
+            profile_file,
+            bindings_ui: BindingsUi::default(),
             arbiter,
-            window_title: "Rust Engine: Modular Architecture".to_string(),
+            gamepads,
+            triggers: TriggerStage::default(),
+            injected: InjectedSignals::default(),
+            contexts,
+            base_contexts,
             gui: GuiSystem::new(),
+            console: ConsoleState::default(),
 
             engine_toggle_inspector,
             engine_request_hot_reload,
+            engine_toggle_console,
+            engine_toggle_plugin_panels,
+            engine_toggle_bindings,
 
             last_input_state: InputState::default(),
-            plugin_path: plugin_path.to_string(),
+            profile_actions,
+            profile_context_actions,
+            plugin_paths: project.plugin_sources(),
+            project,
+        }
+    }
+
+    /// Rebuilds the live bindings from the defaults plus the profile file as it is now.
+    pub(crate) fn reload_input_profile(&mut self) -> Result<(), String> {
+        let profile = self.profile_file.load()?;
+        let Some(registry) = input::GLOBAL_REGISTRY.get() else {
+            return Err("Action registry is not initialized".to_string());
+        };
+        let mut registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut input_map = self.base_map.clone();
+        self.profile_actions = profile.apply(&mut registry, &mut input_map);
+        self.input_map = input_map;
+        let mut context_defs = self.base_contexts.clone();
+        self.profile_context_actions = profile.apply_contexts(&mut registry, &mut context_defs);
+        self.contexts.set_definitions(context_defs);
+        self.gamepads
+            .configure(profile.gamepad.deadzone, profile.gamepad.devices.clone());
+
+        tracing::info!("⌨️ Input profile loaded from {}", self.profile_file.path.display());
+        Ok(())
+    }
+
+    /// Writes the live bindings (every action) and pad deadzones to the profile file.
+    pub(crate) fn save_input_profile(&mut self) -> Result<(), String> {
+        let Some(registry) = input::GLOBAL_REGISTRY.get() else {
+            return Err("Action registry is not initialized".to_string());
+        };
+        let registry = registry.lock().unwrap_or_else(|e| e.into_inner());
+        let profile = InputProfile::capture(&registry, &self.input_map, self.contexts.definitions(), &self.gamepads);
+        self.profile_file.save(&profile)?;
+
+        // Every action is in the file now: plugin defaults must not override any of them.
+        self.profile_actions = registry.entries().into_iter().map(|(_, id)| id).collect();
+        self.profile_context_actions = profile
+            .contexts
+            .iter()
+            .flat_map(|(context, config)| config.bindings.keys().map(move |action| (context, action)))
+            .filter_map(|(context, action)| Some((context.clone(), registry.get_id(action)?)))
+            .collect();
+        tracing::info!("💾 Input profile saved to {}", self.profile_file.path.display());
+        Ok(())
+    }
+
+    /// A plugin's default binding in an input context, unless the profile set
+    /// that action there; creates the context if needed.
+    pub(crate) fn apply_context_binding(&mut self, context: &str, action: ActionId, binding: BindingSpec) {
+        if self.profile_context_actions.contains(&(context.to_string(), action)) {
+            tracing::debug!("Default binding {binding:?} for action {action} in '{context}' skipped: set by the input profile");
+            return;
+        }
+        let base = self.base_contexts.entry(context.to_string()).or_default();
+        let _ = base.map.bind_spec_default(binding.clone(), action);
+        if let Err(existing) = self.contexts.def_mut(context).map.bind_spec_default(binding.clone(), action) {
+            tracing::warn!(
+                "⚠️ Default binding {binding:?} for action {action} in '{context}' ignored: already bound to action {existing}"
+            );
         }
     }
 
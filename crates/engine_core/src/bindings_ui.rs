@@ -0,0 +1,183 @@
+// crates/engine_core/src/bindings_ui.rs
+//! "Key Bindings" window (F3): rebind any registered action to keys, mouse
+//! buttons, wheel steps or gamepad buttons, and save the result as the input
+//! profile (see `input::profile`). Triggers (chords, holds, sequences) are
+//! listed and can be removed; they are added by name in the profile.
+
+use std::path::Path;
+
+use egui::{Color32, Context};
+use engine_shared::ActionId;
+use winit::event::{ElementState, MouseScrollDelta, WindowEvent};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::input::mouse::{self, MouseInput};
+use crate::input::{Binding, InputMap, GLOBAL_REGISTRY};
+
+pub enum BindingsRequest {
+    Save,
+    Reload,
+}
+
+#[derive(Default)]
+pub struct BindingsUi {
+    /// Action waiting for the next input pressed.
+    capturing: Option<ActionId>,
+    /// Captured input that is bound elsewhere: (input, wanted by, bound to).
+    conflict: Option<(Binding, ActionId, ActionId)>,
+    /// Outcome of the last save or reload.
+    pub status: Option<Result<String, String>>,
+}
+
+impl BindingsUi {
+    pub fn is_capturing(&self) -> bool {
+        self.capturing.is_some()
+    }
+
+    /// Binds the input pressed while capturing, unless another action has it
+    /// (then the window asks first). Escape cancels.
+    pub fn capture(&mut self, binding: Binding, input_map: &mut InputMap) {
+        let Some(action) = self.capturing.take() else { return };
+        if binding == Binding::Key(KeyCode::Escape) {
+            return;
+        }
+        match input_map.action_for(binding) {
+            Some(existing) if existing != action => self.conflict = Some((binding, action, existing)),
+            _ => input_map.bind(binding, action),
+        }
+    }
+}
+
+/// The input a window event presses, for capturing.
+pub fn binding_from_event(event: &WindowEvent) -> Option<Binding> {
+    match event {
+        WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed && !event.repeat => {
+            match event.physical_key {
+                PhysicalKey::Code(code) => Some(Binding::Key(code)),
+                PhysicalKey::Unidentified(_) => None,
+            }
+        }
+        WindowEvent::MouseInput { state: ElementState::Pressed, button, .. } => {
+            mouse::from_winit(*button).map(Binding::Mouse)
+        }
+        WindowEvent::MouseWheel { delta, .. } => {
+            let lines = match delta {
+                MouseScrollDelta::LineDelta(_, y) => *y,
+                MouseScrollDelta::PixelDelta(p) => p.y as f32,
+            };
+            match lines {
+                l if l > 0.0 => Some(Binding::Mouse(MouseInput::WheelUp)),
+                l if l < 0.0 => Some(Binding::Mouse(MouseInput::WheelDown)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+pub fn show(
+    ctx: &Context,
+    state: &mut BindingsUi,
+    input_map: &mut InputMap,
+    file_conflicts: &[String],
+    profile_path: &Path,
+    open: &mut bool,
+) -> Option<BindingsRequest> {
+    let actions = GLOBAL_REGISTRY
+        .get()
+        .map(|r| r.lock().unwrap_or_else(|e| e.into_inner()).entries())
+        .unwrap_or_default();
+    let action_name = |id: ActionId| {
+        actions
+            .iter()
+            .find(|(_, a)| *a == id)
+            .map_or_else(|| format!("#{id}"), |(name, _)| name.clone())
+    };
+    let binding_name = |b: Binding| b.name().unwrap_or_else(|| format!("{b:?}"));
+
+    let mut request = None;
+    egui::Window::new("Key Bindings")
+        .open(open)
+        .default_pos([700.0, 60.0])
+        .default_width(380.0)
+        .show(ctx, |ui| {
+            for conflict in file_conflicts {
+                ui.colored_label(Color32::RED, format!("Conflict in profile: {conflict}"));
+            }
+
+            if let Some((binding, wanted, existing)) = state.conflict {
+                ui.colored_label(
+                    Color32::YELLOW,
+                    format!(
+                        "{} is bound to {}. Move it to {}?",
+                        binding_name(binding),
+                        action_name(existing),
+                        action_name(wanted)
+                    ),
+                );
+                ui.horizontal(|ui| {
+                    if ui.button("Replace").clicked() {
+                        input_map.bind(binding, wanted);
+                        state.conflict = None;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        state.conflict = None;
+                    }
+                });
+                ui.separator();
+            }
+
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                egui::Grid::new("bindings").striped(true).num_columns(2).show(ui, |ui| {
+                    for (name, id) in &actions {
+                        ui.label(name);
+                        ui.horizontal_wrapped(|ui| {
+                            for binding in input_map.bindings_of(*id) {
+                                if ui
+                                    .small_button(format!("{} ✕", binding_name(binding)))
+                                    .on_hover_text("Remove")
+                                    .clicked()
+                                {
+                                    input_map.unbind(binding);
+                                }
+                            }
+                            for trigger in input_map.triggers_of(*id) {
+                                let name = trigger.name().unwrap_or_else(|| format!("{trigger:?}"));
+                                if ui.small_button(format!("{name} ✕")).on_hover_text("Remove").clicked() {
+                                    input_map.unbind_trigger(&trigger);
+                                }
+                            }
+                            if state.capturing == Some(*id) {
+                                ui.colored_label(Color32::YELLOW, "press an input… (Esc cancels)");
+                            } else if ui.small_button("+").on_hover_text("Add a binding").clicked() {
+                                state.capturing = Some(*id);
+                                state.conflict = None;
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(format!("Save to {}", profile_path.display())).clicked() {
+                    request = Some(BindingsRequest::Save);
+                }
+                if ui.button("Reload").clicked() {
+                    request = Some(BindingsRequest::Reload);
+                }
+            });
+            match &state.status {
+                Some(Ok(msg)) => {
+                    ui.colored_label(Color32::GREEN, msg);
+                }
+                Some(Err(msg)) => {
+                    ui.colored_label(Color32::RED, msg);
+                }
+                None => {}
+            }
+        });
+
+    request
+}
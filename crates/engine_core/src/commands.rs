@@ -0,0 +1,245 @@
+// crates/engine_core/src/commands.rs
+//! Console commands. Host commands are plain Rust functions; plugin commands
+//! are `ConsoleCommandFn` callbacks owned by the plugin that registered them
+//! and dropped again when that plugin is unloaded.
+//!
+//! A line that doesn't name a command but names a cvar reads it (`player.speed`)
+//! or assigns it (`player.speed 800`).
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ffi::c_void;
+use std::path::Path;
+use std::sync::Mutex;
+
+use engine_ecs::World;
+use engine_shared::plugin_api::{ConsoleCommandFn, HostContext};
+
+use crate::cvars::{self, CVARS_FILE};
+use crate::input::contexts::{self, ContextRequest};
+use crate::logging;
+
+/// Host command: receives the World and the argument text after the command name.
+pub type HostCommandFn = fn(&mut World, &str) -> Result<(), String>;
+
+#[derive(Clone, Copy)]
+enum Handler {
+    Host(HostCommandFn),
+    Plugin {
+        callback: ConsoleCommandFn,
+        user_data: PluginData,
+    },
+}
+
+/// Plugin-owned pointer; only handed back to the plugin on the main thread.
+#[derive(Clone, Copy)]
+struct PluginData(*mut c_void);
+unsafe impl Send for PluginData {}
+
+struct Command {
+    description: String,
+    /// Plugin name, `None` for host commands.
+    owner: Option<String>,
+    handler: Handler,
+}
+
+static COMMANDS: Mutex<BTreeMap<String, Command>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    /// Plugin currently inside `on_load`; attributed as owner of commands it registers.
+    static CURRENT_OWNER: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn commands() -> std::sync::MutexGuard<'static, BTreeMap<String, Command>> {
+    COMMANDS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Runs `f` (a plugin's `on_load`) with `owner` as the owner of any commands it registers.
+pub fn with_owner<R>(owner: &str, f: impl FnOnce() -> R) -> R {
+    CURRENT_OWNER.with(|o| *o.borrow_mut() = Some(owner.to_string()));
+    let result = f();
+    CURRENT_OWNER.with(|o| *o.borrow_mut() = None);
+    result
+}
+
+pub fn register_host(name: &str, description: &str, handler: HostCommandFn) {
+    commands().insert(
+        name.to_string(),
+        Command {
+            description: description.to_string(),
+            owner: None,
+            handler: Handler::Host(handler),
+        },
+    );
+}
+
+/// Drops every command registered by `owner` (call before its library goes away).
+pub fn remove_owned_by(owner: &str) {
+    commands().retain(|_, c| c.owner.as_deref() != Some(owner));
+}
+
+/// Executes one console line against `world`. Output goes to the log under target "console".
+pub fn execute(line: &str, world: &mut World) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+    tracing::info!(target: "console", "> {line}");
+
+    let (name, args) = match line.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (line, ""),
+    };
+
+    // Copy the handler out so the lock isn't held while it runs (it may register or log).
+    let handler = commands().get(name).map(|c| c.handler);
+    let result = match handler {
+        Some(Handler::Host(f)) => f(world, args),
+        Some(Handler::Plugin { callback, user_data }) => {
+            let ctx = world as *mut World as *mut HostContext;
+            callback(user_data.0, ctx, args.as_ptr(), args.len());
+            Ok(())
+        }
+        None => cvar_command(name, args),
+    };
+
+    if let Err(e) = result {
+        tracing::warn!(target: "console", "{e}");
+    }
+    cvars::save_if_dirty(Path::new(CVARS_FILE));
+}
+
+/// Names of all commands and cvars starting with `prefix` (for console completion).
+pub fn completions(prefix: &str) -> Vec<String> {
+    let mut names: Vec<String> = commands()
+        .keys()
+        .filter(|n| n.starts_with(prefix))
+        .cloned()
+        .collect();
+    cvars::with(|r| {
+        names.extend(r.iter().map(|v| v.name.clone()).filter(|n| n.starts_with(prefix)));
+    });
+    names.sort();
+    names
+}
+
+fn cvar_command(name: &str, args: &str) -> Result<(), String> {
+    if args.is_empty() {
+        let var = cvars::with(|r| r.get(name).cloned())
+            .ok_or_else(|| format!("Unknown command or cvar '{name}'"))?;
+        tracing::info!(
+            target: "console",
+            "{} = {} (default {}) - {}",
+            var.name, var.value, var.default, var.description
+        );
+        return Ok(());
+    }
+    let value = cvars::with(|r| r.set_from_str(name, args))?;
+    tracing::info!(target: "console", "{name} = {value}");
+    Ok(())
+}
+
+/// Built-in host commands. Called once from `App::new`.
+pub fn register_builtins() {
+    register_host("help", "List console commands", |_, filter| {
+        for (name, cmd) in commands().iter().filter(|(n, _)| n.contains(filter)) {
+            let owner = cmd.owner.as_deref().unwrap_or("host");
+            tracing::info!(target: "console", "{name} [{owner}] - {}", cmd.description);
+        }
+        Ok(())
+    });
+    register_host("cvars", "List console variables (optional filter)", |_, filter| {
+        cvars::with(|r| {
+            for var in r.iter().filter(|v| v.name.contains(filter)) {
+                tracing::info!(
+                    target: "console",
+                    "{} = {} ({:?}) - {}",
+                    var.name, var.value, var.value.kind(), var.description
+                );
+            }
+        });
+        Ok(())
+    });
+    register_host("set", "set <cvar> <value>", |_, args| {
+        let (name, value) = args
+            .split_once(char::is_whitespace)
+            .ok_or("usage: set <cvar> <value>")?;
+        cvar_command(name, value.trim())
+    });
+    register_host("reset", "reset <cvar> - restore the default value", |_, name| {
+        cvars::with(|r| r.reset(name))?;
+        cvar_command(name, "")
+    });
+    register_host("cvars_save", "Write persistent cvars to disk", |_, _| {
+        cvars::save(Path::new(CVARS_FILE))?;
+        tracing::info!(target: "console", "Saved {CVARS_FILE}");
+        Ok(())
+    });
+    register_host("clear", "Clear the console", |_, _| {
+        logging::clear();
+        Ok(())
+    });
+    register_host("push_context", "push_context <name> - push an input context", |_, name| {
+        if name.is_empty() {
+            return Err("usage: push_context <name>".to_string());
+        }
+        contexts::request(ContextRequest::Push(name.to_string()));
+        Ok(())
+    });
+    register_host("pop_context", "pop_context <name> - pop an input context", |_, name| {
+        if name.is_empty() {
+            return Err("usage: pop_context <name>".to_string());
+        }
+        contexts::request(ContextRequest::Pop(name.to_string()));
+        Ok(())
+    });
+}
+
+/// `HostInterface::register_command` implementation.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_register_command(
+    name_ptr: *const u8,
+    name_len: usize,
+    desc_ptr: *const u8,
+    desc_len: usize,
+    callback: ConsoleCommandFn,
+    user_data: *mut c_void,
+) -> bool {
+    let Some(name) = (unsafe { logging::str_from_raw(name_ptr, name_len) }) else {
+        return false;
+    };
+    let description = unsafe { logging::str_from_raw(desc_ptr, desc_len) }.unwrap_or("");
+
+    let Some(owner) = CURRENT_OWNER.with(|o| o.borrow().clone()) else {
+        tracing::warn!("⚠️ register_command '{name}' refused: only valid during on_load");
+        return false;
+    };
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        tracing::warn!("⚠️ register_command: invalid name '{name}'");
+        return false;
+    }
+
+    let mut commands = commands();
+    if let Some(existing) = commands.get(name) {
+        if existing.owner.as_deref() != Some(owner.as_str()) {
+            tracing::warn!(
+                "⚠️ register_command '{name}' refused: already registered by {}",
+                existing.owner.as_deref().unwrap_or("host")
+            );
+            return false;
+        }
+    }
+
+    commands.insert(
+        name.to_string(),
+        Command {
+            description: description.to_string(),
+            owner: Some(owner),
+            handler: Handler::Plugin {
+                callback,
+                user_data: PluginData(user_data),
+            },
+        },
+    );
+    true
+}
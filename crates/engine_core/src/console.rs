@@ -0,0 +1,377 @@
+// crates/engine_core/src/console.rs
+use egui::{Color32, Context, Key, Ui};
+use tracing::Level;
+
+use crate::commands;
+use crate::cvars::{self, CVar, CVarValue};
+use crate::logging::{self, LogEntry, LogSource};
+
+/// Filter / view settings for the in-game console. Lives in App so it
+/// survives the window being closed and reopened.
+pub struct ConsoleState {
+    pub search: String,
+    pub target_filter: String,
+    /// Most verbose level shown (ERROR shows only errors, TRACE shows everything).
+    pub max_level: Level,
+    pub show_host: bool,
+    pub show_plugin: bool,
+    pub group_by_frame: bool,
+
+    /// Command line being edited, plus previously submitted lines (oldest first).
+    pub input: String,
+    history: Vec<String>,
+    history_pos: Option<usize>,
+    completions: Vec<String>,
+    /// Lines entered this frame; drained by PlatformRunner, which owns the World.
+    submitted: Vec<String>,
+}
+
+impl Default for ConsoleState {
+    fn default() -> Self {
+        Self {
+            search: String::new(),
+            target_filter: String::new(),
+            max_level: Level::INFO,
+            show_host: true,
+            show_plugin: true,
+            group_by_frame: false,
+            input: String::new(),
+            history: Vec::new(),
+            history_pos: None,
+            completions: Vec::new(),
+            submitted: Vec::new(),
+        }
+    }
+}
+
+impl ConsoleState {
+    pub fn take_submitted(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.submitted)
+    }
+
+    fn submit(&mut self) {
+        let line = std::mem::take(&mut self.input);
+        self.history_pos = None;
+        self.completions.clear();
+        if line.trim().is_empty() {
+            return;
+        }
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+        }
+        self.submitted.push(line);
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let last = self.history.len() - 1;
+        self.history_pos = match (self.history_pos, older) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.input = self
+            .history_pos
+            .map(|i| self.history[i].clone())
+            .unwrap_or_default();
+    }
+
+    /// Completes the first word to the longest common prefix of matching commands/cvars.
+    fn complete(&mut self) {
+        if self.input.contains(char::is_whitespace) {
+            return;
+        }
+        let matches = commands::completions(&self.input);
+        if let Some(first) = matches.first() {
+            let common = matches.iter().fold(first.as_str(), |acc, m| {
+                let len = acc
+                    .char_indices()
+                    .zip(m.chars())
+                    .take_while(|((_, a), b)| a == b)
+                    .last()
+                    .map_or(0, |((i, c), _)| i + c.len_utf8());
+                &acc[..len]
+            });
+            self.input = common.to_string();
+            if matches.len() == 1 {
+                self.input.push(' ');
+            }
+        }
+        self.completions = if matches.len() > 1 { matches } else { Vec::new() };
+    }
+
+    fn accepts(&self, entry: &LogEntry) -> bool {
+        let source_ok = match entry.source {
+            LogSource::Host => self.show_host,
+            LogSource::Plugin => self.show_plugin,
+        };
+
+        source_ok
+            && entry.level <= self.max_level
+            && contains_ignore_case(&entry.target, &self.target_filter)
+            && contains_ignore_case(&entry.message, &self.search)
+    }
+}
+
+pub fn show(ctx: &Context, state: &mut ConsoleState, open: &mut bool) {
+    egui::Window::new("Console")
+        .open(open)
+        .default_size([640.0, 320.0])
+        .show(ctx, |ui| {
+            filter_bar(ui, state);
+            variables(ui);
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .max_height((ui.available_height() - 32.0).max(0.0))
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    logging::with_entries(|entries| {
+                        let visible = entries.iter().filter(|e| state.accepts(e));
+                        if state.group_by_frame {
+                            grouped_by_frame(ui, visible);
+                        } else {
+                            for entry in visible {
+                                entry_line(ui, entry);
+                            }
+                        }
+                    });
+                });
+
+            ui.separator();
+            command_line(ui, state);
+        });
+}
+
+fn command_line(ui: &mut Ui, state: &mut ConsoleState) {
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut state.input)
+            .id_source("console_input")
+            .hint_text("Command or cvar (Tab completes, Up/Down for history)")
+            .lock_focus(true)
+            .desired_width(f32::INFINITY),
+    );
+
+    if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+        state.submit();
+        response.request_focus();
+    } else if response.has_focus() {
+        if ui.input(|i| i.key_pressed(Key::Tab)) {
+            state.complete();
+        } else if ui.input(|i| i.key_pressed(Key::ArrowUp)) {
+            state.browse_history(true);
+        } else if ui.input(|i| i.key_pressed(Key::ArrowDown)) {
+            state.browse_history(false);
+        }
+    }
+
+    if !state.completions.is_empty() {
+        ui.weak(state.completions.join("  "));
+    }
+}
+
+/// Inline editors for every registered cvar.
+fn variables(ui: &mut Ui) {
+    egui::CollapsingHeader::new("Variables")
+        .id_source("console_variables")
+        .show(ui, |ui| {
+            let vars: Vec<CVar> = cvars::with(|r| r.iter().cloned().collect());
+            egui::Grid::new("console_cvar_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for var in vars {
+                        ui.label(&var.name).on_hover_text(&var.description);
+                        let mut value = var.value.clone();
+                        let changed = match &mut value {
+                            CVarValue::Int(v) => ui.add(egui::DragValue::new(v)).changed(),
+                            CVarValue::Float(v) => {
+                                ui.add(egui::DragValue::new(v).speed(0.1)).changed()
+                            }
+                            CVarValue::Bool(v) => ui.checkbox(v, "").changed(),
+                            CVarValue::Str(v) => ui.text_edit_singleline(v).changed(),
+                        };
+                        if changed {
+                            let _ = cvars::with(|r| r.set(&var.name, value));
+                        }
+                        if ui
+                            .add_enabled(var.value != var.default, egui::Button::new("Reset"))
+                            .clicked()
+                        {
+                            let _ = cvars::with(|r| r.reset(&var.name));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn filter_bar(ui: &mut Ui, state: &mut ConsoleState) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("console_level")
+            .selected_text(level_name(state.max_level))
+            .show_ui(ui, |ui| {
+                for level in [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE] {
+                    ui.selectable_value(&mut state.max_level, level, level_name(level));
+                }
+            });
+        ui.checkbox(&mut state.show_host, "Host");
+        ui.checkbox(&mut state.show_plugin, "Plugin");
+        ui.checkbox(&mut state.group_by_frame, "Group by frame");
+        if ui.button("Clear").clicked() {
+            logging::clear();
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut state.search)
+                .hint_text("Search")
+                .desired_width(220.0),
+        );
+        ui.add(
+            egui::TextEdit::singleline(&mut state.target_filter)
+                .hint_text("Target")
+                .desired_width(160.0),
+        );
+    });
+}
+
+fn grouped_by_frame<'a>(ui: &mut Ui, entries: impl Iterator<Item = &'a LogEntry>) {
+    let mut group: Vec<&LogEntry> = Vec::new();
+
+    let flush = |ui: &mut Ui, group: &mut Vec<&LogEntry>| {
+        let Some(first) = group.first() else { return };
+        egui::CollapsingHeader::new(format!("Frame {} ({} entries)", first.frame, group.len()))
+            .id_source(("console_frame", first.frame))
+            .default_open(true)
+            .show(ui, |ui| {
+                for entry in group.iter() {
+                    entry_line(ui, entry);
+                }
+            });
+        group.clear();
+    };
+
+    for entry in entries {
+        if group.first().is_some_and(|g| g.frame != entry.frame) {
+            flush(ui, &mut group);
+        }
+        group.push(entry);
+    }
+    flush(ui, &mut group);
+}
+
+fn entry_line(ui: &mut Ui, entry: &LogEntry) {
+    let source = match entry.source {
+        LogSource::Host => "host",
+        LogSource::Plugin => "plugin",
+    };
+    ui.colored_label(
+        level_color(entry.level),
+        format!(
+            "[{}] {:5} [{}] {}: {}",
+            entry.frame,
+            level_name(entry.level),
+            source,
+            entry.target,
+            entry.message
+        ),
+    );
+}
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::ERROR => "ERROR",
+        Level::WARN => "WARN",
+        Level::INFO => "INFO",
+        Level::DEBUG => "DEBUG",
+        Level::TRACE => "TRACE",
+    }
+}
+
+fn level_color(level: Level) -> Color32 {
+    match level {
+        Level::ERROR => Color32::RED,
+        Level::WARN => Color32::YELLOW,
+        Level::INFO => Color32::LIGHT_GRAY,
+        Level::DEBUG => Color32::GRAY,
+        Level::TRACE => Color32::DARK_GRAY,
+    }
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    needle.is_empty() || haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, source: LogSource, target: &str, message: &str) -> LogEntry {
+        LogEntry { frame: 0, level, source, target: target.to_string(), message: message.to_string() }
+    }
+
+    #[test]
+    fn filters_by_level_source_target_and_text() {
+        let mut state = ConsoleState::default();
+        let warn = entry(Level::WARN, LogSource::Plugin, "game_plugin", "Enemy Spawned");
+        let debug = entry(Level::DEBUG, LogSource::Host, "engine_core::gui", "redraw");
+        assert!(state.accepts(&warn));
+        assert!(!state.accepts(&debug));
+
+        state.max_level = Level::TRACE;
+        state.search = "enemy".into();
+        state.target_filter = "GAME".into();
+        assert!(state.accepts(&warn));
+        assert!(!state.accepts(&debug));
+
+        state.show_plugin = false;
+        assert!(!state.accepts(&warn));
+    }
+
+    #[test]
+    fn submit_records_history_and_arrows_browse_it() {
+        let mut state = ConsoleState::default();
+        for line in ["spawn 1", "spawn 1", "  ", "god"] {
+            state.input = line.into();
+            state.submit();
+        }
+        assert_eq!(state.take_submitted(), ["spawn 1", "spawn 1", "god"]);
+        assert_eq!(state.history, ["spawn 1", "god"]);
+
+        state.browse_history(true);
+        assert_eq!(state.input, "god");
+        state.browse_history(true);
+        state.browse_history(true);
+        assert_eq!(state.input, "spawn 1");
+        state.browse_history(false);
+        assert_eq!(state.input, "god");
+        state.browse_history(false);
+        assert_eq!(state.input, "");
+    }
+
+    #[test]
+    fn tab_completes_the_common_prefix() {
+        fn noop(_: &mut engine_ecs::World, _: &str) -> Result<(), String> {
+            Ok(())
+        }
+        commands::register_host("console_test.alpha", "", noop);
+        commands::register_host("console_test.alpine", "", noop);
+
+        let mut state = ConsoleState { input: "console_test.a".into(), ..Default::default() };
+        state.complete();
+        assert_eq!(state.input, "console_test.alp");
+        assert_eq!(state.completions, ["console_test.alpha", "console_test.alpine"]);
+
+        state.input = "console_test.alpi".into();
+        state.complete();
+        assert_eq!(state.input, "console_test.alpine ");
+        assert!(state.completions.is_empty());
+    }
+}
@@ -0,0 +1,396 @@
+// crates/engine_core/src/cvars.rs
+//! Console variables: typed, named tuning values editable at runtime.
+//!
+//! One process-wide registry (like `input::GLOBAL_REGISTRY`) so host code,
+//! the console and plugins (through `HostInterface`) all see the same values.
+//! Persisted cvars are written to `cvars.toml` when they differ from their default.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use engine_shared::plugin_api::{CVarKind, CVAR_PERSIST};
+
+use crate::logging;
+
+/// Default location of the persisted cvar file (relative to the working directory).
+pub const CVARS_FILE: &str = "cvars.toml";
+
+static CVARS: Mutex<Option<CVarRegistry>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CVarValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+}
+
+impl CVarValue {
+    pub fn kind(&self) -> CVarKind {
+        match self {
+            CVarValue::Int(_) => CVarKind::Int,
+            CVarValue::Float(_) => CVarKind::Float,
+            CVarValue::Bool(_) => CVarKind::Bool,
+            CVarValue::Str(_) => CVarKind::String,
+        }
+    }
+
+    /// Parses console / FFI text as a value of `kind`.
+    pub fn parse(kind: CVarKind, text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        match kind {
+            CVarKind::Int => text
+                .parse()
+                .map(CVarValue::Int)
+                .map_err(|_| format!("'{text}' is not an integer")),
+            CVarKind::Float => text
+                .parse()
+                .map(CVarValue::Float)
+                .map_err(|_| format!("'{text}' is not a number")),
+            CVarKind::Bool => match text.to_ascii_lowercase().as_str() {
+                "1" | "true" | "on" | "yes" => Ok(CVarValue::Bool(true)),
+                "0" | "false" | "off" | "no" => Ok(CVarValue::Bool(false)),
+                _ => Err(format!("'{text}' is not a bool (true/false/1/0)")),
+            },
+            CVarKind::String => Ok(CVarValue::Str(text.to_string())),
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            CVarValue::Int(v) => Some(v as f64),
+            CVarValue::Float(v) => Some(v),
+            CVarValue::Bool(v) => Some(if v { 1.0 } else { 0.0 }),
+            CVarValue::Str(_) => None,
+        }
+    }
+
+    fn to_toml(&self) -> toml::Value {
+        match self {
+            CVarValue::Int(v) => toml::Value::Integer(*v),
+            CVarValue::Float(v) => toml::Value::Float(*v),
+            CVarValue::Bool(v) => toml::Value::Boolean(*v),
+            CVarValue::Str(v) => toml::Value::String(v.clone()),
+        }
+    }
+
+    fn from_toml(kind: CVarKind, value: &toml::Value) -> Option<Self> {
+        match (kind, value) {
+            (CVarKind::Int, toml::Value::Integer(v)) => Some(CVarValue::Int(*v)),
+            (CVarKind::Float, toml::Value::Float(v)) => Some(CVarValue::Float(*v)),
+            (CVarKind::Float, toml::Value::Integer(v)) => Some(CVarValue::Float(*v as f64)),
+            (CVarKind::Bool, toml::Value::Boolean(v)) => Some(CVarValue::Bool(*v)),
+            (CVarKind::String, toml::Value::String(v)) => Some(CVarValue::Str(v.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CVarValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CVarValue::Int(v) => write!(f, "{v}"),
+            CVarValue::Float(v) => write!(f, "{v}"),
+            CVarValue::Bool(v) => write!(f, "{v}"),
+            CVarValue::Str(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CVar {
+    pub name: String,
+    pub value: CVarValue,
+    pub default: CVarValue,
+    pub description: String,
+    pub flags: u32,
+}
+
+impl CVar {
+    pub fn persistent(&self) -> bool {
+        self.flags & CVAR_PERSIST != 0
+    }
+}
+
+#[derive(Default)]
+pub struct CVarRegistry {
+    vars: BTreeMap<String, CVar>,
+    /// Values read from disk for cvars nobody has registered yet
+    /// (e.g. a plugin that hasn't loaded). Applied on registration, kept on save.
+    pending: HashMap<String, toml::Value>,
+    dirty: bool,
+}
+
+impl CVarRegistry {
+    /// Registers `name`. Re-registering with the same type keeps the current value
+    /// (so hot-reloaded plugins don't reset tuning); a type change is refused.
+    pub fn register(
+        &mut self,
+        name: &str,
+        default: CVarValue,
+        description: &str,
+        flags: u32,
+    ) -> Result<(), String> {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(format!("Invalid cvar name '{name}'"));
+        }
+
+        if let Some(existing) = self.vars.get_mut(name) {
+            if existing.default.kind() != default.kind() {
+                return Err(format!(
+                    "Cvar '{name}' already registered as {:?}",
+                    existing.default.kind()
+                ));
+            }
+            existing.default = default;
+            existing.description = description.to_string();
+            existing.flags = flags;
+            return Ok(());
+        }
+
+        let value = match self.pending.remove(name) {
+            Some(stored) => CVarValue::from_toml(default.kind(), &stored).unwrap_or_else(|| {
+                tracing::warn!("⚠️ Ignoring stored value for cvar '{name}': wrong type");
+                default.clone()
+            }),
+            None => default.clone(),
+        };
+
+        self.vars.insert(
+            name.to_string(),
+            CVar {
+                name: name.to_string(),
+                value,
+                default,
+                description: description.to_string(),
+                flags,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CVar> {
+        self.vars.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CVar> {
+        self.vars.values()
+    }
+
+    pub fn set(&mut self, name: &str, value: CVarValue) -> Result<(), String> {
+        let var = self
+            .vars
+            .get_mut(name)
+            .ok_or_else(|| format!("Unknown cvar '{name}'"))?;
+        if var.value.kind() != value.kind() {
+            return Err(format!("Cvar '{name}' is {:?}", var.value.kind()));
+        }
+        if var.value != value {
+            var.value = value;
+            self.dirty |= var.persistent();
+        }
+        Ok(())
+    }
+
+    /// Parses `text` according to the cvar's type and stores it.
+    pub fn set_from_str(&mut self, name: &str, text: &str) -> Result<CVarValue, String> {
+        let kind = self
+            .vars
+            .get(name)
+            .map(|v| v.value.kind())
+            .ok_or_else(|| format!("Unknown cvar '{name}'"))?;
+        let value = CVarValue::parse(kind, text)?;
+        self.set(name, value.clone())?;
+        Ok(value)
+    }
+
+    pub fn reset(&mut self, name: &str) -> Result<(), String> {
+        let default = self
+            .vars
+            .get(name)
+            .map(|v| v.default.clone())
+            .ok_or_else(|| format!("Unknown cvar '{name}'"))?;
+        self.set(name, default)
+    }
+
+    pub fn load_str(&mut self, text: &str) -> Result<(), String> {
+        let table: toml::Table = toml::from_str(text).map_err(|e| e.to_string())?;
+        for (name, stored) in table {
+            match self.vars.get_mut(&name) {
+                Some(var) => match CVarValue::from_toml(var.value.kind(), &stored) {
+                    Some(value) => var.value = value,
+                    None => tracing::warn!("⚠️ Ignoring stored value for cvar '{name}': wrong type"),
+                },
+                None => {
+                    self.pending.insert(name, stored);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn save_string(&self) -> String {
+        let mut table: toml::Table = self
+            .pending
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        for var in self.vars.values() {
+            if var.persistent() && var.value != var.default {
+                table.insert(var.name.clone(), var.value.to_toml());
+            }
+        }
+        toml::to_string(&table).unwrap_or_default()
+    }
+}
+
+/// Runs `f` with the global registry (created on first use).
+pub fn with<R>(f: impl FnOnce(&mut CVarRegistry) -> R) -> R {
+    let mut guard = CVARS.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(CVarRegistry::default))
+}
+
+pub fn register(name: &str, default: CVarValue, description: &str, flags: u32) {
+    if let Err(e) = with(|r| r.register(name, default, description, flags)) {
+        tracing::warn!("⚠️ {e}");
+    }
+}
+
+pub fn get(name: &str) -> Option<CVarValue> {
+    with(|r| r.get(name).map(|v| v.value.clone()))
+}
+
+pub fn get_f32(name: &str) -> Option<f32> {
+    get(name).and_then(|v| v.as_f64()).map(|v| v as f32)
+}
+
+/// Merges values from `path` into the registry. A missing file is not an error.
+pub fn load(path: &Path) {
+    let Ok(text) = fs::read_to_string(path) else { return };
+    if let Err(e) = with(|r| r.load_str(&text)) {
+        tracing::warn!("⚠️ Invalid cvar file {}: {e}", path.display());
+    }
+}
+
+pub fn save(path: &Path) -> Result<(), String> {
+    let text = with(|r| {
+        r.dirty = false;
+        r.save_string()
+    });
+    fs::write(path, text).map_err(|e| format!("Cannot write {}: {e}", path.display()))
+}
+
+/// Writes the cvar file only if a persistent cvar changed since the last save.
+pub fn save_if_dirty(path: &Path) {
+    if with(|r| r.dirty) {
+        if let Err(e) = save(path) {
+            tracing::warn!("⚠️ {e}");
+        }
+    }
+}
+
+// --- HostInterface implementations ---
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn host_cvar_register(
+    kind: u32,
+    flags: u32,
+    name_ptr: *const u8,
+    name_len: usize,
+    default_ptr: *const u8,
+    default_len: usize,
+    desc_ptr: *const u8,
+    desc_len: usize,
+) -> bool {
+    let Some(kind) = CVarKind::from_u32(kind) else { return false };
+    let (Some(name), Some(default)) = (unsafe { logging::str_from_raw(name_ptr, name_len) }, unsafe {
+        logging::str_from_raw(default_ptr, default_len)
+    }) else {
+        return false;
+    };
+    let description = unsafe { logging::str_from_raw(desc_ptr, desc_len) }.unwrap_or("");
+
+    let result = CVarValue::parse(kind, default)
+        .and_then(|default| with(|r| r.register(name, default, description, flags)));
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("⚠️ cvar_register '{name}': {e}");
+            false
+        }
+    }
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_cvar_get_f64(name_ptr: *const u8, name_len: usize, fallback: f64) -> f64 {
+    unsafe { logging::str_from_raw(name_ptr, name_len) }
+        .and_then(get)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(fallback)
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_cvar_get_str(
+    name_ptr: *const u8,
+    name_len: usize,
+    buf: *mut u8,
+    buf_len: usize,
+) -> usize {
+    let Some(value) = unsafe { logging::str_from_raw(name_ptr, name_len) }.and_then(get) else {
+        return 0;
+    };
+    let text = value.to_string();
+    if !buf.is_null() {
+        let n = text.len().min(buf_len);
+        unsafe { std::ptr::copy_nonoverlapping(text.as_ptr(), buf, n) };
+    }
+    text.len()
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_cvar_set(
+    name_ptr: *const u8,
+    name_len: usize,
+    value_ptr: *const u8,
+    value_len: usize,
+) -> bool {
+    let (Some(name), Some(value)) = (unsafe { logging::str_from_raw(name_ptr, name_len) }, unsafe {
+        logging::str_from_raw(value_ptr, value_len)
+    }) else {
+        return false;
+    };
+    with(|r| r.set_from_str(name, value)).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_set_reset_and_persistence() {
+        let mut reg = CVarRegistry::default();
+        reg.load_str("\"player.speed\" = 750.0\n\"later.var\" = 3\n").unwrap();
+
+        reg.register("player.speed", CVarValue::Float(600.0), "", CVAR_PERSIST).unwrap();
+        reg.register("debug.draw", CVarValue::Bool(false), "", 0).unwrap();
+        assert_eq!(reg.get("player.speed").unwrap().value, CVarValue::Float(750.0));
+
+        assert!(reg.set_from_str("debug.draw", "maybe").is_err());
+        assert_eq!(reg.set_from_str("debug.draw", "on"), Ok(CVarValue::Bool(true)));
+        assert!(reg.register("debug.draw", CVarValue::Int(1), "", 0).is_err());
+
+        // Only persistent, non-default values are written; unknown stored ones survive.
+        let saved: toml::Table = toml::from_str(&reg.save_string()).unwrap();
+        assert_eq!(saved.get("player.speed"), Some(&toml::Value::Float(750.0)));
+        assert_eq!(saved.get("later.var"), Some(&toml::Value::Integer(3)));
+        assert!(!saved.contains_key("debug.draw"));
+
+        reg.reset("player.speed").unwrap();
+        let saved: toml::Table = toml::from_str(&reg.save_string()).unwrap();
+        assert!(!saved.contains_key("player.speed"));
+    }
+}
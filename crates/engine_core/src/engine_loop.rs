@@ -3,8 +3,19 @@
 use std::time::Instant;
 
 use crate::plugin_manager::PluginManager;
+use crate::replay::{Player, Recorder};
 use engine_ecs::World;
-use engine_shared::input_types::InputState;
+use engine_shared::input_types::{ActionSet, InputState};
+
+/// Where each simulation tick's input comes from.
+pub enum InputMode {
+    /// The Arbiter's output for the frame.
+    Live,
+    /// Live input, stored frame by frame.
+    Recording(Recorder),
+    /// Frames from a recording; back to `Live` once they run out.
+    Replaying(Player),
+}
 
 /// Encapsulates fixed-timestep simulation bookkeeping (time, accumulator, limits).
 /// Mirrors the original App::run behavior: accumulator, max steps, backlog drop.
@@ -13,6 +24,12 @@ pub struct EngineLoop {
     sim_accumulator: f32,
     sim_dt: f32,
     max_steps_per_frame: u32,
+    /// Simulation steps run so far.
+    tick: u64,
+    /// Frame edges (`InputState::pressed_mask` / `released_mask`) not yet seen by a tick.
+    pending_pressed: ActionSet,
+    pending_released: ActionSet,
+    mode: InputMode,
 }
 
 impl EngineLoop {
@@ -22,6 +39,41 @@ impl EngineLoop {
             sim_accumulator: 0.0,
             sim_dt,
             max_steps_per_frame: 5,
+            tick: 0,
+            pending_pressed: ActionSet::EMPTY,
+            pending_released: ActionSet::EMPTY,
+            mode: InputMode::Live,
+        }
+    }
+
+    /// Records every tick's input from now on.
+    pub fn record(&mut self, recorder: Recorder) {
+        self.mode = InputMode::Recording(recorder);
+    }
+
+    /// Feeds `player`'s frames to the plugins instead of live input, at the
+    /// step they were recorded with.
+    pub fn replay(&mut self, player: Player) {
+        if player.sim_dt() != self.sim_dt {
+            tracing::warn!(
+                "⚠️ Replay was recorded at {} Hz; simulating at that rate instead of {} Hz",
+                1.0 / player.sim_dt(),
+                1.0 / self.sim_dt
+            );
+            self.sim_dt = player.sim_dt();
+        }
+        self.mode = InputMode::Replaying(player);
+    }
+
+    pub fn mode(&self) -> &InputMode {
+        &self.mode
+    }
+
+    /// Writes the recording out, if one is running.
+    pub fn finish_recording(&mut self) -> Result<(), String> {
+        match &self.mode {
+            InputMode::Recording(recorder) => recorder.finish(),
+            _ => Ok(()),
         }
     }
 
@@ -41,18 +93,29 @@ impl EngineLoop {
     /// Runs fixed-timestep simulation steps until the accumulator is caught up
     /// or we hit max_steps_per_frame. If the backlog still remains at the cap,
     /// we drop it, to avoid "chasing" an infinite backlog under heavy load.
+    /// Returns the number of steps run.
+    ///
+    /// `input_state`'s edge masks are this frame's; they are buffered until a
+    /// step runs, and only the first step of a frame sees them.
     pub fn update_simulation(
         &mut self,
         frame_dt: f32,
         world: &mut World,
         plugin_manager: &mut PluginManager,
         input_state: &InputState,
-    ) {
+    ) -> u32 {
         self.sim_accumulator += frame_dt;
+        self.pending_pressed |= input_state.pressed_mask;
+        self.pending_released |= input_state.released_mask;
 
         let mut steps = 0;
         while self.sim_accumulator >= self.sim_dt && steps < self.max_steps_per_frame {
-            plugin_manager.update(world, input_state, self.sim_dt);
+            let mut input = *input_state;
+            input.pressed_mask = std::mem::take(&mut self.pending_pressed);
+            input.released_mask = std::mem::take(&mut self.pending_released);
+            let input = self.tick_input(&input);
+            plugin_manager.update(world, &input, self.sim_dt);
+            self.tick += 1;
             self.sim_accumulator -= self.sim_dt;
             steps += 1;
         }
@@ -61,5 +124,65 @@ impl EngineLoop {
         if steps == self.max_steps_per_frame && self.sim_accumulator >= self.sim_dt {
             self.sim_accumulator = 0.0;
         }
+        steps
+    }
+
+    /// The input plugins get for the current tick, per `mode`.
+    fn tick_input(&mut self, live: &InputState) -> InputState {
+        let replayed = match &mut self.mode {
+            InputMode::Live => return *live,
+            InputMode::Recording(recorder) => return recorder.record(self.tick, live),
+            InputMode::Replaying(player) => player.next_input(),
+        };
+        replayed.unwrap_or_else(|| {
+            tracing::info!("⏹️ Replay finished after {} ticks; back to live input", self.tick);
+            self.mode = InputMode::Live;
+            *live
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_shared::ActionId;
+    use std::path::PathBuf;
+
+    #[test]
+    fn edges_wait_for_a_tick_and_reach_only_the_first() {
+        let mut world = World::new();
+        let mut plugins = PluginManager {
+            plugins: Vec::new(),
+            rejected: Vec::new(),
+        };
+        let mut engine_loop = EngineLoop::new(0.25);
+        engine_loop.record(Recorder::start(PathBuf::new(), 0.25, &world, &mut plugins));
+
+        let mut frame = |dt: f32, held: &[ActionId], previous: &[ActionId]| {
+            let mut state = InputState {
+                digital_mask: held.iter().copied().collect(),
+                ..Default::default()
+            };
+            state.set_edges_from(&InputState {
+                digital_mask: previous.iter().copied().collect(),
+                ..Default::default()
+            });
+            engine_loop.update_simulation(dt, &mut world, &mut plugins, &state)
+        };
+        // Tapped and let go before any tick ran, then a frame that catches up three ticks.
+        assert_eq!(frame(0.125, &[1], &[]), 0);
+        assert_eq!(frame(0.0, &[], &[1]), 0);
+        assert_eq!(frame(0.625, &[100], &[]), 3);
+
+        let InputMode::Recording(recorder) = engine_loop.mode() else { panic!("not recording") };
+        let ids = |set: ActionSet| set.iter().collect::<Vec<_>>();
+        let edges: Vec<_> = recorder
+            .frames()
+            .iter()
+            .map(|f| (ids(f.actions), ids(f.pressed), ids(f.released)))
+            .collect();
+        assert_eq!(edges[0], (vec![100], vec![1, 100], vec![1]));
+        assert_eq!(edges[1], (vec![100], vec![], vec![]));
+        assert_eq!(edges[2], edges[1]);
     }
 }
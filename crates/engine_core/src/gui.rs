@@ -7,6 +7,15 @@ pub struct GuiSystem {
     // State is an Option because it requires the Window to be created first
     state: Option<egui_winit::State>,
     pub show_inspector: bool,
+    pub show_console: bool,
+    pub show_plugin_panels: bool,
+    pub show_bindings: bool,
+}
+
+impl Default for GuiSystem {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GuiSystem {
@@ -15,6 +24,9 @@ impl GuiSystem {
             ctx: Context::default(),
             state: None,
             show_inspector: true,
+            show_console: false,
+            show_plugin_panels: true,
+            show_bindings: false,
         }
     }
 
@@ -40,10 +52,27 @@ impl GuiSystem {
         self.ctx.wants_keyboard_input()
     }
 
+    /// The cursor is over (or dragging) an egui window.
+    pub fn wants_pointer_input(&self) -> bool {
+        self.ctx.wants_pointer_input()
+    }
+
     pub fn toggle_inspector(&mut self) {
         self.show_inspector = !self.show_inspector;
     }
 
+    pub fn toggle_console(&mut self) {
+        self.show_console = !self.show_console;
+    }
+
+    pub fn toggle_bindings(&mut self) {
+        self.show_bindings = !self.show_bindings;
+    }
+
+    pub fn toggle_plugin_panels(&mut self) {
+        self.show_plugin_panels = !self.show_plugin_panels;
+    }
+
     /// Prepare the frame, run the UI closure, and output draw data
     pub fn draw(
         &mut self,
@@ -1,44 +1,48 @@
 // crates/engine_core/src/host.rs
+use crate::commands;
+use crate::cvars;
 use crate::input;
+use crate::logging;
+use crate::message_bus;
+use crate::prefabs;
 use engine_ecs::World;
-use engine_shared::{CEnemy, CSprite, CTransform, HostContext, HostInterface};
+use engine_shared::{HostContext, HostInterface};
 use glam::Vec2;
 
 /// The implementation of the spawn function provided to the plugin.
 extern "C" fn host_spawn_enemy(ctx: *mut HostContext, x: f32, y: f32) {
     if ctx.is_null() {
-        eprintln!("host_spawn_enemy called with null HostContext");
+        tracing::error!("host_spawn_enemy called with null HostContext");
         return;
     }
 
-    unsafe {
-        // Cast HostContext back to World.
-        let world = &mut *(ctx as *mut World);
-
-        let enemy = world.spawn();
-        world.add_component(
-            enemy,
-            CTransform {
-                pos: Vec2::new(x, y),
-                scale: Vec2::splat(0.8),
-                rotation: 0.0,
-            },
-        );
-        world.add_component(enemy, CEnemy { speed: 100.0 });
-        world.add_component(
-            enemy,
-            CSprite {
-                color: glam::Vec4::new(1.0, 0.0, 0.0, 1.0),
-            },
-        );
-    }
+    // Cast HostContext back to World.
+    let world = unsafe { &mut *(ctx as *mut World) };
+    prefabs::spawn(world, "enemy", Vec2::new(x, y));
 }
 
 /// Helper to construct the interface struct
 pub fn create_interface() -> HostInterface {
     HostInterface {
         get_action_id: input::host_get_action_id,
-        log: None,
+        register_action: input::host_register_action,
+        get_axis_id: input::host_get_axis_id,
+        bind_default_key: input::host_bind_default_key,
+        bind_context_key: input::ffi::host_bind_context_key,
+        push_input_context: input::contexts::host_push_input_context,
+        pop_input_context: input::contexts::host_pop_input_context,
+        submit_movement: input::injected::host_submit_movement,
+        submit_action: input::injected::host_submit_action,
+        log: logging::host_log,
         spawn_enemy: host_spawn_enemy,
+        cvar_register: cvars::host_cvar_register,
+        cvar_get_f64: cvars::host_cvar_get_f64,
+        cvar_get_str: cvars::host_cvar_get_str,
+        cvar_set: cvars::host_cvar_set,
+        register_command: commands::host_register_command,
+        bus_publish: message_bus::host_bus_publish,
+        bus_subscribe: message_bus::host_bus_subscribe,
+        bus_unsubscribe: message_bus::host_bus_unsubscribe,
+        bus_receive: message_bus::host_bus_receive,
     }
 }
\ No newline at end of file
@@ -4,22 +4,26 @@ use glam::Vec2;
 
 use engine_shared::input_types::{
     ActionId,
+    ActionSet,
     InputState,
     PriorityLayer,
-    canonical_actions,
 };
 
 pub mod channels {
     use engine_shared::input_types::canonical_actions::*;
-    pub const MASK_MOVE: u64 =
-        (1 << MOVE_UP) | (1 << MOVE_DOWN) | (1 << MOVE_LEFT) | (1 << MOVE_RIGHT);
-    pub const MASK_ALL: u64 = !0;
+    use engine_shared::input_types::ActionSet;
+    pub const MASK_MOVE: ActionSet = ActionSet::EMPTY
+        .with(MOVE_UP)
+        .with(MOVE_DOWN)
+        .with(MOVE_LEFT)
+        .with(MOVE_RIGHT);
+    pub const MASK_ALL: ActionSet = ActionSet::ALL;
 }
 
 #[derive(Clone, Copy)]
 pub struct LayerConfig {
     pub layer: PriorityLayer,
-    pub allowed_mask_when_active: u64,
+    pub allowed_mask_when_active: ActionSet,
     pub lock_on_activation: bool,
     pub lock_frames_on_activation: u32,
 }
@@ -27,7 +31,7 @@ pub struct LayerConfig {
 #[derive(Default, Clone, Copy)]
 pub struct LayerRuntimeState {
     pub lock_frames_remaining: u32,
-    pub locked_permission_mask: u64,
+    pub locked_permission_mask: ActionSet,
 }
 
 pub struct MovementSignal {
@@ -89,6 +93,9 @@ impl Arbiter {
         self.action_signals.push(signal);
     }
 
+    /// A layer that is active (or still locked) restricts the layers below it
+    /// to its `allowed_mask_when_active`; its own signals go through, so a
+    /// Reflex knockback isn't vetoed by the Reflex mask.
     pub fn resolve(&mut self) -> InputState {
         let mut state = InputState::default();
 
@@ -98,9 +105,12 @@ impl Arbiter {
             .map(|cfg| self.layer_has_activity(cfg.layer))
             .collect();
 
-        let mut global_permission: u64 = channels::MASK_ALL;
+        // What each layer may still drive, given the layers above it.
+        let mut permissions = Vec::with_capacity(self.layer_configs.len());
+        let mut global_permission = channels::MASK_ALL;
 
         for (idx, config) in self.layer_configs.iter().enumerate() {
+            permissions.push((config.layer, global_permission));
             let runtime = &mut self.layer_state[idx];
             let layer_active = layer_activities[idx];
 
@@ -121,18 +131,24 @@ impl Arbiter {
             global_permission &= layer_mask;
         }
 
-        let final_vector = self.resolve_movement(global_permission);
+        // Layers without a config get what is left below every configured one.
+        let permission_for = |layer: PriorityLayer| {
+            permissions
+                .iter()
+                .find(|(l, _)| *l == layer)
+                .map_or(global_permission, |&(_, mask)| mask)
+        };
+
+        let final_vector = self.resolve_movement(permission_for);
         state.analog_axes[0] = final_vector.x;
         state.analog_axes[1] = final_vector.y;
 
-        let mut digital_requests: u64 = 0;
-        for sig in &self.action_signals {
-            let bit_index = sig.action_id as u32;
-            if bit_index < 64 && sig.active {
-                digital_requests |= 1u64 << bit_index;
-            }
-        }
-        state.digital_mask = digital_requests & global_permission;
+        state.digital_mask = self
+            .action_signals
+            .iter()
+            .filter(|sig| sig.active && permission_for(sig.layer).contains(sig.action_id))
+            .map(|sig| sig.action_id)
+            .collect();
 
         state
     }
@@ -147,17 +163,9 @@ impl Arbiter {
                 .any(|s| s.layer == layer && s.active)
     }
 
-    fn resolve_movement(&self, global_permission: u64) -> Vec2 {
+    fn resolve_movement(&self, permission_for: impl Fn(PriorityLayer) -> ActionSet) -> Vec2 {
         use engine_shared::input_types::canonical_actions::*;
 
-        // [DEBUG LOGGING ACTIVE] 
-        // Zero Tolerance Suppression
-        if (global_permission & channels::MASK_MOVE) == 0 {
-            // IF THIS PRINTS, YOUR INPUT IS LOCKED BY THE ARBITER
-            println!("[DEBUG] Input Suppressed by Arbiter Mask!");
-            return Vec2::ZERO;
-        }
-
         if self.move_signals.is_empty() {
             return Vec2::ZERO;
         }
@@ -175,6 +183,15 @@ impl Arbiter {
         }
 
         let Some(layer) = winning_layer else { return Vec2::ZERO; };
+        let global_permission = permission_for(layer);
+
+        // [DEBUG LOGGING ACTIVE]
+        // Zero Tolerance Suppression
+        if (global_permission & channels::MASK_MOVE).is_empty() {
+            // IF THIS PRINTS, YOUR INPUT IS LOCKED BY THE ARBITER
+            tracing::debug!("Input Suppressed by Arbiter Mask!");
+            return Vec2::ZERO;
+        }
 
         let mut raw = Vec2::ZERO;
         for sig in &self.move_signals {
@@ -185,16 +202,16 @@ impl Arbiter {
 
         let mut final_vec = raw;
 
-        if (global_permission & (1 << MOVE_RIGHT)) == 0 && final_vec.x > 0.0 {
+        if !global_permission.contains(MOVE_RIGHT) && final_vec.x > 0.0 {
             final_vec.x = 0.0;
         }
-        if (global_permission & (1 << MOVE_LEFT)) == 0 && final_vec.x < 0.0 {
+        if !global_permission.contains(MOVE_LEFT) && final_vec.x < 0.0 {
             final_vec.x = 0.0;
         }
-        if (global_permission & (1 << MOVE_UP)) == 0 && final_vec.y > 0.0 {
+        if !global_permission.contains(MOVE_UP) && final_vec.y > 0.0 {
             final_vec.y = 0.0;
         }
-        if (global_permission & (1 << MOVE_DOWN)) == 0 && final_vec.y < 0.0 {
+        if !global_permission.contains(MOVE_DOWN) && final_vec.y < 0.0 {
             final_vec.y = 0.0;
         }
 
@@ -212,4 +229,62 @@ impl Arbiter {
 
         final_vec
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::config::InputDefaults;
+    use crate::input::injected::{self, InjectedSignals, PENDING_TEST_LOCK};
+    use engine_shared::input_types::canonical_actions::MOVE_UP;
+
+    const FRAME_DT: f32 = 0.25;
+
+    /// One frame of the player holding "up" on Control, plus whatever is injected.
+    fn frame(arbiter: &mut Arbiter, injected: &mut InjectedSignals) -> InputState {
+        arbiter.clear();
+        arbiter.add_movement(MovementSignal { layer: PriorityLayer::Control, vector: Vec2::Y, weight: 1.0 });
+        arbiter.add_action(ActionSignal { layer: PriorityLayer::Control, action_id: MOVE_UP, active: true });
+        injected.feed_arbiter(arbiter);
+        let state = arbiter.resolve();
+        injected.end_frame(1, FRAME_DT);
+        state
+    }
+
+    /// Horizontal movement on `layer`, as a plugin would submit it.
+    fn inject_for_two_frames(layer: PriorityLayer, x: f32) {
+        assert!(injected::host_submit_movement(layer as u32, x, 0.0, 1.0, 2.0 * FRAME_DT));
+    }
+
+    fn control_gets_through(state: &InputState) -> bool {
+        state.is_active(MOVE_UP) && state.analog_axes[1] == 1.0
+    }
+
+    #[test]
+    fn injected_signals_lock_out_lower_layers_until_they_expire() {
+        let _pending = PENDING_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let layers = InputDefaults::default_arbiter_layers();
+        let reflex_lock = layers[0].lock_frames_on_activation as usize;
+        let mut arbiter = Arbiter::new(layers, 0.1);
+        let mut injected = InjectedSignals::default();
+
+        // Cutscene has no lock: Control is back the frame after the walk ends.
+        inject_for_two_frames(PriorityLayer::Cutscene, 1.0);
+        for _ in 0..2 {
+            let state = frame(&mut arbiter, &mut injected);
+            assert_eq!((state.analog_axes[0], state.analog_axes[1]), (1.0, 0.0));
+            assert!(!state.is_active(MOVE_UP));
+        }
+        assert!(injected.active().is_empty());
+        assert!(control_gets_through(&frame(&mut arbiter, &mut injected)));
+
+        // Reflex stays locked for `lock_frames_on_activation` resolves after the one
+        // that activated it, long after the two-frame knockback itself.
+        inject_for_two_frames(PriorityLayer::Reflex, -1.0);
+        for n in 0..=reflex_lock {
+            assert!(!control_gets_through(&frame(&mut arbiter, &mut injected)), "frame {n}");
+        }
+        assert!(injected.active().is_empty());
+        assert!(control_gets_through(&frame(&mut arbiter, &mut injected)));
+    }
+}
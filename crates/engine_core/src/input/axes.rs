@@ -0,0 +1,229 @@
+// crates/engine_core/src/input/axes.rs
+//! Named axes beyond movement (Aim, Zoom, Throttle), declared in the input
+//! profile and written into `InputState::analog_axes` from
+//! `axes::FIRST_NAMED` on (plugins find them with `get_axis_id`).
+//!
+//! ```toml
+//! [axes.Zoom]
+//! x = ["Mouse.Wheel", "Minus/Equal"]
+//!
+//! [axes.Aim]
+//! x = ["Pad.RightStickX", "Mouse.DeltaX"]
+//! y = ["Pad.RightStickY", "Mouse.DeltaY"]
+//! deadzone = 0.1
+//! sensitivity = 2.0
+//! invert_y = true
+//! curve = "quadratic"
+//! ```
+//!
+//! An axis with `y` is 2D and takes two slots. Each component sums its
+//! sources: key pairs ("KeyA/KeyD", any two bindable inputs, -1 and +1), raw
+//! stick axes, cursor movement in pixels (+Y up) and wheel lines since the last
+//! tick. The sum goes through the deadzone (rescaled to start from zero at its
+//! edge), the curve, the sensitivity and the inversion, in that order.
+
+use std::collections::HashSet;
+
+use engine_shared::input_types::InputState;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::input::gamepad::{self, Gamepads, PadAxis};
+use crate::input::triggers::held_form;
+use crate::input::{Binding, InputMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AxisSource {
+    /// -1 while the first input is held, +1 while the second is ("KeyA/KeyD").
+    Pair(Binding, Binding),
+    Pad(PadAxis),
+    /// Cursor movement since the last tick, in pixels ("Mouse.DeltaX", "Mouse.DeltaY").
+    MouseDeltaX,
+    MouseDeltaY,
+    /// Wheel lines since the last tick ("Mouse.Wheel").
+    Wheel,
+}
+
+const MOUSE_SOURCES: &[(&str, AxisSource)] = &[
+    ("Mouse.DeltaX", AxisSource::MouseDeltaX),
+    ("Mouse.DeltaY", AxisSource::MouseDeltaY),
+    ("Mouse.Wheel", AxisSource::Wheel),
+];
+
+impl AxisSource {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if let Some((negative, positive)) = name.split_once('/') {
+            return Some(AxisSource::Pair(Binding::from_name(negative)?, Binding::from_name(positive)?));
+        }
+        gamepad::axis_from_name(name).map(AxisSource::Pad).or_else(|| {
+            MOUSE_SOURCES
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|&(_, source)| source)
+        })
+    }
+
+    /// The name `from_name` reads back; `None` for keys without one.
+    pub fn name(self) -> Option<String> {
+        match self {
+            AxisSource::Pair(negative, positive) => Some(format!("{}/{}", negative.name()?, positive.name()?)),
+            AxisSource::Pad(axis) => Some(gamepad::axis_name(axis).to_string()),
+            other => MOUSE_SOURCES
+                .iter()
+                .find(|&&(_, s)| s == other)
+                .map(|&(name, _)| name.to_string()),
+        }
+    }
+
+    fn read(self, inputs: &AxisInputs) -> f32 {
+        let held = |b: Binding| inputs.held.contains(&held_form(b));
+        match self {
+            AxisSource::Pair(negative, positive) => held(positive) as i32 as f32 - held(negative) as i32 as f32,
+            AxisSource::Pad(axis) => inputs.gamepads.axis_value(axis),
+            AxisSource::MouseDeltaX => inputs.motion.x,
+            AxisSource::MouseDeltaY => inputs.motion.y,
+            AxisSource::Wheel => inputs.scroll,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AxisCurve {
+    #[default]
+    Linear,
+    Quadratic,
+    Cubic,
+}
+
+impl AxisCurve {
+    fn apply(self, magnitude: f32) -> f32 {
+        match self {
+            AxisCurve::Linear => magnitude,
+            AxisCurve::Quadratic => magnitude * magnitude,
+            AxisCurve::Cubic => magnitude * magnitude * magnitude,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxisSettings {
+    pub deadzone: f32,
+    pub sensitivity: f32,
+    pub curve: AxisCurve,
+    /// Flips the 1D value, or X of a 2D axis.
+    pub invert_x: bool,
+    pub invert_y: bool,
+}
+
+impl Default for AxisSettings {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.0,
+            sensitivity: 1.0,
+            curve: AxisCurve::Linear,
+            invert_x: false,
+            invert_y: false,
+        }
+    }
+}
+
+impl AxisSettings {
+    pub fn apply(&self, raw: f32, invert: bool) -> f32 {
+        let magnitude = raw.abs();
+        if magnitude <= self.deadzone || self.deadzone >= 1.0 {
+            return 0.0;
+        }
+        let scaled = (magnitude - self.deadzone) / (1.0 - self.deadzone);
+        let value = raw.signum() * self.curve.apply(scaled) * self.sensitivity;
+        if invert {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+/// What a named axis reads: sources for the value (or X), and for Y on 2D axes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AxisBinding {
+    pub x: Vec<AxisSource>,
+    pub y: Vec<AxisSource>,
+    pub settings: AxisSettings,
+}
+
+/// This frame's raw input, as the axis sources read it.
+pub struct AxisInputs<'a> {
+    /// Held inputs, keys as `Binding::Key` (see `InputPoller::held_inputs`).
+    pub held: &'a HashSet<Binding>,
+    pub gamepads: &'a Gamepads,
+    pub scroll: f32,
+    pub motion: Vec2,
+}
+
+/// Writes every bound named axis into `state`.
+pub fn write_axes(state: &mut InputState, input_map: &InputMap, inputs: &AxisInputs) {
+    for (id, binding) in input_map.axis_bindings() {
+        let sum = |sources: &[AxisSource]| sources.iter().map(|s| s.read(inputs)).sum::<f32>();
+        let settings = &binding.settings;
+        let x = id as usize;
+        if let Some(slot) = state.analog_axes.get_mut(x) {
+            *slot = settings.apply(sum(&binding.x), settings.invert_x);
+        }
+        if !binding.y.is_empty() {
+            if let Some(slot) = state.analog_axes.get_mut(x + 1) {
+                *slot = settings.apply(sum(&binding.y), settings.invert_y);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::keyboard::KeyCode;
+
+    #[test]
+    fn axes_sum_their_sources_and_shape_the_result() {
+        let pair = AxisSource::from_name("physical.keya/KeyD").unwrap();
+        assert_eq!(pair.name().as_deref(), Some("Physical.KeyA/KeyD"));
+        assert_eq!(AxisSource::from_name("mouse.deltay"), Some(AxisSource::MouseDeltaY));
+        assert_eq!(AxisSource::from_name("KeyA/Nope"), None);
+
+        let mut map = InputMap::default();
+        map.bind_axis(8, AxisBinding {
+            x: vec![pair, AxisSource::Wheel],
+            y: vec![AxisSource::MouseDeltaY],
+            settings: AxisSettings {
+                deadzone: 0.5,
+                curve: AxisCurve::Quadratic,
+                sensitivity: 2.0,
+                invert_y: true,
+                ..Default::default()
+            },
+        });
+        map.bind_axis(10, AxisBinding {
+            x: vec![AxisSource::Pad(PadAxis::RightStickX)],
+            ..Default::default()
+        });
+
+        let held: HashSet<_> = [Binding::Key(KeyCode::KeyA)].into_iter().collect();
+        let mut pads = Gamepads::default();
+        pads.connect(0, "Pad");
+        pads.set_axis(0, PadAxis::RightStickX, -0.25);
+        let inputs = AxisInputs {
+            held: &held,
+            gamepads: &pads,
+            scroll: 3.0,
+            motion: Vec2::new(0.0, 0.25),
+        };
+        let mut state = InputState::default();
+        write_axes(&mut state, &map, &inputs);
+
+        // x: -1 + 3 = 2 -> (2 - 0.5) / 0.5 = 3 -> 9 -> 18; y: inside the deadzone.
+        assert_eq!(state.get_axis_2d(8), Vec2::new(18.0, 0.0));
+        assert_eq!(state.get_axis(10), -0.25);
+    }
+}
@@ -1,8 +1,9 @@
 // crates/engine_core/src/input/config.rs
 
 use crate::input::arbiter::{channels, LayerConfig};
+use crate::input::gamepad::PadButton;
 use crate::input::{ActionRegistry, InputMap};
-use engine_shared::input_types::{canonical_actions, PriorityLayer};
+use engine_shared::input_types::{canonical_actions, ActionSet, PriorityLayer};
 use winit::keyboard::KeyCode;
 
 /// Centralized defaults for input configuration.
@@ -10,16 +11,17 @@ use winit::keyboard::KeyCode;
 pub struct InputDefaults;
 
 impl InputDefaults {
-    /// Registers canonical movement actions and their default key bindings.
+    /// Registers canonical movement actions and their default key and D-pad bindings.
     ///
     /// IMPORTANT: We register movement actions first so their numeric IDs
     /// match canonical_actions::{MOVE_UP, MOVE_DOWN, MOVE_LEFT, MOVE_RIGHT}.
     pub fn setup(registry: &mut ActionRegistry, input_map: &mut InputMap) {
         // 1. Register canonical movement actions
-        let move_up = registry.register("MoveUp");
-        let move_down = registry.register("MoveDown");
-        let move_left = registry.register("MoveLeft");
-        let move_right = registry.register("MoveRight");
+        let mut register = |name| registry.register(name).expect("movement actions are registered first");
+        let move_up = register("MoveUp");
+        let move_down = register("MoveDown");
+        let move_left = register("MoveLeft");
+        let move_right = register("MoveRight");
 
         // Verify alignment with canonical IDs (debug-only to avoid panics in Release).
         debug_assert_eq!(move_up, canonical_actions::MOVE_UP);
@@ -32,6 +34,12 @@ impl InputDefaults {
         input_map.bind_logical(KeyCode::KeyS, move_down);
         input_map.bind_logical(KeyCode::KeyA, move_left);
         input_map.bind_logical(KeyCode::KeyD, move_right);
+
+        // 3. D-pad mirrors WASD; the left stick feeds analog movement instead.
+        input_map.bind_button(PadButton::DPadUp, move_up);
+        input_map.bind_button(PadButton::DPadDown, move_down);
+        input_map.bind_button(PadButton::DPadLeft, move_left);
+        input_map.bind_button(PadButton::DPadRight, move_right);
     }
 
     /// Default Arbiter layer configuration, matching the Reflex / Cutscene /
@@ -40,13 +48,13 @@ impl InputDefaults {
         vec![
             LayerConfig {
                 layer: PriorityLayer::Reflex,
-                allowed_mask_when_active: 0, // Block everything
+                allowed_mask_when_active: ActionSet::EMPTY, // Block everything
                 lock_on_activation: true,
                 lock_frames_on_activation: 30,
             },
             LayerConfig {
                 layer: PriorityLayer::Cutscene,
-                allowed_mask_when_active: 0,
+                allowed_mask_when_active: ActionSet::EMPTY,
                 lock_on_activation: false,
                 lock_frames_on_activation: 0,
             },
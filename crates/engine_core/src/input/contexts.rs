@@ -0,0 +1,249 @@
+// crates/engine_core/src/input/contexts.rs
+//! Input contexts: named `InputMap`s pushed on a stack above gameplay (the main
+//! map), e.g. a pause menu pushes "Menu", which binds Escape and the arrows.
+//!
+//! Each frame the stack is read top-down. A context gets the held inputs no
+//! context above it took, and takes the ones it binds (plainly or in a
+//! trigger), so they never reach the contexts below or gameplay. A context
+//! with `blocks_keyboard` takes every key; the engine pushes `TEXT_ENTRY`, one
+//! such context, while an egui text field has focus. Inputs held when the
+//! stack changes are hidden from everyone until released, so the Escape that
+//! opened a menu doesn't also close it (or reach gameplay once it's closed).
+//!
+//! Contexts are declared in the input profile (`[contexts.Menu]`), by the
+//! engine, or on first use. The host and plugins push and pop them by name
+//! (`HostInterface::push_input_context`, the `push_context` console command);
+//! requests are applied at the start of the next frame.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::input::gamepad::Gamepads;
+use crate::input::poller::InputPoller;
+use crate::input::{Arbiter, Binding, InputMap, TriggerStage};
+
+/// Pushed by the engine while the GUI has keyboard focus.
+pub const TEXT_ENTRY: &str = "TextEntry";
+
+#[derive(Default, Clone)]
+pub struct ContextDef {
+    pub map: InputMap,
+    /// Takes every key, bound or not.
+    pub blocks_keyboard: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ContextRequest {
+    Push(String),
+    /// Removes the topmost entry with this name.
+    Pop(String),
+}
+
+/// Requests from FFI callbacks and console commands, which can't reach the stack.
+static PENDING: Mutex<Vec<ContextRequest>> = Mutex::new(Vec::new());
+
+pub fn request(request: ContextRequest) {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner()).push(request);
+}
+
+pub fn take_requests() -> Vec<ContextRequest> {
+    std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+unsafe fn name_from_raw<'a>(ptr: *const u8, len: usize) -> Option<&'a str> {
+    if ptr.is_null() || len == 0 {
+        return None;
+    }
+    std::str::from_utf8(std::slice::from_raw_parts(ptr, len)).ok()
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_push_input_context(name_ptr: *const u8, name_len: usize) -> bool {
+    let Some(name) = (unsafe { name_from_raw(name_ptr, name_len) }) else {
+        return false;
+    };
+    request(ContextRequest::Push(name.to_string()));
+    true
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_pop_input_context(name_ptr: *const u8, name_len: usize) -> bool {
+    let Some(name) = (unsafe { name_from_raw(name_ptr, name_len) }) else {
+        return false;
+    };
+    request(ContextRequest::Pop(name.to_string()));
+    true
+}
+
+#[derive(Default)]
+pub struct InputContexts {
+    defs: BTreeMap<String, ContextDef>,
+    /// Active contexts, bottom first.
+    stack: Vec<String>,
+    /// One trigger stage per context, so their timers don't mix.
+    triggers: HashMap<String, TriggerStage>,
+    /// Held when the stack last changed; hidden until released.
+    held_at_switch: HashSet<Binding>,
+    switched: bool,
+}
+
+impl InputContexts {
+    pub fn definitions(&self) -> &BTreeMap<String, ContextDef> {
+        &self.defs
+    }
+
+    /// Replaces every context's bindings (profile reload); the stack stays.
+    pub fn set_definitions(&mut self, defs: BTreeMap<String, ContextDef>) {
+        self.defs = defs;
+        self.triggers.retain(|name, _| self.defs.contains_key(name));
+    }
+
+    /// The named context's definition, created empty if needed.
+    pub fn def_mut(&mut self, name: &str) -> &mut ContextDef {
+        self.defs.entry(name.to_string()).or_default()
+    }
+
+    /// Active contexts, bottom first.
+    pub fn stack(&self) -> &[String] {
+        &self.stack
+    }
+
+    pub fn is_active(&self, name: &str) -> bool {
+        self.stack.iter().any(|n| n == name)
+    }
+
+    pub fn push(&mut self, name: &str) {
+        if !self.defs.contains_key(name) {
+            tracing::debug!("Input context '{name}' pushed without bindings");
+        }
+        self.stack.push(name.to_string());
+        self.switched = true;
+        tracing::info!("⌨️ Input context pushed: {}", self.stack.join(" > "));
+    }
+
+    /// Removes the topmost `name`; false if it isn't on the stack.
+    pub fn pop(&mut self, name: &str) -> bool {
+        let Some(index) = self.stack.iter().rposition(|n| n == name) else {
+            tracing::warn!("⚠️ Input context '{name}' popped but not active");
+            return false;
+        };
+        self.stack.remove(index);
+        self.switched = true;
+        tracing::info!("⌨️ Input context popped: {name}");
+        true
+    }
+
+    /// Pushes or pops `name` so it is active exactly when `active` is.
+    pub fn set_active(&mut self, name: &str, active: bool) {
+        match (active, self.is_active(name)) {
+            (true, false) => self.push(name),
+            (false, true) => {
+                self.pop(name);
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies the push and pop requests queued since the last frame.
+    pub fn apply_requests(&mut self) {
+        for request in take_requests() {
+            match request {
+                ContextRequest::Push(name) => self.push(&name),
+                ContextRequest::Pop(name) => {
+                    self.pop(&name);
+                }
+            }
+        }
+    }
+
+    /// Feeds the active contexts' bindings to the Arbiter, top-down, and
+    /// returns the held inputs they took, plus those held since the stack last
+    /// changed (gameplay must skip them all).
+    pub fn feed_arbiter(
+        &mut self,
+        arbiter: &mut Arbiter,
+        poller: &InputPoller,
+        gamepads: &Gamepads,
+        held: &HashSet<Binding>,
+        now: Instant,
+    ) -> HashSet<Binding> {
+        if std::mem::take(&mut self.switched) {
+            self.held_at_switch = held.clone();
+        }
+        self.held_at_switch.retain(|input| held.contains(input));
+
+        let mut claimed = self.held_at_switch.clone();
+        for name in self.stack.iter().rev() {
+            let Some(def) = self.defs.get(name) else { continue };
+            let visible: HashSet<Binding> = held.difference(&claimed).copied().collect();
+
+            let stage = self.triggers.entry(name.clone()).or_default();
+            stage.update(&def.map, &visible, now);
+            let skip: HashSet<Binding> = claimed.union(stage.consumed()).copied().collect();
+            poller.feed_arbiter(arbiter, &def.map, &skip);
+            gamepads.feed_buttons(arbiter, &def.map, &skip);
+            stage.feed_arbiter(arbiter);
+
+            claimed.extend(
+                visible
+                    .into_iter()
+                    .filter(|&input| def.map.binds(input) || (def.blocks_keyboard && matches!(input, Binding::Key(_)))),
+            );
+        }
+        claimed
+    }
+
+    /// End of frame for the contexts' trigger stages (see `TriggerStage::end_frame`).
+    pub fn end_frame(&mut self, ticks_ran: u32) {
+        for stage in self.triggers.values_mut() {
+            stage.end_frame(ticks_ran);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::keyboard::KeyCode;
+
+    fn feed(contexts: &mut InputContexts, keys: &[KeyCode]) -> Vec<Binding> {
+        let held: HashSet<_> = keys.iter().map(|&k| Binding::Key(k)).collect();
+        let mut arbiter = Arbiter::default();
+        let claimed = contexts.feed_arbiter(&mut arbiter, &InputPoller::new(), &Gamepads::default(), &held, Instant::now());
+        let mut claimed: Vec<_> = claimed.into_iter().collect();
+        claimed.sort_by_key(|b| b.name());
+        claimed
+    }
+
+    #[test]
+    fn contexts_take_their_inputs_top_down() {
+        let mut menu = InputMap::default();
+        menu.bind(Binding::Key(KeyCode::Escape), 10);
+        menu.bind(Binding::Physical(KeyCode::ArrowUp), 11);
+        let mut defs = BTreeMap::new();
+        defs.insert("Menu".to_string(), ContextDef { map: menu, blocks_keyboard: false });
+        defs.insert(TEXT_ENTRY.to_string(), ContextDef { map: InputMap::default(), blocks_keyboard: true });
+        let mut contexts = InputContexts::default();
+        contexts.set_definitions(defs);
+        let all = [KeyCode::Escape, KeyCode::ArrowUp, KeyCode::KeyW];
+
+        // The Escape held while the menu opens stays hidden until released.
+        assert!(host_push_input_context("Menu".as_ptr(), 4));
+        contexts.apply_requests();
+        assert_eq!(feed(&mut contexts, &[KeyCode::Escape]), [Binding::Key(KeyCode::Escape)]);
+        assert!(feed(&mut contexts, &[]).is_empty());
+        assert_eq!(feed(&mut contexts, &all), [Binding::Key(KeyCode::ArrowUp), Binding::Key(KeyCode::Escape)]);
+
+        contexts.set_active(TEXT_ENTRY, true);
+        assert_eq!(contexts.stack(), ["Menu", TEXT_ENTRY]);
+        feed(&mut contexts, &[]);
+        assert_eq!(feed(&mut contexts, &all).len(), 3);
+
+        assert!(!contexts.pop("Nope"));
+        contexts.set_active(TEXT_ENTRY, false);
+        assert!(contexts.pop("Menu"));
+        feed(&mut contexts, &[]);
+        assert!(feed(&mut contexts, &all).is_empty());
+    }
+}
@@ -1,10 +1,49 @@
 // crates/engine_core/src/input/ffi.rs
 use std::sync::{Mutex, OnceLock};
-use engine_shared::{ActionId, ACTION_NOT_FOUND};
+use engine_shared::{ActionId, AxisId, ACTION_NOT_FOUND, AXIS_NOT_FOUND};
+use super::map::BindingSpec;
 use super::registry::ActionRegistry;
 
 pub static GLOBAL_REGISTRY: OnceLock<Mutex<ActionRegistry>> = OnceLock::new();
 
+/// Digital actions are bits of `InputState::digital_mask`.
+pub use engine_shared::input_types::MAX_ACTIONS;
+
+/// A default binding suggested by a plugin, for gameplay or an input context.
+#[derive(Debug, Clone)]
+pub struct PendingBinding {
+    pub context: Option<String>,
+    pub action: ActionId,
+    pub binding: BindingSpec,
+}
+
+/// Default bindings suggested by plugins, waiting for PlatformRunner to apply
+/// them to the live `InputMap` (which the FFI callbacks can't reach).
+static PENDING_BINDINGS: Mutex<Vec<PendingBinding>> = Mutex::new(Vec::new());
+
+fn global_registry() -> &'static Mutex<ActionRegistry> {
+    GLOBAL_REGISTRY.get_or_init(|| Mutex::new(ActionRegistry::default()))
+}
+
+pub fn queue_default_binding(pending: PendingBinding) {
+    PENDING_BINDINGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(pending);
+}
+
+pub fn take_pending_bindings() -> Vec<PendingBinding> {
+    std::mem::take(&mut *PENDING_BINDINGS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+unsafe fn name_from_raw<'a>(ptr: *const u8, len: usize) -> Option<&'a str> {
+    if ptr.is_null() || len == 0 {
+        return None;
+    }
+    std::str::from_utf8(std::slice::from_raw_parts(ptr, len)).ok()
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn host_get_action_id(name_ptr: *const u8, name_len: usize) -> ActionId {
     unsafe {
         if name_ptr.is_null() || name_len == 0 {
@@ -20,4 +59,70 @@ pub extern "C" fn host_get_action_id(name_ptr: *const u8, name_len: usize) -> Ac
         }
     }
     ACTION_NOT_FOUND
-}
\ No newline at end of file
+}
+
+/// Looks up a named axis (input profile `[axes]`) by name.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_get_axis_id(name_ptr: *const u8, name_len: usize) -> AxisId {
+    let Some(name) = (unsafe { name_from_raw(name_ptr, name_len) }) else {
+        return AXIS_NOT_FOUND;
+    };
+    let reg = global_registry().lock().unwrap_or_else(|e| e.into_inner());
+    reg.get_axis(name).map_or(AXIS_NOT_FOUND, |(id, _)| id)
+}
+
+/// Registers (or looks up) an action. Names keep their ID for the lifetime of
+/// the host, so a hot-reloaded plugin gets the same IDs back.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_register_action(name_ptr: *const u8, name_len: usize) -> ActionId {
+    let Some(name) = (unsafe { name_from_raw(name_ptr, name_len) }) else {
+        return ACTION_NOT_FOUND;
+    };
+    let mut reg = global_registry().lock().unwrap_or_else(|e| e.into_inner());
+    reg.register(name).unwrap_or_else(|e| {
+        tracing::error!("❌ {e}");
+        ACTION_NOT_FOUND
+    })
+}
+
+/// Suggests a default key, gamepad button or trigger ("Ctrl+KeyS") for
+/// `action`. Applied at the start of the next frame, and only if it isn't bound already.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_bind_default_key(action: ActionId, key_ptr: *const u8, key_len: usize) -> bool {
+    let Some(name) = (unsafe { name_from_raw(key_ptr, key_len) }) else {
+        return false;
+    };
+    queue_checked(None, action, name)
+}
+
+/// `bind_default_key` for an input context ("Menu"), which is created if needed.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_bind_context_key(
+    context_ptr: *const u8,
+    context_len: usize,
+    action: ActionId,
+    key_ptr: *const u8,
+    key_len: usize,
+) -> bool {
+    let (Some(context), Some(name)) =
+        (unsafe { (name_from_raw(context_ptr, context_len), name_from_raw(key_ptr, key_len)) })
+    else {
+        return false;
+    };
+    queue_checked(Some(context.to_string()), action, name)
+}
+
+fn queue_checked(context: Option<String>, action: ActionId, name: &str) -> bool {
+    let binding = match BindingSpec::from_name(name) {
+        Ok(binding) => binding,
+        Err(e) => {
+            tracing::warn!("⚠️ bind_default_key: unknown key '{name}' ({e})");
+            return false;
+        }
+    };
+    if action as usize >= MAX_ACTIONS {
+        return false;
+    }
+    queue_default_binding(PendingBinding { context, action, binding });
+    true
+}
@@ -0,0 +1,430 @@
+// crates/engine_core/src/input/gamepad.rs
+//! Gamepads: the left stick becomes a `MovementSignal` on the Control layer,
+//! buttons become `ActionSignal`s through `InputMap` (bound by name, "Pad.South").
+//!
+//! Device state lives in `Gamepads`; `GamepadPoller` feeds it from gilrs when
+//! the `gamepad` feature is enabled (Linux builds then need the libudev
+//! development package) and is a no-op otherwise. Devices are remembered by
+//! name, so a pad that is unplugged and plugged back in keeps its deadzone.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use glam::Vec2;
+
+use crate::input::arbiter::{ActionSignal, MovementSignal};
+use crate::input::{Arbiter, Binding, InputMap};
+use engine_shared::input_types::PriorityLayer;
+
+/// Stick deflection ignored when neither the profile nor the inspector set one.
+pub const DEFAULT_DEADZONE: f32 = 0.15;
+
+macro_rules! button_table {
+    ($($button:ident),* $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum PadButton {
+            $($button),*
+        }
+
+        const BUTTONS: &[(&str, PadButton)] =
+            &[$((concat!("Pad.", stringify!($button)), PadButton::$button)),*];
+    };
+}
+
+// Face buttons by position (South = A on Xbox, Cross on PlayStation).
+button_table!(
+    South, East, North, West,
+    LeftBumper, RightBumper, LeftTrigger, RightTrigger,
+    Select, Start, Mode, LeftStick, RightStick,
+    DPadUp, DPadDown, DPadLeft, DPadRight,
+);
+
+/// Case-insensitive; the "Pad." prefix is required so pad and key names never clash.
+pub fn button_from_name(name: &str) -> Option<PadButton> {
+    let name = name.trim();
+    BUTTONS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|&(_, button)| button)
+}
+
+pub fn button_name(button: PadButton) -> &'static str {
+    BUTTONS
+        .iter()
+        .find(|&&(_, b)| b == button)
+        .map(|&(name, _)| name)
+        .unwrap_or("Pad.?")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StickAxis {
+    X,
+    Y,
+}
+
+/// Raw stick axes for named axis bindings ("Pad.RightStickX", +Y up).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+}
+
+const PAD_AXES: &[(&str, PadAxis)] = &[
+    ("Pad.LeftStickX", PadAxis::LeftStickX),
+    ("Pad.LeftStickY", PadAxis::LeftStickY),
+    ("Pad.RightStickX", PadAxis::RightStickX),
+    ("Pad.RightStickY", PadAxis::RightStickY),
+];
+
+pub fn axis_from_name(name: &str) -> Option<PadAxis> {
+    let name = name.trim();
+    PAD_AXES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|&(_, axis)| axis)
+}
+
+pub fn axis_name(axis: PadAxis) -> &'static str {
+    PAD_AXES
+        .iter()
+        .find(|&&(_, a)| a == axis)
+        .map(|&(name, _)| name)
+        .unwrap_or("Pad.?")
+}
+
+#[derive(Debug, Clone)]
+pub struct PadDevice {
+    /// Backend id; reassigned when the device reconnects.
+    pub id: usize,
+    pub name: String,
+    pub connected: bool,
+    /// Raw left stick, +Y up.
+    pub stick: Vec2,
+    /// Raw right stick, +Y up.
+    pub right_stick: Vec2,
+    pub buttons: BTreeSet<PadButton>,
+    pub deadzone: f32,
+}
+
+impl PadDevice {
+    /// Left stick with a radial deadzone, rescaled so motion starts from zero
+    /// at its edge and reaches full length at full deflection.
+    pub fn movement(&self) -> Vec2 {
+        let len = self.stick.length();
+        if len <= self.deadzone || self.deadzone >= 1.0 {
+            return Vec2::ZERO;
+        }
+        let scaled = ((len - self.deadzone) / (1.0 - self.deadzone)).min(1.0);
+        self.stick / len * scaled
+    }
+
+    /// Raw value of a stick axis (axis bindings apply their own deadzone).
+    pub fn axis(&self, axis: PadAxis) -> f32 {
+        match axis {
+            PadAxis::LeftStickX => self.stick.x,
+            PadAxis::LeftStickY => self.stick.y,
+            PadAxis::RightStickX => self.right_stick.x,
+            PadAxis::RightStickY => self.right_stick.y,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Gamepads {
+    /// Every device seen this session, connected or not, in order of first connection.
+    pub devices: Vec<PadDevice>,
+    default_deadzone: Option<f32>,
+    /// Per-device deadzones by name (from the input profile or the inspector).
+    deadzones: BTreeMap<String, f32>,
+}
+
+impl Gamepads {
+    pub fn new(default_deadzone: Option<f32>, deadzones: BTreeMap<String, f32>) -> Self {
+        Self {
+            devices: Vec::new(),
+            default_deadzone,
+            deadzones,
+        }
+    }
+
+    pub fn connect(&mut self, id: usize, name: &str) {
+        let deadzone = self.deadzone_for(name);
+        match self.devices.iter_mut().find(|d| !d.connected && d.name == name) {
+            Some(device) => {
+                device.id = id;
+                device.connected = true;
+            }
+            None => self.devices.push(PadDevice {
+                id,
+                name: name.to_string(),
+                connected: true,
+                stick: Vec2::ZERO,
+                right_stick: Vec2::ZERO,
+                buttons: BTreeSet::new(),
+                deadzone,
+            }),
+        }
+        tracing::info!("🎮 Gamepad connected: {name}");
+    }
+
+    /// Keeps the device (and its settings) around; drops its held input.
+    pub fn disconnect(&mut self, id: usize) {
+        if let Some(device) = self.device_mut(id) {
+            device.connected = false;
+            device.stick = Vec2::ZERO;
+            device.right_stick = Vec2::ZERO;
+            device.buttons.clear();
+            tracing::info!("🎮 Gamepad disconnected: {}", device.name);
+        }
+    }
+
+    pub fn set_button(&mut self, id: usize, button: PadButton, pressed: bool) {
+        if let Some(device) = self.device_mut(id) {
+            if pressed {
+                device.buttons.insert(button);
+            } else {
+                device.buttons.remove(&button);
+            }
+        }
+    }
+
+    pub fn set_stick_axis(&mut self, id: usize, axis: StickAxis, value: f32) {
+        let axis = match axis {
+            StickAxis::X => PadAxis::LeftStickX,
+            StickAxis::Y => PadAxis::LeftStickY,
+        };
+        self.set_axis(id, axis, value);
+    }
+
+    pub fn set_axis(&mut self, id: usize, axis: PadAxis, value: f32) {
+        if let Some(device) = self.device_mut(id) {
+            let value = value.clamp(-1.0, 1.0);
+            match axis {
+                PadAxis::LeftStickX => device.stick.x = value,
+                PadAxis::LeftStickY => device.stick.y = value,
+                PadAxis::RightStickX => device.right_stick.x = value,
+                PadAxis::RightStickY => device.right_stick.y = value,
+            }
+        }
+    }
+
+    /// `axis` on the connected pad that pushes it furthest (0 without pads).
+    pub fn axis_value(&self, axis: PadAxis) -> f32 {
+        self.connected()
+            .map(|d| d.axis(axis))
+            .fold(0.0, |best, v| if v.abs() > best.abs() { v } else { best })
+    }
+
+    /// Sets a device's deadzone, also for when it reconnects.
+    pub fn set_deadzone(&mut self, name: &str, deadzone: f32) {
+        let deadzone = deadzone.clamp(0.0, 0.95);
+        self.deadzones.insert(name.to_string(), deadzone);
+        for device in self.devices.iter_mut().filter(|d| d.name == name) {
+            device.deadzone = deadzone;
+        }
+    }
+
+    /// Replaces the configured deadzones (profile reload) and applies them to known devices.
+    pub fn configure(&mut self, default_deadzone: Option<f32>, deadzones: BTreeMap<String, f32>) {
+        self.default_deadzone = default_deadzone;
+        self.deadzones = deadzones;
+        for i in 0..self.devices.len() {
+            self.devices[i].deadzone = self.deadzone_for(&self.devices[i].name);
+        }
+    }
+
+    /// `(default, per device)` deadzones as configured, for saving.
+    pub fn deadzones(&self) -> (Option<f32>, BTreeMap<String, f32>) {
+        (self.default_deadzone, self.deadzones.clone())
+    }
+
+    pub fn connected(&self) -> impl Iterator<Item = &PadDevice> {
+        self.devices.iter().filter(|d| d.connected)
+    }
+
+    /// Buttons held on any connected pad, for the trigger stage.
+    pub fn held_inputs(&self) -> impl Iterator<Item = Binding> + '_ {
+        self.connected().flat_map(|d| d.buttons.iter().map(|&b| Binding::Pad(b)))
+    }
+
+    /// Adds every connected pad's stick and bound buttons to the Arbiter,
+    /// except buttons in `consumed`.
+    pub fn feed_arbiter(&self, arbiter: &mut Arbiter, input_map: &InputMap, consumed: &HashSet<Binding>) {
+        for device in self.connected() {
+            arbiter.add_movement(MovementSignal {
+                layer: PriorityLayer::Control,
+                vector: device.movement(),
+                weight: 1.0,
+            });
+        }
+        self.feed_buttons(arbiter, input_map, consumed);
+    }
+
+    /// Adds the bound buttons of every connected pad (input contexts have no stick movement).
+    pub fn feed_buttons(&self, arbiter: &mut Arbiter, input_map: &InputMap, consumed: &HashSet<Binding>) {
+        for device in self.connected() {
+            for &button in &device.buttons {
+                if consumed.contains(&Binding::Pad(button)) {
+                    continue;
+                }
+                if let Some(action_id) = input_map.map_button(button) {
+                    arbiter.add_action(ActionSignal {
+                        layer: PriorityLayer::Control,
+                        action_id,
+                        active: true,
+                    });
+                }
+            }
+        }
+    }
+
+    fn deadzone_for(&self, name: &str) -> f32 {
+        self.deadzones
+            .get(name)
+            .copied()
+            .or(self.default_deadzone)
+            .unwrap_or(DEFAULT_DEADZONE)
+    }
+
+    fn device_mut(&mut self, id: usize) -> Option<&mut PadDevice> {
+        self.devices.iter_mut().find(|d| d.connected && d.id == id)
+    }
+}
+
+/// Reads gamepad events from the OS into `Gamepads`.
+#[cfg(feature = "gamepad")]
+pub struct GamepadPoller {
+    gilrs: Option<gilrs::Gilrs>,
+}
+
+#[cfg(feature = "gamepad")]
+impl GamepadPoller {
+    /// Also registers pads that were plugged in before startup.
+    pub fn new(gamepads: &mut Gamepads) -> Self {
+        let gilrs = match gilrs::Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(gilrs::Error::NotImplemented(gilrs)) => {
+                tracing::warn!("⚠️ Gamepads are not supported on this platform");
+                Some(gilrs)
+            }
+            Err(e) => {
+                tracing::error!("❌ Gamepad backend failed to start: {e}");
+                None
+            }
+        };
+        if let Some(gilrs) = &gilrs {
+            for (id, pad) in gilrs.gamepads() {
+                gamepads.connect(id.into(), pad.name());
+            }
+        }
+        Self { gilrs }
+    }
+
+    /// Drains pending events: hotplug, buttons and sticks.
+    pub fn poll(&mut self, gamepads: &mut Gamepads) {
+        use gilrs::{Axis, EventType};
+
+        let Some(gilrs) = &mut self.gilrs else { return };
+        while let Some(gilrs::Event { id, event, .. }) = gilrs.next_event() {
+            let index: usize = id.into();
+            match event {
+                EventType::Connected => gamepads.connect(index, gilrs.gamepad(id).name()),
+                EventType::Disconnected => gamepads.disconnect(index),
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(button) = pad_button(button) {
+                        gamepads.set_button(index, button, true);
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(button) = pad_button(button) {
+                        gamepads.set_button(index, button, false);
+                    }
+                }
+                EventType::AxisChanged(Axis::LeftStickX, value, _) => {
+                    gamepads.set_stick_axis(index, StickAxis::X, value)
+                }
+                EventType::AxisChanged(Axis::LeftStickY, value, _) => {
+                    gamepads.set_stick_axis(index, StickAxis::Y, value)
+                }
+                EventType::AxisChanged(Axis::RightStickX, value, _) => {
+                    gamepads.set_axis(index, PadAxis::RightStickX, value)
+                }
+                EventType::AxisChanged(Axis::RightStickY, value, _) => {
+                    gamepads.set_axis(index, PadAxis::RightStickY, value)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn pad_button(button: gilrs::Button) -> Option<PadButton> {
+    use gilrs::Button;
+
+    Some(match button {
+        Button::South => PadButton::South,
+        Button::East => PadButton::East,
+        Button::North => PadButton::North,
+        Button::West => PadButton::West,
+        Button::LeftTrigger => PadButton::LeftBumper,
+        Button::RightTrigger => PadButton::RightBumper,
+        Button::LeftTrigger2 => PadButton::LeftTrigger,
+        Button::RightTrigger2 => PadButton::RightTrigger,
+        Button::Select => PadButton::Select,
+        Button::Start => PadButton::Start,
+        Button::Mode => PadButton::Mode,
+        Button::LeftThumb => PadButton::LeftStick,
+        Button::RightThumb => PadButton::RightStick,
+        Button::DPadUp => PadButton::DPadUp,
+        Button::DPadDown => PadButton::DPadDown,
+        Button::DPadLeft => PadButton::DPadLeft,
+        Button::DPadRight => PadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+/// Built without the `gamepad` feature: no devices ever connect.
+#[cfg(not(feature = "gamepad"))]
+pub struct GamepadPoller;
+
+#[cfg(not(feature = "gamepad"))]
+impl GamepadPoller {
+    pub fn new(_gamepads: &mut Gamepads) -> Self {
+        tracing::debug!("Gamepad support not compiled in (enable the `gamepad` feature)");
+        Self
+    }
+
+    pub fn poll(&mut self, _gamepads: &mut Gamepads) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconnected_pad_keeps_its_deadzone_and_drops_held_input() {
+        let mut pads = Gamepads::new(Some(0.2), BTreeMap::new());
+        pads.connect(0, "Pad A");
+        pads.set_stick_axis(0, StickAxis::X, 0.1);
+        assert_eq!(pads.devices[0].movement(), Vec2::ZERO);
+
+        pads.set_stick_axis(0, StickAxis::X, 0.6);
+        assert!((pads.devices[0].movement().x - 0.5).abs() < 1e-6);
+
+        pads.set_deadzone("Pad A", 0.5);
+        pads.set_button(0, PadButton::South, true);
+        pads.disconnect(0);
+        assert_eq!(pads.connected().count(), 0);
+
+        pads.connect(3, "Pad A");
+        let device = &pads.devices[0];
+        assert_eq!((pads.devices.len(), device.id, device.deadzone), (1, 3, 0.5));
+        assert!(device.buttons.is_empty() && device.stick == Vec2::ZERO);
+
+        assert_eq!(button_from_name("pad.dpadup"), Some(PadButton::DPadUp));
+        assert_eq!(button_name(PadButton::South), "Pad.South");
+        assert_eq!(button_from_name("South"), None);
+    }
+}
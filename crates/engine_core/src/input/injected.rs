@@ -0,0 +1,169 @@
+// crates/engine_core/src/input/injected.rs
+//! Signals plugins push onto any Arbiter layer through
+//! `HostInterface::submit_movement` / `submit_action`: knockback on Reflex, a
+//! scripted walk on Cutscene. Each lasts `duration` seconds of frame time (at
+//! least until a simulation tick has seen it) and goes through the Arbiter like
+//! input does, so layer masks and locks apply to it.
+
+use std::sync::Mutex;
+
+use engine_shared::input_types::{ActionId, PriorityLayer, MAX_ACTIONS};
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::input::arbiter::{ActionSignal, MovementSignal};
+use crate::input::Arbiter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Injected {
+    Movement { vector: Vec2, weight: f32 },
+    Action(ActionId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InjectedSignal {
+    pub layer: PriorityLayer,
+    pub signal: Injected,
+    /// Seconds left.
+    pub remaining: f32,
+}
+
+/// Submitted since the host last collected them (from FFI callbacks, or a sandbox reply).
+static PENDING: Mutex<Vec<InjectedSignal>> = Mutex::new(Vec::new());
+
+/// Tests submitting through `PENDING` take turns, so none collects another's signals.
+#[cfg(test)]
+pub(crate) static PENDING_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn submit(signal: InjectedSignal) {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner()).push(signal);
+}
+
+pub fn take_pending() -> Vec<InjectedSignal> {
+    std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+fn checked(layer: u32, signal: Injected, duration: f32) -> Option<InjectedSignal> {
+    let Some(layer) = PriorityLayer::from_u32(layer) else {
+        tracing::warn!("⚠️ Signal submitted on unknown layer {layer}");
+        return None;
+    };
+    let valid = match signal {
+        Injected::Movement { vector, weight } => vector.is_finite() && weight.is_finite(),
+        Injected::Action(action) => (action as usize) < MAX_ACTIONS,
+    };
+    if !valid || !duration.is_finite() {
+        tracing::warn!("⚠️ Invalid signal submitted on {layer:?}: {signal:?} for {duration}s");
+        return None;
+    }
+    Some(InjectedSignal {
+        layer,
+        signal,
+        remaining: duration.max(0.0),
+    })
+}
+
+/// Pushes `(x, y) * weight` onto `layer` (`PriorityLayer` as `u32`) for `duration` seconds.
+pub extern "C" fn host_submit_movement(layer: u32, x: f32, y: f32, weight: f32, duration: f32) -> bool {
+    let signal = Injected::Movement {
+        vector: Vec2::new(x, y),
+        weight,
+    };
+    checked(layer, signal, duration).map(submit).is_some()
+}
+
+/// Holds `action` on `layer` (`PriorityLayer` as `u32`) for `duration` seconds.
+pub extern "C" fn host_submit_action(layer: u32, action: ActionId, duration: f32) -> bool {
+    checked(layer, Injected::Action(action), duration).map(submit).is_some()
+}
+
+/// The injected signals in effect, owned by the host loop.
+#[derive(Default)]
+pub struct InjectedSignals {
+    active: Vec<InjectedSignal>,
+}
+
+impl InjectedSignals {
+    /// Takes newly submitted signals and adds every live one to the Arbiter
+    /// (after the pollers, which clear it).
+    pub fn feed_arbiter(&mut self, arbiter: &mut Arbiter) {
+        self.active.extend(take_pending());
+        for injected in &self.active {
+            match injected.signal {
+                Injected::Movement { vector, weight } => arbiter.add_movement(MovementSignal {
+                    layer: injected.layer,
+                    vector,
+                    weight,
+                }),
+                Injected::Action(action_id) => arbiter.add_action(ActionSignal {
+                    layer: injected.layer,
+                    action_id,
+                    active: true,
+                }),
+            }
+        }
+    }
+
+    /// End of frame: counts `frame_dt` off every signal; expired ones are
+    /// dropped once a simulation tick has seen them.
+    pub fn end_frame(&mut self, ticks_ran: u32, frame_dt: f32) {
+        for injected in &mut self.active {
+            injected.remaining -= frame_dt;
+        }
+        if ticks_ran > 0 {
+            self.active.retain(|s| s.remaining > 0.0);
+        }
+    }
+
+    pub fn active(&self) -> &[InjectedSignal] {
+        &self.active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::config::InputDefaults;
+    use engine_shared::input_types::canonical_actions::MOVE_UP;
+
+    #[test]
+    fn knockback_overrides_control_and_keeps_the_reflex_lock_afterwards() {
+        let _pending = PENDING_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut arbiter = Arbiter::new(InputDefaults::default_arbiter_layers(), 0.1);
+        let mut injected = InjectedSignals::default();
+        let control_walk = |arbiter: &mut Arbiter| {
+            arbiter.clear();
+            arbiter.add_movement(MovementSignal {
+                layer: PriorityLayer::Control,
+                vector: Vec2::Y,
+                weight: 1.0,
+            });
+            arbiter.add_action(ActionSignal {
+                layer: PriorityLayer::Control,
+                action_id: MOVE_UP,
+                active: true,
+            });
+        };
+
+        assert!(!host_submit_movement(9, 1.0, 0.0, 1.0, 0.1));
+        assert!(!host_submit_action(PriorityLayer::Cutscene as u32, MAX_ACTIONS as ActionId, 1.0));
+        assert!(host_submit_movement(PriorityLayer::Reflex as u32, -1.0, 0.0, 1.0, 0.05));
+
+        control_walk(&mut arbiter);
+        injected.feed_arbiter(&mut arbiter);
+        let state = arbiter.resolve();
+        assert_eq!((state.analog_axes[0], state.analog_axes[1]), (-1.0, 0.0));
+        assert!(!state.is_active(MOVE_UP));
+
+        // Not expired until a tick ran; then the Reflex lock still vetoes Control.
+        injected.end_frame(0, 0.1);
+        assert_eq!(injected.active().len(), 1);
+        injected.end_frame(1, 0.0);
+        assert!(injected.active().is_empty());
+        control_walk(&mut arbiter);
+        injected.feed_arbiter(&mut arbiter);
+        let state = arbiter.resolve();
+        assert_eq!((state.analog_axes[0], state.analog_axes[1]), (0.0, 0.0));
+        assert!(!state.is_active(MOVE_UP));
+    }
+}
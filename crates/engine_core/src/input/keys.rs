@@ -0,0 +1,46 @@
+// crates/engine_core/src/input/keys.rs
+//! Text names for `KeyCode`s, used wherever bindings cross a text boundary
+//! (plugin FFI, sandbox IPC). Names match winit's variant names ("KeyW", "Space");
+//! lookups are case-insensitive and accept a bare letter or digit ("w", "1").
+
+use winit::keyboard::KeyCode;
+
+macro_rules! key_table {
+    ($($key:ident),* $(,)?) => {
+        const KEYS: &[(&str, KeyCode)] = &[$((stringify!($key), KeyCode::$key)),*];
+    };
+}
+
+key_table!(
+    KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM,
+    KeyN, KeyO, KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ,
+    Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    Space, Enter, Escape, Tab, Backspace, CapsLock,
+    ShiftLeft, ShiftRight, ControlLeft, ControlRight, AltLeft, AltRight,
+    ArrowUp, ArrowDown, ArrowLeft, ArrowRight,
+    Insert, Delete, Home, End, PageUp, PageDown,
+    Backquote, Minus, Equal, BracketLeft, BracketRight, Backslash,
+    Semicolon, Quote, Comma, Period, Slash,
+    Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
+    NumpadAdd, NumpadSubtract, NumpadMultiply, NumpadDivide, NumpadDecimal, NumpadEnter,
+);
+
+pub fn key_from_name(name: &str) -> Option<KeyCode> {
+    let name = name.trim();
+    let lookup = |n: &str| {
+        KEYS.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(n))
+            .map(|&(_, code)| code)
+    };
+
+    lookup(name).or_else(|| match name.as_bytes() {
+        [c] if c.is_ascii_alphabetic() => lookup(&format!("Key{}", *c as char)),
+        [c] if c.is_ascii_digit() => lookup(&format!("Digit{}", *c as char)),
+        _ => None,
+    })
+}
+
+pub fn key_name(code: KeyCode) -> Option<&'static str> {
+    KEYS.iter().find(|&&(_, c)| c == code).map(|&(name, _)| name)
+}
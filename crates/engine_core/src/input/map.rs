@@ -1,9 +1,83 @@
 // crates/engine_core/src/input/map.rs
 use std::collections::HashMap;
 use winit::keyboard::{KeyCode, PhysicalKey};
-use engine_shared::ActionId;
+use engine_shared::{ActionId, AxisId};
 
-#[derive(Default)]
+use super::axes::AxisBinding;
+use super::gamepad::{self, PadButton};
+use super::keys;
+use super::mouse::{self, MouseInput};
+use super::triggers::Trigger;
+
+/// Anything an action can be bound to by name (profiles, plugin defaults, IPC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Binding {
+    /// Logical key ("KeyW").
+    Key(KeyCode),
+    /// Key by position ("Physical.KeyW").
+    Physical(KeyCode),
+    Pad(PadButton),
+    Mouse(MouseInput),
+}
+
+const PHYSICAL_PREFIX: &str = "Physical.";
+
+impl Binding {
+    /// A key name (`keys::key_from_name`), optionally prefixed "Physical.", a
+    /// gamepad button ("Pad.South") or a mouse button or wheel step
+    /// ("Mouse.Left", "Mouse.WheelUp").
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if let Some(key) = name
+            .get(..PHYSICAL_PREFIX.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(PHYSICAL_PREFIX))
+            .and_then(|_| keys::key_from_name(&name[PHYSICAL_PREFIX.len()..]))
+        {
+            return Some(Binding::Physical(key));
+        }
+        gamepad::button_from_name(name)
+            .map(Binding::Pad)
+            .or_else(|| mouse::mouse_from_name(name).map(Binding::Mouse))
+            .or_else(|| keys::key_from_name(name).map(Binding::Key))
+    }
+
+    /// The name `from_name` reads back; `None` for keys without one.
+    pub fn name(self) -> Option<String> {
+        match self {
+            Binding::Key(key) => keys::key_name(key).map(str::to_string),
+            Binding::Physical(key) => keys::key_name(key).map(|k| format!("{PHYSICAL_PREFIX}{k}")),
+            Binding::Pad(button) => Some(gamepad::button_name(button).to_string()),
+            Binding::Mouse(input) => Some(mouse::mouse_name(input).to_string()),
+        }
+    }
+}
+
+/// A binding as written by name: a plain input, or a chord, timed or
+/// sequence trigger (see `input::triggers`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BindingSpec {
+    Input(Binding),
+    Trigger(Trigger),
+}
+
+impl BindingSpec {
+    /// `Binding::from_name`, falling back to `Trigger::from_name`.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match Binding::from_name(name) {
+            Some(binding) => Ok(BindingSpec::Input(binding)),
+            None => Trigger::from_name(name).map(BindingSpec::Trigger),
+        }
+    }
+
+    pub fn name(&self) -> Option<String> {
+        match self {
+            BindingSpec::Input(binding) => binding.name(),
+            BindingSpec::Trigger(trigger) => trigger.name(),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
 pub struct InputMap {
     /// Logical bindings: "Press the key labeled 'W'".
     /// Good for menus, typing, and non-directional actions (e.g. 'I' for Inventory).
@@ -13,6 +87,18 @@ pub struct InputMap {
     /// Good for movement (WASD), ensuring the hand position stays the same
     /// regardless of the user's keyboard layout (QWERTY/AZERTY).
     physical_bindings: HashMap<PhysicalKey, ActionId>,
+
+    /// Gamepad buttons, shared by every connected pad.
+    pad_bindings: HashMap<PadButton, ActionId>,
+
+    /// Mouse buttons and wheel steps.
+    mouse_bindings: HashMap<MouseInput, ActionId>,
+
+    /// Chords, timed and sequence triggers, evaluated by `TriggerStage`.
+    triggers: Vec<(Trigger, ActionId)>,
+
+    /// Named axes (see `input::axes`).
+    axes: HashMap<AxisId, AxisBinding>,
 }
 
 impl InputMap {
@@ -26,6 +112,67 @@ impl InputMap {
         self.physical_bindings.insert(key, action);
     }
 
+    /// Bind a gamepad button.
+    pub fn bind_button(&mut self, button: PadButton, action: ActionId) {
+        self.pad_bindings.insert(button, action);
+    }
+
+    /// Bind a mouse button or wheel step.
+    pub fn bind_mouse(&mut self, input: MouseInput, action: ActionId) {
+        self.mouse_bindings.insert(input, action);
+    }
+
+    /// Bind a key, a gamepad button or a mouse input.
+    pub fn bind(&mut self, binding: Binding, action: ActionId) {
+        match binding {
+            Binding::Key(key) => self.bind_logical(key, action),
+            Binding::Physical(key) => self.bind_physical(PhysicalKey::Code(key), action),
+            Binding::Pad(button) => self.bind_button(button, action),
+            Binding::Mouse(input) => self.bind_mouse(input, action),
+        }
+    }
+
+    /// Bind a trigger, replacing whatever action it had.
+    pub fn bind_trigger(&mut self, trigger: Trigger, action: ActionId) {
+        match self.triggers.iter_mut().find(|(t, _)| *t == trigger) {
+            Some(entry) => entry.1 = action,
+            None => self.triggers.push((trigger, action)),
+        }
+    }
+
+    /// Bind an input or a trigger.
+    pub fn bind_spec(&mut self, spec: BindingSpec, action: ActionId) {
+        match spec {
+            BindingSpec::Input(binding) => self.bind(binding, action),
+            BindingSpec::Trigger(trigger) => self.bind_trigger(trigger, action),
+        }
+    }
+
+    /// `bind_default` for an input or a trigger.
+    pub fn bind_spec_default(&mut self, spec: BindingSpec, action: ActionId) -> Result<(), ActionId> {
+        match spec {
+            BindingSpec::Input(binding) => self.bind_default(binding, action),
+            BindingSpec::Trigger(trigger) => match self.triggers.iter().find(|(t, _)| *t == trigger) {
+                Some(&(_, existing)) if existing != action => Err(existing),
+                _ => {
+                    self.bind_trigger(trigger, action);
+                    Ok(())
+                }
+            },
+        }
+    }
+
+    /// Bind unless the key or button is already bound. Returns the existing action on conflict.
+    pub fn bind_default(&mut self, binding: Binding, action: ActionId) -> Result<(), ActionId> {
+        match self.action_for(binding) {
+            Some(existing) if existing != action => Err(existing),
+            _ => {
+                self.bind(binding, action);
+                Ok(())
+            }
+        }
+    }
+
     /// Resolve an Action ID from a raw input event.
     ///
     /// The paper specifies that the engine must support both interpretations.
@@ -50,9 +197,129 @@ impl InputMap {
         None
     }
 
+    /// Resolve an Action ID from a gamepad button.
+    pub fn map_button(&self, button: PadButton) -> Option<ActionId> {
+        self.pad_bindings.get(&button).copied()
+    }
+
+    /// Resolve an Action ID from a mouse button or wheel step.
+    pub fn map_mouse(&self, input: MouseInput) -> Option<ActionId> {
+        self.mouse_bindings.get(&input).copied()
+    }
+
+    /// The action `binding` triggers, if any.
+    pub fn action_for(&self, binding: Binding) -> Option<ActionId> {
+        match binding {
+            Binding::Key(key) => self.logical_bindings.get(&key),
+            Binding::Physical(key) => self.physical_bindings.get(&PhysicalKey::Code(key)),
+            Binding::Pad(button) => self.pad_bindings.get(&button),
+            Binding::Mouse(input) => self.mouse_bindings.get(&input),
+        }
+        .copied()
+    }
+
+    /// Every binding with its action.
+    pub fn bindings(&self) -> Vec<(Binding, ActionId)> {
+        let logical = self.logical_bindings.iter().map(|(&k, &a)| (Binding::Key(k), a));
+        let physical = self.physical_bindings.iter().filter_map(|(&k, &a)| match k {
+            PhysicalKey::Code(code) => Some((Binding::Physical(code), a)),
+            PhysicalKey::Unidentified(_) => None,
+        });
+        let pad = self.pad_bindings.iter().map(|(&b, &a)| (Binding::Pad(b), a));
+        let mouse = self.mouse_bindings.iter().map(|(&m, &a)| (Binding::Mouse(m), a));
+        logical.chain(physical).chain(pad).chain(mouse).collect()
+    }
+
+    /// Everything bound to `action`, by name.
+    pub fn bindings_of(&self, action: ActionId) -> Vec<Binding> {
+        let mut bindings: Vec<Binding> = self
+            .bindings()
+            .into_iter()
+            .filter(|&(_, a)| a == action)
+            .map(|(b, _)| b)
+            .collect();
+        bindings.sort_by_key(|b| b.name());
+        bindings
+    }
+
+    /// Sets what a named axis reads, replacing its previous sources and settings.
+    pub fn bind_axis(&mut self, axis: AxisId, binding: AxisBinding) {
+        self.axes.insert(axis, binding);
+    }
+
+    pub fn axis_binding(&self, axis: AxisId) -> Option<&AxisBinding> {
+        self.axes.get(&axis)
+    }
+
+    pub fn axis_bindings(&self) -> impl Iterator<Item = (AxisId, &AxisBinding)> {
+        self.axes.iter().map(|(&id, binding)| (id, binding))
+    }
+
+    /// True when a held input (keys as `Binding::Key`) is bound here, plainly
+    /// either way or as part of a trigger.
+    pub fn binds(&self, input: Binding) -> bool {
+        let plain = match input {
+            Binding::Key(key) | Binding::Physical(key) => {
+                self.logical_bindings.contains_key(&key)
+                    || self.physical_bindings.contains_key(&PhysicalKey::Code(key))
+            }
+            Binding::Pad(button) => self.pad_bindings.contains_key(&button),
+            Binding::Mouse(mouse) => self.mouse_bindings.contains_key(&mouse),
+        };
+        plain
+            || self
+                .triggers
+                .iter()
+                .flat_map(|(t, _)| t.steps.iter().flatten())
+                .any(|part| part.covers(input))
+    }
+
+    /// Every trigger with its action, in the order bound.
+    pub fn triggers(&self) -> &[(Trigger, ActionId)] {
+        &self.triggers
+    }
+
+    /// Triggers bound to `action`, by name.
+    pub fn triggers_of(&self, action: ActionId) -> Vec<Trigger> {
+        let mut triggers: Vec<Trigger> = self
+            .triggers
+            .iter()
+            .filter(|&&(_, a)| a == action)
+            .map(|(t, _)| t.clone())
+            .collect();
+        triggers.sort_by_key(|t| t.name());
+        triggers
+    }
+
+    pub fn unbind_trigger(&mut self, trigger: &Trigger) {
+        self.triggers.retain(|(t, _)| t != trigger);
+    }
+
+    pub fn unbind(&mut self, binding: Binding) {
+        match binding {
+            Binding::Key(key) => self.logical_bindings.remove(&key),
+            Binding::Physical(key) => self.physical_bindings.remove(&PhysicalKey::Code(key)),
+            Binding::Pad(button) => self.pad_bindings.remove(&button),
+            Binding::Mouse(input) => self.mouse_bindings.remove(&input),
+        };
+    }
+
+    /// Remove every key and trigger bound to `action`.
+    pub fn unbind_action(&mut self, action: ActionId) {
+        self.logical_bindings.retain(|_, a| *a != action);
+        self.physical_bindings.retain(|_, a| *a != action);
+        self.pad_bindings.retain(|_, a| *a != action);
+        self.mouse_bindings.retain(|_, a| *a != action);
+        self.triggers.retain(|&(_, a)| a != action);
+    }
+
     /// Clear all bindings (useful for resetting configuration).
     pub fn clear(&mut self) {
         self.logical_bindings.clear();
         self.physical_bindings.clear();
+        self.pad_bindings.clear();
+        self.mouse_bindings.clear();
+        self.triggers.clear();
+        self.axes.clear();
     }
 }
\ No newline at end of file
@@ -1,14 +1,24 @@
 // crates/engine_core/src/input/mod.rs
 pub mod registry;
+pub mod axes;
 pub mod map;
 pub mod arbiter;
 pub mod ffi;
+pub mod keys;
+pub mod gamepad;
+pub mod contexts;
+pub mod injected;
+pub mod mouse;
+pub mod profile;
+pub mod triggers;
 
 // Re-export core types to maintain the API `crate::input::ActionRegistry`
-pub use registry::ActionRegistry;
-pub use map::InputMap;
+pub use registry::{ActionRegistry, AxisKind};
+pub use map::{Binding, BindingSpec, InputMap};
+pub use triggers::{Trigger, TriggerStage};
+pub use contexts::InputContexts;
 pub use arbiter::Arbiter;
-pub use ffi::{host_get_action_id, GLOBAL_REGISTRY};
+pub use ffi::{host_bind_default_key, host_get_action_id, host_get_axis_id, host_register_action, GLOBAL_REGISTRY};
 pub mod config;
 pub mod poller;
 // plus whatever you already had: registry, map, ffi, etc.
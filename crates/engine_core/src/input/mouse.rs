@@ -0,0 +1,49 @@
+// crates/engine_core/src/input/mouse.rs
+//! Mouse buttons and wheel steps as bindable inputs ("Mouse.Left",
+//! "Mouse.WheelUp"). A wheel step holds its action for the next simulation
+//! tick only; the cursor itself is published through analog axes (see
+//! `engine_shared::input_types::axes`).
+
+use winit::event::MouseButton;
+
+macro_rules! mouse_table {
+    ($($input:ident),* $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum MouseInput {
+            $($input),*
+        }
+
+        const INPUTS: &[(&str, MouseInput)] =
+            &[$((concat!("Mouse.", stringify!($input)), MouseInput::$input)),*];
+    };
+}
+
+mouse_table!(Left, Right, Middle, Back, Forward, WheelUp, WheelDown);
+
+/// Case-insensitive; the "Mouse." prefix is required.
+pub fn mouse_from_name(name: &str) -> Option<MouseInput> {
+    let name = name.trim();
+    INPUTS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|&(_, input)| input)
+}
+
+pub fn mouse_name(input: MouseInput) -> &'static str {
+    INPUTS
+        .iter()
+        .find(|&&(_, i)| i == input)
+        .map(|&(name, _)| name)
+        .unwrap_or("Mouse.?")
+}
+
+pub fn from_winit(button: MouseButton) -> Option<MouseInput> {
+    Some(match button {
+        MouseButton::Left => MouseInput::Left,
+        MouseButton::Right => MouseInput::Right,
+        MouseButton::Middle => MouseInput::Middle,
+        MouseButton::Back => MouseInput::Back,
+        MouseButton::Forward => MouseInput::Forward,
+        MouseButton::Other(_) => return None,
+    })
+}
@@ -1,69 +1,175 @@
 // crates/engine_core/src/input/poller.rs
 
+use std::collections::HashSet;
+
+use glam::Vec2;
+
 use crate::input::arbiter::ActionSignal;
-use crate::input::{Arbiter, InputMap};
-use engine_shared::input_types::PriorityLayer;
-use winit::event::{KeyEvent, WindowEvent};
+use crate::input::mouse::{self, MouseInput};
+use crate::input::{Arbiter, Binding, InputMap};
+use crate::renderer::camera::CameraView;
+use engine_shared::input_types::{axes, InputState, PriorityLayer};
+use winit::event::{ElementState, KeyEvent, MouseScrollDelta, WindowEvent};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
-/// Low-level input collector that tracks active physical keys.
+/// Touchpads report pixels; this many count as one wheel line.
+const PIXELS_PER_LINE: f32 = 40.0;
+
+/// Low-level input collector that tracks active physical keys, mouse buttons,
+/// the cursor and the wheel.
 /// This keeps raw device state out of App / PlatformRunner.
 pub struct InputPoller {
     active_keys: Vec<KeyCode>,
+    active_buttons: Vec<MouseInput>,
+    /// Window pixels; `None` while the cursor is outside the window.
+    cursor: Option<Vec2>,
+    /// Wheel lines not yet seen by a simulation tick.
+    scroll: f32,
+    /// Cursor movement in pixels not yet seen by a simulation tick, +Y up.
+    motion: Vec2,
+}
+
+impl Default for InputPoller {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl InputPoller {
     pub fn new() -> Self {
         Self {
             active_keys: Vec::new(),
+            active_buttons: Vec::new(),
+            cursor: None,
+            scroll: 0.0,
+            motion: Vec2::ZERO,
         }
     }
 
-    /// Process a single winit WindowEvent and update internal key state.
+    /// Process a single winit WindowEvent and update internal key/mouse state.
     pub fn handle_event(&mut self, event: &WindowEvent) {
-        if let WindowEvent::KeyboardInput { event: key_event, .. } = event {
-            self.handle_keyboard_input(key_event);
+        match event {
+            WindowEvent::KeyboardInput { event: key_event, .. } => {
+                self.handle_keyboard_input(key_event);
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let Some(button) = mouse::from_winit(*button) else { return };
+                match state {
+                    ElementState::Pressed => {
+                        if !self.active_buttons.contains(&button) {
+                            self.active_buttons.push(button);
+                        }
+                    }
+                    ElementState::Released => self.active_buttons.retain(|&b| b != button),
+                }
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = Vec2::new(position.x as f32, position.y as f32);
+                if let Some(previous) = self.cursor {
+                    self.motion += Vec2::new(position.x - previous.x, previous.y - position.y);
+                }
+                self.cursor = Some(position);
+            }
+            WindowEvent::CursorLeft { .. } => self.cursor = None,
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.scroll += match delta {
+                    MouseScrollDelta::LineDelta(_, lines) => *lines,
+                    MouseScrollDelta::PixelDelta(pixels) => pixels.y as f32 / PIXELS_PER_LINE,
+                };
+            }
+            _ => {}
         }
     }
 
     fn handle_keyboard_input(&mut self, key_event: &KeyEvent) {
         if let PhysicalKey::Code(keycode) = key_event.physical_key {
             match key_event.state {
-                winit::event::ElementState::Pressed => {
+                ElementState::Pressed => {
                     if !self.active_keys.contains(&keycode) {
                         self.active_keys.push(keycode);
                     }
                 }
-                winit::event::ElementState::Released => {
+                ElementState::Released => {
                     self.active_keys.retain(|&k| k != keycode);
                 }
             }
         }
     }
 
-    /// Returns true if a given physical key is currently pressed.
-    /// Used to implement engine-level Reflex tests (e.g., P key) without
-    /// leaking active_keys out of this struct.
-    pub fn is_key_active(&self, key: KeyCode) -> bool {
-        self.active_keys.contains(&key)
+    /// Keys, mouse buttons and the pending wheel step held right now, for the
+    /// trigger stage (keys as `Binding::Key`).
+    pub fn held_inputs(&self) -> impl Iterator<Item = Binding> + '_ {
+        let keys = self.active_keys.iter().map(|&key| Binding::Key(key));
+        let mouse = self.active_buttons.iter().copied().chain(self.wheel_step());
+        keys.chain(mouse.map(Binding::Mouse))
     }
 
-    /// Sync raw physical state into the high-level Arbiter using the InputMap.
-    /// This clears the Arbiter first, as in the original App::run.
-    pub fn synchronize_with_arbiter(&self, arbiter: &mut Arbiter, input_map: &InputMap) {
-        arbiter.clear();
-
-        for &key in &self.active_keys {
-            let physical = PhysicalKey::Code(key);
-            if let Some(action_id) =
-                input_map.map_signal_to_intent(Some(key), physical)
-            {
-                arbiter.add_action(ActionSignal {
-                    layer: PriorityLayer::Control,
-                    action_id,
-                    active: true,
-                });
-            }
+    fn wheel_step(&self) -> Option<MouseInput> {
+        match self.scroll {
+            s if s > 0.0 => Some(MouseInput::WheelUp),
+            s if s < 0.0 => Some(MouseInput::WheelDown),
+            _ => None,
+        }
+    }
+
+    /// Sync raw physical state into the high-level Arbiter using `input_map`
+    /// (called once per active input context, after `Arbiter::clear`). Inputs in
+    /// `consumed` (completing a chord, or taken by a context above) are left out.
+    pub fn feed_arbiter(&self, arbiter: &mut Arbiter, input_map: &InputMap, consumed: &HashSet<Binding>) {
+        let keys = self
+            .active_keys
+            .iter()
+            .filter(|&&key| !consumed.contains(&Binding::Key(key)))
+            .filter_map(|&key| input_map.map_signal_to_intent(Some(key), PhysicalKey::Code(key)));
+
+        let mouse = self
+            .active_buttons
+            .iter()
+            .copied()
+            .chain(self.wheel_step())
+            .filter(|&input| !consumed.contains(&Binding::Mouse(input)))
+            .filter_map(|input| input_map.map_mouse(input));
+
+        for action_id in keys.chain(mouse) {
+            arbiter.add_action(ActionSignal {
+                layer: PriorityLayer::Control,
+                action_id,
+                active: true,
+            });
+        }
+    }
+
+    /// Writes the cursor (screen and world, through `camera`) and the wheel into
+    /// the resolved state. With the cursor outside the window the last position stays.
+    pub fn write_pointer_axes(
+        &self,
+        state: &mut InputState,
+        previous: &InputState,
+        camera: &CameraView,
+        window_size: Vec2,
+    ) {
+        let (screen, world) = match self.cursor {
+            Some(screen) => (screen, camera.screen_to_world(screen, window_size)),
+            None => (previous.cursor_screen(), previous.cursor_world()),
+        };
+        state.analog_axes[axes::CURSOR_SCREEN_X] = screen.x;
+        state.analog_axes[axes::CURSOR_SCREEN_Y] = screen.y;
+        state.analog_axes[axes::CURSOR_WORLD_X] = world.x;
+        state.analog_axes[axes::CURSOR_WORLD_Y] = world.y;
+        state.analog_axes[axes::SCROLL] = self.scroll;
+    }
+
+    /// Wheel lines and cursor movement since the last tick, for named axes.
+    pub fn pointer_motion(&self) -> (f32, Vec2) {
+        (self.scroll, self.motion)
+    }
+
+    /// End of frame: wheel steps and cursor movement are dropped once a
+    /// simulation tick has seen them, and carried into the next frame otherwise.
+    pub fn end_frame(&mut self, ticks_ran: u32) {
+        if ticks_ran > 0 {
+            self.scroll = 0.0;
+            self.motion = Vec2::ZERO;
         }
     }
 }
@@ -0,0 +1,384 @@
+// crates/engine_core/src/input/profile.rs
+//! Key binding profile named by the project's `[input] profile`.
+//!
+//! ```toml
+//! [bindings]
+//! MoveUp = ["Physical.KeyW", "ArrowUp", "Pad.DPadUp"]
+//! Dash = ["Space", "Pad.South"]
+//! ZoomIn = ["Mouse.WheelUp"]
+//!
+//! [axes.Aim]
+//! x = ["Pad.RightStickX", "Mouse.DeltaX"]
+//! y = ["Pad.RightStickY", "Mouse.DeltaY"]
+//! invert_y = true
+//!
+//! [contexts.Menu]
+//! bindings = { MenuBack = ["Escape", "Pad.East"], MenuUp = ["ArrowUp"] }
+//!
+//! [gamepad]
+//! deadzone = 0.15
+//! devices = { "Xbox Wireless Controller" = 0.25 }
+//! ```
+//!
+//! Each listed action's bindings replace the defaults (an empty list unbinds
+//! it); names are the ones `Binding::from_name` accepts, or triggers such as
+//! `"Ctrl+KeyS"` or `"KeyE:hold(500)"` (see `input::triggers`). Actions a plugin
+//! registers later may be named here too: they are registered up front so the
+//! IDs line up. `[axes]` declares named axes (see `input::axes`) and replaces
+//! their sources and settings. `[contexts]` gives input contexts (see
+//! `input::contexts`) their own bindings, replaced per action like `[bindings]`,
+//! and `blocks_keyboard`. `[gamepad]` sets the stick deadzone for every
+//! pad and per device name.
+//!
+//! The file is reloaded when it changes on disk and written by the Key
+//! Bindings window (F3). An input listed under two actions is a conflict: it is
+//! reported, and the action that sorts last keeps it.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use engine_shared::ActionId;
+use serde::{Deserialize, Serialize};
+
+use crate::input::axes::{AxisBinding, AxisSettings, AxisSource};
+use crate::input::contexts::ContextDef;
+use crate::input::gamepad::Gamepads;
+use crate::input::{ActionRegistry, AxisKind, Binding, BindingSpec, InputMap};
+
+/// Where the Key Bindings window saves when the project names no profile.
+pub const DEFAULT_PROFILE_FILE: &str = "input.toml";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct InputProfile {
+    #[serde(default)]
+    pub bindings: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub axes: BTreeMap<String, AxisConfig>,
+    #[serde(default)]
+    pub contexts: BTreeMap<String, ContextConfig>,
+    #[serde(default)]
+    pub gamepad: GamepadSection,
+}
+
+/// One `[axes.Name]` table; with `y` the axis is 2D.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AxisConfig {
+    #[serde(default)]
+    pub x: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub settings: AxisSettings,
+}
+
+/// One `[contexts.Name]` table.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ContextConfig {
+    /// Unset keeps the engine's or a plugin's choice.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocks_keyboard: Option<bool>,
+    #[serde(default)]
+    pub bindings: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct GamepadSection {
+    /// Left stick deadzone for pads not listed in `devices`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deadzone: Option<f32>,
+    /// Deadzone by device name, as shown in the Input Inspector.
+    #[serde(default)]
+    pub devices: BTreeMap<String, f32>,
+}
+
+impl InputProfile {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read input profile {}: {e}", path.display()))?;
+        toml::from_str(&text).map_err(|e| format!("Invalid input profile {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = toml::to_string_pretty(self)
+            .map_err(|e| format!("Cannot serialize input profile: {e}"))?;
+        fs::write(path, text).map_err(|e| format!("Cannot write input profile {}: {e}", path.display()))
+    }
+
+    /// The live bindings of every registered action, the contexts' bound
+    /// actions, and the pads' deadzones.
+    pub fn capture(
+        registry: &ActionRegistry,
+        input_map: &InputMap,
+        contexts: &BTreeMap<String, ContextDef>,
+        gamepads: &Gamepads,
+    ) -> Self {
+        let names_of = |map: &InputMap, id| -> Vec<String> {
+            let inputs = map.bindings_of(id).into_iter().filter_map(Binding::name);
+            let triggers = map.triggers_of(id).into_iter().filter_map(|t| t.name());
+            inputs.chain(triggers).collect()
+        };
+        let bindings = registry
+            .entries()
+            .into_iter()
+            .map(|(name, id)| (name, names_of(input_map, id)))
+            .collect();
+        let contexts = contexts
+            .iter()
+            .map(|(name, def)| {
+                let bindings = registry
+                    .entries()
+                    .into_iter()
+                    .map(|(action, id)| (action, names_of(&def.map, id)))
+                    .filter(|(_, names)| !names.is_empty())
+                    .collect();
+                let config = ContextConfig {
+                    blocks_keyboard: Some(def.blocks_keyboard),
+                    bindings,
+                };
+                (name.clone(), config)
+            })
+            .collect();
+        let axes = registry
+            .axis_entries()
+            .into_iter()
+            .map(|(name, id, kind)| {
+                let binding = input_map.axis_binding(id).cloned().unwrap_or_default();
+                let names = |sources: &[AxisSource]| sources.iter().filter_map(|s| s.name()).collect();
+                let config = AxisConfig {
+                    x: names(&binding.x),
+                    y: (kind == AxisKind::TwoD).then(|| names(&binding.y)),
+                    settings: binding.settings,
+                };
+                (name, config)
+            })
+            .collect();
+        let (deadzone, devices) = gamepads.deadzones();
+
+        Self {
+            bindings,
+            axes,
+            contexts,
+            gamepad: GamepadSection { deadzone, devices },
+        }
+    }
+
+    /// Inputs listed under more than one action, as readable messages.
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut owners: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for (action, names) in &self.bindings {
+            for name in names {
+                let Some(key) = Binding::from_name(name).and_then(Binding::name) else { continue };
+                let actions = owners.entry(key).or_default();
+                if !actions.contains(&action.as_str()) {
+                    actions.push(action);
+                }
+            }
+        }
+        owners
+            .into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(key, actions)| format!("{key} is bound to {}", actions.join(" and ")))
+            .collect()
+    }
+
+    /// Applies the bindings and returns the actions it configured, so later
+    /// plugin default bindings can leave them alone.
+    pub fn apply(&self, registry: &mut ActionRegistry, input_map: &mut InputMap) -> HashSet<ActionId> {
+        let configured = apply_bindings(&self.bindings, registry, input_map);
+
+        for (axis_name, config) in &self.axes {
+            let kind = if config.y.is_some() { AxisKind::TwoD } else { AxisKind::OneD };
+            let axis = match registry.register_axis(axis_name, kind) {
+                Ok(axis) => axis,
+                Err(e) => {
+                    tracing::error!("❌ Input profile: {e}");
+                    continue;
+                }
+            };
+            let sources = |names: &[String]| {
+                names
+                    .iter()
+                    .filter_map(|name| {
+                        let source = AxisSource::from_name(name);
+                        if source.is_none() {
+                            tracing::warn!("⚠️ Input profile: unknown axis source '{name}' for '{axis_name}'");
+                        }
+                        source
+                    })
+                    .collect()
+            };
+            input_map.bind_axis(axis, AxisBinding {
+                x: sources(&config.x),
+                y: sources(config.y.as_deref().unwrap_or_default()),
+                settings: config.settings,
+            });
+        }
+
+        configured
+    }
+
+    /// Applies `[contexts]` on top of `contexts` (creating the ones it names)
+    /// and returns the actions it configured in each.
+    pub fn apply_contexts(
+        &self,
+        registry: &mut ActionRegistry,
+        contexts: &mut BTreeMap<String, ContextDef>,
+    ) -> HashSet<(String, ActionId)> {
+        let mut configured = HashSet::new();
+        for (name, config) in &self.contexts {
+            let def = contexts.entry(name.clone()).or_default();
+            if let Some(blocks_keyboard) = config.blocks_keyboard {
+                def.blocks_keyboard = blocks_keyboard;
+            }
+            let actions = apply_bindings(&config.bindings, registry, &mut def.map);
+            configured.extend(actions.into_iter().map(|action| (name.clone(), action)));
+        }
+        configured
+    }
+
+    /// Gamepad state configured with this profile's deadzones.
+    pub fn gamepads(&self) -> Gamepads {
+        Gamepads::new(self.gamepad.deadzone, self.gamepad.devices.clone())
+    }
+}
+
+/// Replaces the bindings of each listed action, registering it if needed.
+fn apply_bindings(
+    bindings: &BTreeMap<String, Vec<String>>,
+    registry: &mut ActionRegistry,
+    input_map: &mut InputMap,
+) -> HashSet<ActionId> {
+    let mut configured = HashSet::new();
+
+    for (action_name, key_names) in bindings {
+        let action = match registry.register(action_name) {
+            Ok(action) => action,
+            Err(e) => {
+                tracing::error!("❌ Input profile: {e}");
+                continue;
+            }
+        };
+        input_map.unbind_action(action);
+        configured.insert(action);
+
+        for key_name in key_names {
+            match BindingSpec::from_name(key_name) {
+                Ok(binding) => input_map.bind_spec(binding, action),
+                Err(e) => tracing::warn!("⚠️ Input profile: unknown key '{key_name}' for '{action_name}' ({e})"),
+            }
+        }
+    }
+    configured
+}
+
+/// The profile file behind the live bindings, watched for changes.
+pub struct ProfileFile {
+    pub path: PathBuf,
+    /// Modification time when last loaded or saved.
+    modified: Option<SystemTime>,
+    /// Conflicts found in the file as last loaded.
+    pub conflicts: Vec<String>,
+}
+
+impl ProfileFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            modified: None,
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Reads the file (an empty profile if it doesn't exist yet) and reports its conflicts.
+    pub fn load(&mut self) -> Result<InputProfile, String> {
+        self.modified = modified_time(&self.path);
+        if !self.path.exists() {
+            self.conflicts.clear();
+            return Ok(InputProfile::default());
+        }
+
+        let profile = InputProfile::from_file(&self.path)?;
+        self.conflicts = profile.conflicts();
+        for conflict in &self.conflicts {
+            tracing::warn!("⚠️ Input profile {}: {conflict}", self.path.display());
+        }
+        Ok(profile)
+    }
+
+    pub fn save(&mut self, profile: &InputProfile) -> Result<(), String> {
+        profile.save(&self.path)?;
+        self.modified = modified_time(&self.path);
+        self.conflicts = profile.conflicts();
+        Ok(())
+    }
+
+    /// True when the file was written by something else since the last load or save.
+    pub fn changed_on_disk(&self) -> bool {
+        modified_time(&self.path) != self.modified
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::gamepad::PadButton;
+
+    #[test]
+    fn profile_round_trips_through_the_input_map_and_reports_conflicts() {
+        let profile: InputProfile = toml::from_str(
+            "[bindings]\nJump = [\"Space\", \"Pad.South\"]\nDash = [\"Physical.KeyQ\", \"Space\", \"Mouse.Right\"]",
+        )
+        .unwrap();
+        assert_eq!(profile.conflicts(), ["Space is bound to Dash and Jump"]);
+
+        let mut registry = ActionRegistry::default();
+        let mut map = InputMap::default();
+        profile.apply(&mut registry, &mut map);
+        let (dash, jump) = (registry.get_id("Dash").unwrap(), registry.get_id("Jump").unwrap());
+        assert_eq!(map.action_for(Binding::Pad(PadButton::South)), Some(jump));
+
+        // Saving what is live resolves the conflict in favour of the action that kept the key.
+        let saved = InputProfile::capture(&registry, &map, &BTreeMap::new(), &Gamepads::default());
+        assert_eq!(saved.bindings["Dash"], ["Mouse.Right", "Physical.KeyQ"]);
+        assert_eq!(saved.bindings["Jump"], ["Pad.South", "Space"]);
+        assert!(saved.conflicts().is_empty());
+
+        let text = toml::to_string_pretty(&saved).unwrap();
+        let reloaded: InputProfile = toml::from_str(&text).unwrap();
+        let mut map = InputMap::default();
+        reloaded.apply(&mut registry, &mut map);
+        let names: Vec<_> = map.bindings_of(dash).into_iter().filter_map(Binding::name).collect();
+        assert_eq!(names, ["Mouse.Right", "Physical.KeyQ"]);
+    }
+
+    #[test]
+    fn axes_register_with_their_kind_and_round_trip() {
+        let profile: InputProfile = toml::from_str(
+            "[axes.Zoom]\nx = [\"Mouse.Wheel\", \"Minus/Equal\", \"Mouse.Nope\"]\n\
+             [axes.Aim]\nx = [\"Pad.RightStickX\"]\ny = [\"Mouse.DeltaY\"]\ninvert_y = true\ncurve = \"cubic\"",
+        )
+        .unwrap();
+        let mut registry = ActionRegistry::default();
+        let mut map = InputMap::default();
+        profile.apply(&mut registry, &mut map);
+
+        let (aim, kind) = registry.get_axis("Aim").unwrap();
+        assert_eq!(kind, AxisKind::TwoD);
+        let settings = map.axis_binding(aim).unwrap().settings;
+        assert!(settings.invert_y && !settings.invert_x && settings.sensitivity == 1.0);
+
+        let saved = InputProfile::capture(&registry, &map, &BTreeMap::new(), &Gamepads::default());
+        assert_eq!(saved.axes["Zoom"].x, ["Mouse.Wheel", "Minus/Equal"]);
+        assert!(saved.axes["Zoom"].y.is_none());
+        let text = toml::to_string_pretty(&saved).unwrap();
+        let reloaded: InputProfile = toml::from_str(&text).unwrap();
+        assert_eq!(reloaded.axes["Aim"].y.as_deref(), Some(&["Mouse.DeltaY".to_string()][..]));
+        assert_eq!(reloaded.axes["Aim"].settings, settings);
+    }
+}
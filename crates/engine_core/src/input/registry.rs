@@ -1,25 +1,131 @@
 // crates/engine_core/src/input/registry.rs
 use std::collections::HashMap;
+use engine_shared::input_types::{axes, AxisId, MAX_ACTIONS, MAX_AXES};
 use engine_shared::ActionId;
+use serde::{Deserialize, Serialize};
 
+/// A named axis is one `analog_axes` slot, or two consecutive ones (X, Y).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AxisKind {
+    OneD,
+    TwoD,
+}
+
+impl AxisKind {
+    fn slots(self) -> usize {
+        match self {
+            AxisKind::OneD => 1,
+            AxisKind::TwoD => 2,
+        }
+    }
+}
+
+/// Action and named axis IDs by name.
 #[derive(Default, Clone)]
 pub struct ActionRegistry {
     name_to_id: HashMap<String, ActionId>,
     next_id: ActionId,
+    /// Named axes in registration order; IDs are `analog_axes` indices.
+    axes: Vec<(String, AxisId, AxisKind)>,
 }
 
 impl ActionRegistry {
-    pub fn register(&mut self, name: &str) -> ActionId {
+    /// Returns the name's ID, assigning the next free one to a new name.
+    /// Fails once all `MAX_ACTIONS` IDs are taken (`InputState` has no bit for more).
+    pub fn register(&mut self, name: &str) -> Result<ActionId, String> {
         if let Some(&id) = self.name_to_id.get(name) {
-            return id;
+            return Ok(id);
+        }
+        if self.next_id as usize >= MAX_ACTIONS {
+            return Err(format!(
+                "Cannot register action '{name}': all {MAX_ACTIONS} action IDs are in use"
+            ));
         }
         let id = self.next_id;
         self.name_to_id.insert(name.to_string(), id);
-        self.next_id = self.next_id.wrapping_add(1);
-        id
+        self.next_id += 1;
+        Ok(id)
     }
 
     pub fn get_id(&self, name: &str) -> Option<ActionId> {
         self.name_to_id.get(name).copied()
     }
-}
\ No newline at end of file
+
+    /// Returns the axis's ID, assigning the next free `analog_axes` slot(s) to a
+    /// new name. Fails when the axis exists with the other kind or no slots are left.
+    pub fn register_axis(&mut self, name: &str, kind: AxisKind) -> Result<AxisId, String> {
+        if let Some((_, id, existing)) = self.axes.iter().find(|(n, _, _)| n == name) {
+            return if *existing == kind {
+                Ok(*id)
+            } else {
+                Err(format!("Axis '{name}' is already registered as {existing:?}"))
+            };
+        }
+        let next = self
+            .axes
+            .last()
+            .map_or(axes::FIRST_NAMED, |&(_, id, kind)| id as usize + kind.slots());
+        if next + kind.slots() > MAX_AXES {
+            return Err(format!("Cannot register axis '{name}': all {MAX_AXES} axis slots are in use"));
+        }
+        self.axes.push((name.to_string(), next as AxisId, kind));
+        Ok(next as AxisId)
+    }
+
+    pub fn get_axis(&self, name: &str) -> Option<(AxisId, AxisKind)> {
+        self.axes
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .map(|&(_, id, kind)| (id, kind))
+    }
+
+    /// All named axes, ordered by ID (and so by registration).
+    pub fn axis_entries(&self) -> Vec<(String, AxisId, AxisKind)> {
+        self.axes.clone()
+    }
+
+    /// All registered actions, ordered by ID. Re-registering them in this
+    /// order into an empty registry reproduces the same IDs.
+    pub fn entries(&self) -> Vec<(String, ActionId)> {
+        let mut entries: Vec<_> = self
+            .name_to_id
+            .iter()
+            .map(|(name, &id)| (name.clone(), id))
+            .collect();
+        entries.sort_by_key(|&(_, id)| id);
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_refuses_new_names_once_full() {
+        let mut registry = ActionRegistry::default();
+        for i in 0..MAX_ACTIONS {
+            assert_eq!(registry.register(&format!("A{i}")), Ok(i as ActionId));
+        }
+        assert!(registry.register("OneTooMany").is_err());
+        assert_eq!(registry.register("A7"), Ok(7));
+        assert_eq!(registry.get_id("OneTooMany"), None);
+    }
+
+    #[test]
+    fn axes_take_consecutive_slots_after_the_builtin_ones() {
+        let mut registry = ActionRegistry::default();
+        let first = axes::FIRST_NAMED as AxisId;
+        assert_eq!(registry.register_axis("Aim", AxisKind::TwoD), Ok(first));
+        assert_eq!(registry.register_axis("Zoom", AxisKind::OneD), Ok(first + 2));
+        assert_eq!(registry.register_axis("Aim", AxisKind::TwoD), Ok(first));
+        assert!(registry.register_axis("Zoom", AxisKind::TwoD).is_err());
+
+        let free = MAX_AXES - axes::FIRST_NAMED - 3;
+        for i in 0..free {
+            registry.register_axis(&format!("Axis{i}"), AxisKind::OneD).unwrap();
+        }
+        assert!(registry.register_axis("Full", AxisKind::OneD).is_err());
+        assert_eq!(registry.get_axis("Zoom"), Some((first + 2, AxisKind::OneD)));
+    }
+}
@@ -0,0 +1,424 @@
+// crates/engine_core/src/input/triggers.rs
+//! Trigger stage: bindings that need more than "held = active", evaluated
+//! between `InputPoller` and the `Arbiter`.
+//!
+//! Triggers are declared by name wherever a binding is (input profile,
+//! `bind_default_key`):
+//!
+//! - `Ctrl+KeyS`, `Shift+Mouse.Left`: chord, active while all are held.
+//! - `KeyE:hold(500)`: active once held for 500 ms, until released.
+//! - `KeyE:tap(200)`: fires when released within 200 ms of the press.
+//! - `KeyE:double(300)`: fires on a second press within 300 ms.
+//! - `ArrowDown>ArrowRight>KeyJ`: fires when pressed in this order, each step
+//!   within 400 ms of the last (`:within(600)` changes that).
+//!
+//! `Ctrl`, `Shift`, `Alt` and `Super` match either side of the keyboard, and a
+//! mode applies to a whole chord (`Ctrl+KeyE:hold(500)`). A trigger that fires
+//! keeps its action active until a simulation tick has run. While a chord is
+//! held, its last input doesn't also trigger its own plain binding, so `Ctrl+KeyS`
+//! doesn't move down.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use engine_shared::input_types::{ActionId, PriorityLayer};
+use winit::keyboard::KeyCode;
+
+use crate::input::arbiter::ActionSignal;
+use crate::input::{Arbiter, Binding, InputMap};
+
+/// Sequence steps must follow each other within this many ms unless `:within` says otherwise.
+pub const DEFAULT_SEQUENCE_WINDOW_MS: u32 = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+    Super,
+}
+
+impl Modifier {
+    const ALL: [(Modifier, &'static str, [KeyCode; 2]); 4] = [
+        (Modifier::Ctrl, "Ctrl", [KeyCode::ControlLeft, KeyCode::ControlRight]),
+        (Modifier::Shift, "Shift", [KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+        (Modifier::Alt, "Alt", [KeyCode::AltLeft, KeyCode::AltRight]),
+        (Modifier::Super, "Super", [KeyCode::SuperLeft, KeyCode::SuperRight]),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(_, n, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(m, _, _)| m)
+    }
+
+    fn entry(self) -> &'static (Modifier, &'static str, [KeyCode; 2]) {
+        Self::ALL.iter().find(|(m, _, _)| *m == self).expect("every modifier is listed")
+    }
+
+    pub fn name(self) -> &'static str {
+        self.entry().1
+    }
+
+    fn keys(self) -> [KeyCode; 2] {
+        self.entry().2
+    }
+}
+
+/// One part of a chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriggerInput {
+    Input(Binding),
+    Modifier(Modifier),
+}
+
+impl TriggerInput {
+    fn is_held(self, held: &HashSet<Binding>) -> bool {
+        match self {
+            TriggerInput::Input(input) => held.contains(&held_form(input)),
+            TriggerInput::Modifier(m) => m.keys().iter().any(|&k| held.contains(&Binding::Key(k))),
+        }
+    }
+
+    pub(crate) fn covers(self, input: Binding) -> bool {
+        match self {
+            TriggerInput::Input(own) => held_form(own) == input,
+            TriggerInput::Modifier(m) => m.keys().iter().any(|&k| Binding::Key(k) == input),
+        }
+    }
+
+    fn name(self) -> Option<String> {
+        match self {
+            TriggerInput::Input(input) => input.name(),
+            TriggerInput::Modifier(m) => Some(m.name().to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriggerMode {
+    /// Active while the chord is held.
+    Press,
+    /// Active once held this many ms.
+    Hold(u32),
+    /// Fires on release within this many ms.
+    Tap(u32),
+    /// Fires on a second press within this many ms.
+    DoubleTap(u32),
+    /// Fires when every step was pressed in order, each within this many ms.
+    Sequence(u32),
+}
+
+/// A binding with a chord, a timing mode or several steps (see the module docs).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Trigger {
+    /// Chords in order; more than one only for `TriggerMode::Sequence`. The last
+    /// input of a chord is the one that completes it.
+    pub steps: Vec<Vec<TriggerInput>>,
+    pub mode: TriggerMode,
+}
+
+impl Trigger {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        let (body, suffix) = match name.split_once(':') {
+            Some((body, suffix)) => (body, Some(suffix.trim())),
+            None => (name, None),
+        };
+
+        let steps = body
+            .split('>')
+            .map(|step| step.split('+').map(|part| parse_input(part, name)).collect())
+            .collect::<Result<Vec<Vec<_>>, _>>()?;
+
+        let mode = match suffix {
+            None if steps.len() > 1 => TriggerMode::Sequence(DEFAULT_SEQUENCE_WINDOW_MS),
+            None => TriggerMode::Press,
+            Some(suffix) => parse_mode(suffix).ok_or_else(|| {
+                format!("Unknown trigger mode '{suffix}' in '{name}' (hold, tap, double or within, with ms)")
+            })?,
+        };
+
+        match (mode, steps.len()) {
+            (TriggerMode::Sequence(_), 1) => Err(format!("'{name}': within() needs a sequence (A>B)")),
+            (TriggerMode::Sequence(_), _) | (_, 1) => Ok(Self { steps, mode }),
+            _ => Err(format!("'{name}': a sequence can't also be a hold, tap or double tap")),
+        }
+    }
+
+    /// The name `from_name` reads back; `None` if an input has no name.
+    pub fn name(&self) -> Option<String> {
+        let mut steps = Vec::with_capacity(self.steps.len());
+        for chord in &self.steps {
+            let parts: Vec<String> = chord.iter().map(|p| p.name()).collect::<Option<_>>()?;
+            steps.push(parts.join("+"));
+        }
+        let suffix = match self.mode {
+            TriggerMode::Press => String::new(),
+            TriggerMode::Hold(ms) => format!(":hold({ms})"),
+            TriggerMode::Tap(ms) => format!(":tap({ms})"),
+            TriggerMode::DoubleTap(ms) => format!(":double({ms})"),
+            TriggerMode::Sequence(DEFAULT_SEQUENCE_WINDOW_MS) => String::new(),
+            TriggerMode::Sequence(ms) => format!(":within({ms})"),
+        };
+        Some(format!("{}{suffix}", steps.join(">")))
+    }
+}
+
+fn parse_input(part: &str, trigger: &str) -> Result<TriggerInput, String> {
+    let part = part.trim();
+    Modifier::from_name(part)
+        .map(TriggerInput::Modifier)
+        .or_else(|| Binding::from_name(part).map(TriggerInput::Input))
+        .ok_or_else(|| format!("Unknown input '{part}' in trigger '{trigger}'"))
+}
+
+fn parse_mode(suffix: &str) -> Option<TriggerMode> {
+    let (word, rest) = suffix.split_once('(')?;
+    let ms: u32 = rest.strip_suffix(')')?.trim().parse().ok()?;
+    match word.trim().to_ascii_lowercase().as_str() {
+        "hold" => Some(TriggerMode::Hold(ms)),
+        "tap" => Some(TriggerMode::Tap(ms)),
+        "double" => Some(TriggerMode::DoubleTap(ms)),
+        "within" => Some(TriggerMode::Sequence(ms)),
+        _ => None,
+    }
+}
+
+/// How an input appears in the held set: keys by code, whichever way they're bound.
+pub(crate) fn held_form(input: Binding) -> Binding {
+    match input {
+        Binding::Physical(key) => Binding::Key(key),
+        other => other,
+    }
+}
+
+fn chord_down(chord: &[TriggerInput], held: &HashSet<Binding>) -> bool {
+    chord.iter().all(|part| part.is_held(held))
+}
+
+#[derive(Debug, Clone, Default)]
+struct TriggerState {
+    action: ActionId,
+    /// When the current press began (hold, tap).
+    down_since: Option<Instant>,
+    /// First press of a possible double tap.
+    first_tap: Option<Instant>,
+    /// Sequence steps matched so far, and when the last one was.
+    step: usize,
+    step_at: Option<Instant>,
+    /// Fired; stays active until a simulation tick has run.
+    pulse: bool,
+    active: bool,
+}
+
+/// Per-trigger state, updated once per frame from the inputs held.
+#[derive(Default)]
+pub struct TriggerStage {
+    states: HashMap<Trigger, TriggerState>,
+    /// Inputs held last frame (keys as `Binding::Key`).
+    previous: HashSet<Binding>,
+    /// Inputs held as the last part of an active chord this frame.
+    consumed: HashSet<Binding>,
+    now: Option<Instant>,
+}
+
+impl TriggerStage {
+    /// Advances every trigger in `input_map` to `now`, given the inputs held
+    /// (keys as `Binding::Key`, plus mouse and pad inputs).
+    pub fn update(&mut self, input_map: &InputMap, held: &HashSet<Binding>, now: Instant) {
+        let triggers = input_map.triggers();
+        self.states.retain(|t, _| triggers.iter().any(|(bound, _)| bound == t));
+        self.consumed.clear();
+        let newly_held: Vec<Binding> = held.difference(&self.previous).copied().collect();
+
+        for (trigger, action) in triggers {
+            let state = self.states.entry(trigger.clone()).or_default();
+            state.action = *action;
+            let chord = &trigger.steps[0];
+            let down = chord_down(chord, held);
+            let was_down = chord_down(chord, &self.previous);
+            let within = |since: Option<Instant>, ms: u32| {
+                since.is_some_and(|t| now.duration_since(t) <= Duration::from_millis(ms as u64))
+            };
+
+            let mut held_active = false;
+            match trigger.mode {
+                TriggerMode::Press => held_active = down,
+                TriggerMode::Hold(ms) => {
+                    if down {
+                        let since = *state.down_since.get_or_insert(now);
+                        held_active = now.duration_since(since) >= Duration::from_millis(ms as u64);
+                    } else {
+                        state.down_since = None;
+                    }
+                }
+                TriggerMode::Tap(ms) => {
+                    if down && !was_down {
+                        state.down_since = Some(now);
+                    } else if !down && was_down {
+                        state.pulse |= within(state.down_since, ms);
+                        state.down_since = None;
+                    }
+                }
+                TriggerMode::DoubleTap(ms) => {
+                    if down && !was_down {
+                        if within(state.first_tap, ms) {
+                            state.pulse = true;
+                            state.first_tap = None;
+                        } else {
+                            state.first_tap = Some(now);
+                        }
+                    }
+                }
+                TriggerMode::Sequence(ms) => {
+                    if state.step > 0 && !within(state.step_at, ms) {
+                        state.step = 0;
+                    }
+                    let pressed = |step: &[TriggerInput]| chord_down(step, held) && !chord_down(step, &self.previous);
+                    let expected = &trigger.steps[state.step];
+                    if pressed(expected) {
+                        state.step += 1;
+                        state.step_at = Some(now);
+                    } else if newly_held.iter().any(|&input| !expected.iter().any(|p| p.covers(input))) {
+                        // A wrong input starts over, possibly as the first step.
+                        state.step = usize::from(pressed(&trigger.steps[0]));
+                        state.step_at = Some(now);
+                    }
+                    if state.step == trigger.steps.len() {
+                        state.step = 0;
+                        state.pulse = true;
+                    }
+                }
+            }
+            state.active = held_active || state.pulse;
+
+            // A held chord keeps its last input from also pressing that input's own binding.
+            let is_chord = chord.len() > 1 && !matches!(trigger.mode, TriggerMode::Sequence(_));
+            if is_chord && down {
+                if let Some(&TriggerInput::Input(last)) = chord.last() {
+                    self.consumed.insert(held_form(last));
+                }
+            }
+        }
+
+        self.previous.clone_from(held);
+        self.now = Some(now);
+    }
+
+    /// Inputs the plain bindings should ignore this frame.
+    pub fn consumed(&self) -> &HashSet<Binding> {
+        &self.consumed
+    }
+
+    /// Adds the active triggers' actions to the Arbiter (after the pollers).
+    pub fn feed_arbiter(&self, arbiter: &mut Arbiter) {
+        for state in self.states.values().filter(|s| s.active) {
+            arbiter.add_action(ActionSignal {
+                layer: PriorityLayer::Control,
+                action_id: state.action,
+                active: true,
+            });
+        }
+    }
+
+    /// End of frame: fired triggers are released once a simulation tick has seen them.
+    pub fn end_frame(&mut self, ticks_ran: u32) {
+        if ticks_ran > 0 {
+            for state in self.states.values_mut() {
+                state.pulse = false;
+            }
+        }
+    }
+
+    /// `(trigger, action, active, what it is waiting for)` for the Input Inspector.
+    pub fn describe(&self) -> Vec<(String, ActionId, bool, String)> {
+        let mut rows: Vec<_> = self
+            .states
+            .iter()
+            .map(|(trigger, state)| {
+                let name = trigger.name().unwrap_or_else(|| format!("{trigger:?}"));
+                (name, state.action, state.active, self.phase(trigger, state))
+            })
+            .collect();
+        rows.sort();
+        rows
+    }
+
+    fn phase(&self, trigger: &Trigger, state: &TriggerState) -> String {
+        let elapsed = |since: Instant| self.now.map_or(0, |now| now.duration_since(since).as_millis());
+        match (trigger.mode, state.down_since, state.first_tap) {
+            _ if state.pulse => "fired".to_string(),
+            _ if state.active => "active".to_string(),
+            (TriggerMode::Hold(ms), Some(since), _) => format!("holding {}/{ms} ms", elapsed(since)),
+            (TriggerMode::Tap(ms), Some(since), _) => format!("pressed {}/{ms} ms", elapsed(since)),
+            (TriggerMode::DoubleTap(ms), _, Some(first)) => format!("second tap? {}/{ms} ms", elapsed(first)),
+            (TriggerMode::Sequence(_), _, _) if state.step > 0 => {
+                format!("step {}/{}", state.step, trigger.steps.len())
+            }
+            _ => "idle".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputMap;
+
+    fn held(inputs: &[KeyCode]) -> HashSet<Binding> {
+        inputs.iter().map(|&k| Binding::Key(k)).collect()
+    }
+
+    #[test]
+    fn triggers_parse_and_fire_by_their_rules() {
+        let chord = Trigger::from_name("ctrl+s").unwrap();
+        assert_eq!(chord.name().as_deref(), Some("Ctrl+KeyS"));
+        assert_eq!(Trigger::from_name("A>B:within(250)").unwrap().name().as_deref(), Some("KeyA>KeyB:within(250)"));
+        assert!(Trigger::from_name("KeyE:hold").is_err());
+        assert!(Trigger::from_name("A>B:tap(100)").is_err());
+        assert!(Trigger::from_name("Ctrl+Nope").is_err());
+
+        let mut map = InputMap::default();
+        map.bind_trigger(chord, 1);
+        map.bind_trigger(Trigger::from_name("KeyE:hold(500)").unwrap(), 2);
+        map.bind_trigger(Trigger::from_name("KeyQ:double(300)").unwrap(), 3);
+        map.bind_trigger(Trigger::from_name("ArrowDown>ArrowRight>KeyJ").unwrap(), 4);
+
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+        let mut stage = TriggerStage::default();
+        let mut frame = |keys: &[KeyCode], ms: u64| {
+            stage.update(&map, &held(keys), at(ms));
+            let active: Vec<_> = stage.states.values().filter(|s| s.active).map(|s| s.action).collect();
+            let consumed = stage.consumed().contains(&Binding::Key(KeyCode::KeyS));
+            stage.end_frame(1);
+            (active, consumed)
+        };
+
+        assert_eq!(frame(&[KeyCode::KeyS], 0), (vec![], false));
+        assert_eq!(frame(&[KeyCode::ControlRight, KeyCode::KeyS], 10), (vec![1], true));
+        assert_eq!(frame(&[KeyCode::KeyE], 20), (vec![], false));
+        assert_eq!(frame(&[KeyCode::KeyE], 400), (vec![], false));
+        assert_eq!(frame(&[KeyCode::KeyE], 520), (vec![2], false));
+
+        assert_eq!(frame(&[KeyCode::KeyQ], 600), (vec![], false));
+        assert_eq!(frame(&[], 650), (vec![], false));
+        assert_eq!(frame(&[KeyCode::KeyQ], 800), (vec![3], false));
+        assert_eq!(frame(&[], 810), (vec![], false));
+
+        // A wrong key starts the combo over, and so does a pause longer than the window.
+        let (down, right, j) = (KeyCode::ArrowDown, KeyCode::ArrowRight, KeyCode::KeyJ);
+        let presses = [(down, 1000), (KeyCode::KeyX, 1100), (right, 1200), (j, 1300)];
+        let presses = presses.into_iter().chain([(down, 2000), (right, 2100), (j, 2600)]);
+        for (key, ms) in presses {
+            assert_eq!(frame(&[key], ms), (vec![], false));
+            assert_eq!(frame(&[], ms + 20), (vec![], false));
+        }
+        assert_eq!(frame(&[down], 3000).0, vec![]);
+        assert_eq!(frame(&[down, right], 3100).0, vec![]);
+        assert_eq!(frame(&[j], 3200).0, vec![4]);
+        assert_eq!(frame(&[], 3220).0, vec![]);
+    }
+}
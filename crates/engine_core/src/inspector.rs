@@ -1,8 +1,16 @@
-use egui::{Color32, Context, Ui};
-use engine_shared::{PriorityLayer, ActionSignal, MovementSignal};
-use crate::input::Arbiter;
+use egui::{Color32, Context};
+use engine_shared::PriorityLayer;
+use crate::input::gamepad::{self, Gamepads};
+use crate::input::{Arbiter, InputContexts, TriggerStage};
 
-pub fn show(ctx: &Context, arbiter: &Arbiter, open: &mut bool) {
+pub fn show(
+    ctx: &Context,
+    arbiter: &Arbiter,
+    gamepads: &mut Gamepads,
+    triggers: &TriggerStage,
+    contexts: &InputContexts,
+    open: &mut bool,
+) {
     egui::Window::new("Input Inspector")
         .open(open)
         .show(ctx, |ui| {
@@ -70,5 +78,79 @@ pub fn show(ctx: &Context, arbiter: &Arbiter, open: &mut bool) {
                     ));
                 }
             });
+
+            ui.separator();
+
+            // 3. INPUT CONTEXTS (top first; each takes its inputs from those below)
+            ui.collapsing(format!("Input Contexts ({} active)", contexts.stack().len()), |ui| {
+                for name in contexts.stack().iter().rev() {
+                    let note = match contexts.definitions().get(name) {
+                        Some(def) if def.blocks_keyboard => " (blocks keyboard)",
+                        Some(_) => "",
+                        None => " (no bindings)",
+                    };
+                    ui.colored_label(Color32::GREEN, format!("  • {name}{note}"));
+                }
+                ui.colored_label(Color32::from_gray(160), "  • Gameplay");
+            });
+
+            ui.separator();
+
+            // 4. TRIGGERS (chords, holds, taps, sequences; fired ones wait for a tick)
+            let rows = triggers.describe();
+            ui.collapsing(format!("Triggers ({})", rows.len()), |ui| {
+                if rows.is_empty() {
+                    ui.label("No trigger bound.");
+                }
+                for (name, action_id, active, phase) in rows {
+                    let color = if active { Color32::GREEN } else { Color32::GRAY };
+                    ui.colored_label(color, format!("{name} -> ID: {action_id} ({phase})"));
+                }
+            });
+
+            ui.separator();
+
+            // 5. GAMEPADS (left stick -> Control-layer movement)
+            ui.collapsing(format!("Gamepads ({} connected)", gamepads.connected().count()), |ui| {
+                if gamepads.devices.is_empty() {
+                    ui.label("No gamepad seen yet.");
+                }
+
+                // Deadzone edits apply after the loop (they also cover reconnects).
+                let mut deadzone_edit = None;
+                for device in &gamepads.devices {
+                    let (color, status) = if device.connected {
+                        (Color32::GREEN, "connected")
+                    } else {
+                        (Color32::GRAY, "disconnected")
+                    };
+                    ui.horizontal(|ui| {
+                        ui.strong(&device.name);
+                        ui.colored_label(color, status);
+                    });
+
+                    let movement = device.movement();
+                    ui.label(format!(
+                        "Stick ({:.2}, {:.2}) -> ({:.2}, {:.2})",
+                        device.stick.x, device.stick.y, movement.x, movement.y
+                    ));
+
+                    let held: Vec<_> = device.buttons.iter().map(|&b| gamepad::button_name(b)).collect();
+                    ui.label(format!("Buttons: {}", if held.is_empty() { "-".to_string() } else { held.join(" ") }));
+
+                    let mut deadzone = device.deadzone;
+                    if ui
+                        .add(egui::Slider::new(&mut deadzone, 0.0..=0.95).text("Deadzone"))
+                        .changed()
+                    {
+                        deadzone_edit = Some((device.name.clone(), deadzone));
+                    }
+                    ui.add_space(4.0);
+                }
+
+                if let Some((name, deadzone)) = deadzone_edit {
+                    gamepads.set_deadzone(&name, deadzone);
+                }
+            });
         });
 }
\ No newline at end of file
@@ -3,18 +3,35 @@
 
 // Logic Modules
 pub mod app;
+pub mod bindings_ui;
 pub mod input;
 pub mod inspector; // <--- New Module
+pub mod commands;
+pub mod console;
+pub mod cvars;
+pub mod logging;
+pub mod message_bus;
 pub mod host;   // <--- NEW
 pub mod scene;  // <--- NEW
+pub mod prefabs;
+pub mod project;
 pub mod engine_loop;
 pub mod platform_runner;
+pub mod replay;
 
 // Internal Implementation Modules
 
 mod renderer;
 pub mod gui;            // <--- NEW
 pub mod plugin_manager; // <--- NEW
+pub mod plugin_manifest;
+pub mod plugin_sandbox;
+pub mod plugin_script;
+pub mod plugin_status;
+pub mod plugin_ui;
+pub mod plugin_wasm;
+pub mod plugin_watchdog;
+pub mod world_snapshot;
 
 // Re-export App so the Editor crate can find it easily
 pub use app::App;
\ No newline at end of file
@@ -0,0 +1,183 @@
+// crates/engine_core/src/logging.rs
+//! Routes host and plugin log output through `tracing`, and keeps a bounded
+//! in-memory copy for the in-game console.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use engine_shared::plugin_api::LogLevel;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+/// Oldest entries are dropped beyond this many.
+const MAX_ENTRIES: usize = 5000;
+
+/// Field name carrying the plugin-supplied target on bridged events.
+const PLUGIN_TARGET_FIELD: &str = "plugin_target";
+
+static ENTRIES: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+static FRAME: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSource {
+    Host,
+    Plugin,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub frame: u64,
+    pub level: Level,
+    pub source: LogSource,
+    pub target: String,
+    pub message: String,
+}
+
+/// Installs the global subscriber: formatted output on stderr plus the console buffer.
+/// Safe to call more than once; later calls are ignored.
+pub fn init() {
+    let _ = tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(ConsoleLayer)
+        .try_init();
+}
+
+/// Advances the frame counter used to group console entries.
+pub fn begin_frame() {
+    FRAME.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn current_frame() -> u64 {
+    FRAME.load(Ordering::Relaxed)
+}
+
+/// Runs `f` over the buffered entries (oldest first) without copying them.
+pub fn with_entries<R>(f: impl FnOnce(&VecDeque<LogEntry>) -> R) -> R {
+    let entries = ENTRIES.lock().unwrap_or_else(|e| e.into_inner());
+    f(&entries)
+}
+
+pub fn clear() {
+    ENTRIES.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// `HostInterface::log` implementation: forwards plugin messages into `tracing`.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_log(
+    level: u32,
+    target_ptr: *const u8,
+    target_len: usize,
+    msg_ptr: *const u8,
+    msg_len: usize,
+) {
+    let target = unsafe { str_from_raw(target_ptr, target_len) }.unwrap_or("plugin");
+    let Some(msg) = (unsafe { str_from_raw(msg_ptr, msg_len) }) else {
+        return;
+    };
+
+    // `tracing` needs static levels, hence one macro call per arm.
+    match LogLevel::from_u32(level) {
+        LogLevel::Error => tracing::error!(target: "plugin", plugin_target = target, "{msg}"),
+        LogLevel::Warn => tracing::warn!(target: "plugin", plugin_target = target, "{msg}"),
+        LogLevel::Info => tracing::info!(target: "plugin", plugin_target = target, "{msg}"),
+        LogLevel::Debug => tracing::debug!(target: "plugin", plugin_target = target, "{msg}"),
+        LogLevel::Trace => tracing::trace!(target: "plugin", plugin_target = target, "{msg}"),
+    }
+}
+
+/// Borrows a UTF-8 ptr+len argument from the FFI boundary (`None` if null or invalid).
+pub(crate) unsafe fn str_from_raw<'a>(ptr: *const u8, len: usize) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    std::str::from_utf8(std::slice::from_raw_parts(ptr, len)).ok()
+}
+
+/// `tracing` layer that copies every event into the console buffer.
+struct ConsoleLayer;
+
+impl<S: Subscriber> Layer<S> for ConsoleLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = EntryVisitor::default();
+        event.record(&mut visitor);
+
+        let meta = event.metadata();
+        let (source, target) = match visitor.plugin_target {
+            Some(target) => (LogSource::Plugin, target),
+            None => (LogSource::Host, meta.target().to_string()),
+        };
+
+        let mut entries = ENTRIES.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(LogEntry {
+            frame: current_frame(),
+            level: *meta.level(),
+            source,
+            target,
+            message: visitor.message,
+        });
+    }
+}
+
+#[derive(Default)]
+struct EntryVisitor {
+    message: String,
+    plugin_target: Option<String>,
+}
+
+impl Visit for EntryVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == PLUGIN_TARGET_FIELD {
+            self.plugin_target = Some(value.to_string());
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.message, " {}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plugin_and_host_events_reach_the_console_buffer() {
+        let subscriber = tracing_subscriber::registry().with(ConsoleLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            let (target, msg) = ("enemy_ai", "bridged from a plugin");
+            host_log(LogLevel::Warn as u32, target.as_ptr(), target.len(), msg.as_ptr(), msg.len());
+            // A null message is dropped; a null target falls back to "plugin".
+            host_log(LogLevel::Info as u32, target.as_ptr(), target.len(), std::ptr::null(), 0);
+            host_log(LogLevel::Error as u32, std::ptr::null(), 0, msg.as_ptr(), msg.len());
+            tracing::info!(answer = 42, "host side");
+        });
+
+        let ours: Vec<LogEntry> = with_entries(|entries| {
+            entries
+                .iter()
+                .filter(|e| e.message.contains("bridged from a plugin") || e.message.starts_with("host side"))
+                .cloned()
+                .collect()
+        });
+        assert_eq!(ours.len(), 3);
+        assert_eq!((ours[0].level, ours[0].source, ours[0].target.as_str()), (Level::WARN, LogSource::Plugin, "enemy_ai"));
+        assert_eq!((ours[1].level, ours[1].target.as_str()), (Level::ERROR, "plugin"));
+        assert_eq!(ours[2].source, LogSource::Host);
+        assert_eq!(ours[2].target, module_path!());
+        assert_eq!(ours[2].message, "host side answer=42");
+    }
+}
@@ -0,0 +1,306 @@
+// crates/engine_core/src/message_bus.rs
+//! Host-owned publish/subscribe bus between plugins.
+//!
+//! Messages are a topic name plus opaque bytes, optionally tagged with a
+//! `schema` id (0 = untagged) so receivers can reject layouts they don't know.
+//! Delivery happens at tick boundaries: everything published during tick N
+//! lands in subscribers' inboxes at the start of tick N+1, in publish order,
+//! so what a plugin sees never depends on where it sits in the update order.
+//!
+//! Subscriptions and inboxes belong to the plugin's *name*, not its library, so
+//! both survive a hot reload of either side: the reloaded plugin reads whatever
+//! arrived while it was away. Plugins poll their inbox; the host never calls
+//! into a plugin to deliver.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::Mutex;
+
+use engine_shared::plugin_api::BusMessageHeader;
+use serde::{Deserialize, Serialize};
+
+use crate::logging;
+
+/// Undelivered messages kept per subscriber; the oldest are dropped beyond this
+/// (e.g. while a subscriber sits in `PausedError`).
+pub const MAX_INBOX: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BusMessage {
+    pub topic: String,
+    pub schema: u64,
+    pub payload: Vec<u8>,
+    /// Publishing plugin (or "host").
+    pub sender: String,
+}
+
+#[derive(Default)]
+struct Bus {
+    /// Published this tick; delivered by the next `advance`.
+    pending: Vec<BusMessage>,
+    subscriptions: BTreeMap<String, BTreeSet<String>>,
+    inboxes: BTreeMap<String, VecDeque<BusMessage>>,
+}
+
+static BUS: Mutex<Option<Bus>> = Mutex::new(None);
+
+thread_local! {
+    /// Plugin the host is currently calling into; host functions act on its behalf.
+    static CURRENT_PLUGIN: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn with_bus<R>(f: impl FnOnce(&mut Bus) -> R) -> R {
+    let mut guard = BUS.lock().unwrap_or_else(|e| e.into_inner());
+    f(guard.get_or_insert_with(Bus::default))
+}
+
+/// Runs `f` (a call into a plugin) with `plugin` as the caller of any bus functions it uses.
+pub fn as_plugin<R>(plugin: &str, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_PLUGIN.with(|p| p.borrow_mut().replace(plugin.to_string()));
+    let result = f();
+    CURRENT_PLUGIN.with(|p| *p.borrow_mut() = previous);
+    result
+}
+
+/// The plugin inside the current `as_plugin` call, if any.
+pub fn current_plugin() -> Option<String> {
+    CURRENT_PLUGIN.with(|p| p.borrow().clone())
+}
+
+pub fn publish(sender: &str, topic: &str, schema: u64, payload: &[u8]) {
+    with_bus(|bus| {
+        bus.pending.push(BusMessage {
+            topic: topic.to_string(),
+            schema,
+            payload: payload.to_vec(),
+            sender: sender.to_string(),
+        })
+    });
+}
+
+pub fn subscribe(subscriber: &str, topic: &str) {
+    with_bus(|bus| {
+        bus.subscriptions
+            .entry(subscriber.to_string())
+            .or_default()
+            .insert(topic.to_string());
+    });
+}
+
+pub fn unsubscribe(subscriber: &str, topic: &str) {
+    with_bus(|bus| {
+        if let Some(topics) = bus.subscriptions.get_mut(subscriber) {
+            topics.remove(topic);
+        }
+    });
+}
+
+pub fn subscriptions(subscriber: &str) -> Vec<String> {
+    with_bus(|bus| {
+        bus.subscriptions
+            .get(subscriber)
+            .map(|topics| topics.iter().cloned().collect())
+            .unwrap_or_default()
+    })
+}
+
+/// Replaces `subscriber`'s topics (used to mirror a sandboxed plugin's subscriptions).
+pub fn set_subscriptions(subscriber: &str, topics: Vec<String>) {
+    with_bus(|bus| {
+        bus.subscriptions
+            .insert(subscriber.to_string(), topics.into_iter().collect());
+    });
+}
+
+/// Forgets a plugin for good: its subscriptions, inbox and anything it published this tick.
+pub fn remove_subscriber(subscriber: &str) {
+    with_bus(|bus| {
+        bus.subscriptions.remove(subscriber);
+        bus.inboxes.remove(subscriber);
+        bus.pending.retain(|m| m.sender != subscriber);
+    });
+}
+
+/// Withdraws what `sender` published during the current tick (its tick was rolled back).
+pub fn retract(sender: &str) {
+    with_bus(|bus| bus.pending.retain(|m| m.sender != sender));
+}
+
+/// Tick boundary: moves last tick's messages into the inbox of every subscriber.
+pub fn advance() {
+    with_bus(|bus| {
+        for message in std::mem::take(&mut bus.pending) {
+            for (subscriber, topics) in &bus.subscriptions {
+                if !topics.contains(&message.topic) {
+                    continue;
+                }
+                let inbox = bus.inboxes.entry(subscriber.clone()).or_default();
+                if inbox.len() >= MAX_INBOX {
+                    inbox.pop_front();
+                    tracing::warn!("⚠️ Bus inbox of '{subscriber}' full; dropped its oldest message");
+                }
+                inbox.push_back(message.clone());
+            }
+        }
+    });
+}
+
+/// Removes and returns `subscriber`'s oldest delivered message.
+pub fn receive(subscriber: &str) -> Option<BusMessage> {
+    with_bus(|bus| bus.inboxes.get_mut(subscriber)?.pop_front())
+}
+
+/// Removes and returns everything delivered to `subscriber`.
+pub fn take_inbox(subscriber: &str) -> Vec<BusMessage> {
+    with_bus(|bus| {
+        bus.inboxes
+            .get_mut(subscriber)
+            .map(|inbox| inbox.drain(..).collect())
+            .unwrap_or_default()
+    })
+}
+
+/// Appends messages straight to `subscriber`'s inbox (sandbox child side).
+pub fn deliver(subscriber: &str, messages: Vec<BusMessage>) {
+    with_bus(|bus| bus.inboxes.entry(subscriber.to_string()).or_default().extend(messages));
+}
+
+/// Removes and returns everything published this tick (sandbox child side).
+pub fn take_published() -> Vec<BusMessage> {
+    with_bus(|bus| std::mem::take(&mut bus.pending))
+}
+
+/// `(messages in flight, subscribers)` for the plugin status window.
+pub fn stats() -> (usize, usize) {
+    with_bus(|bus| {
+        let queued = bus.pending.len() + bus.inboxes.values().map(VecDeque::len).sum::<usize>();
+        (queued, bus.subscriptions.len())
+    })
+}
+
+// ----------------------------------------------------------------------------------
+// HostInterface functions (act on behalf of `current_plugin()`)
+// ----------------------------------------------------------------------------------
+
+fn caller() -> Option<String> {
+    let caller = current_plugin();
+    if caller.is_none() {
+        tracing::warn!("⚠️ Bus call outside of a plugin call ignored");
+    }
+    caller
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_bus_publish(
+    topic_ptr: *const u8,
+    topic_len: usize,
+    schema: u64,
+    payload_ptr: *const u8,
+    payload_len: usize,
+) -> bool {
+    let Some(topic) = unsafe { logging::str_from_raw(topic_ptr, topic_len) }.filter(|t| !t.is_empty()) else {
+        return false;
+    };
+    let payload = if payload_ptr.is_null() || payload_len == 0 {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(payload_ptr, payload_len) }
+    };
+    let Some(sender) = caller() else { return false };
+    publish(&sender, topic, schema, payload);
+    true
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_bus_subscribe(topic_ptr: *const u8, topic_len: usize) -> bool {
+    let Some(topic) = unsafe { logging::str_from_raw(topic_ptr, topic_len) }.filter(|t| !t.is_empty()) else {
+        return false;
+    };
+    let Some(subscriber) = caller() else { return false };
+    subscribe(&subscriber, topic);
+    true
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_bus_unsubscribe(topic_ptr: *const u8, topic_len: usize) -> bool {
+    let Some(topic) = (unsafe { logging::str_from_raw(topic_ptr, topic_len) }) else {
+        return false;
+    };
+    let Some(subscriber) = caller() else { return false };
+    unsubscribe(&subscriber, topic);
+    true
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn host_bus_receive(header: *mut BusMessageHeader, buf: *mut u8, buf_len: usize) -> bool {
+    if header.is_null() {
+        return false;
+    }
+    let Some(subscriber) = caller() else {
+        unsafe { *header = BusMessageHeader::default() };
+        return false;
+    };
+
+    with_bus(|bus| {
+        let inbox = bus.inboxes.get_mut(&subscriber);
+        let Some(message) = inbox.as_ref().and_then(|inbox| inbox.front()) else {
+            unsafe { *header = BusMessageHeader::default() };
+            return false;
+        };
+
+        let (topic, payload) = (message.topic.as_bytes(), &message.payload);
+        unsafe {
+            *header = BusMessageHeader {
+                schema: message.schema,
+                topic_len: topic.len(),
+                payload_len: payload.len(),
+            };
+        }
+        if buf.is_null() || buf_len < topic.len() + payload.len() {
+            return false;
+        }
+        unsafe {
+            std::ptr::copy_nonoverlapping(topic.as_ptr(), buf, topic.len());
+            std::ptr::copy_nonoverlapping(payload.as_ptr(), buf.add(topic.len()), payload.len());
+        }
+        inbox.and_then(|inbox| inbox.pop_front());
+        true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delivery_waits_for_the_tick_boundary_and_survives_resubscribing() {
+        subscribe("bus_test.ai", "bus_test.score");
+        subscribe("bus_test.gameplay", "bus_test.aggro");
+
+        publish("bus_test.gameplay", "bus_test.score", 0, b"1");
+        publish("bus_test.ai", "bus_test.aggro", 7, b"enemy");
+        publish("bus_test.ai", "bus_test.nobody", 0, b"");
+        assert_eq!(receive("bus_test.gameplay"), None);
+
+        advance();
+        let aggro = receive("bus_test.gameplay").unwrap();
+        assert_eq!((aggro.schema, aggro.payload.as_slice(), aggro.sender.as_str()), (7, &b"enemy"[..], "bus_test.ai"));
+        assert_eq!(receive("bus_test.gameplay"), None);
+
+        // A reload re-subscribes; queued messages are still there for the new code.
+        publish("bus_test.gameplay", "bus_test.score", 0, b"2");
+        advance();
+        subscribe("bus_test.ai", "bus_test.score");
+        let scores: Vec<_> = take_inbox("bus_test.ai").into_iter().map(|m| m.payload).collect();
+        assert_eq!(scores, [b"1".to_vec(), b"2".to_vec()]);
+
+        // A rolled-back tick takes its messages with it.
+        publish("bus_test.gameplay", "bus_test.score", 0, b"3");
+        retract("bus_test.gameplay");
+        advance();
+        assert_eq!(receive("bus_test.ai"), None);
+
+        remove_subscriber("bus_test.ai");
+        remove_subscriber("bus_test.gameplay");
+    }
+}
@@ -2,25 +2,39 @@
 
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
 
 use glam::Vec2;
-use winit::event::{Event, WindowEvent};
+use winit::event::{ElementState, Event, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
-use winit::keyboard::KeyCode;
 use winit::window::WindowBuilder;
 
 use crate::app::App;
+use crate::bindings_ui::{self, BindingsRequest};
 use crate::engine_loop::EngineLoop;
 use crate::host;
-use crate::input::arbiter::MovementSignal;
+use crate::input;
+use crate::input::axes::{self, AxisInputs};
+use crate::input::contexts;
+use crate::input::ffi::PendingBinding;
+use crate::input::gamepad::GamepadPoller;
+use crate::input::Binding;
 use crate::input::poller::InputPoller;
+use crate::commands;
+use crate::console;
+use crate::cvars::{self, CVARS_FILE};
 use crate::inspector;
-use crate::plugin_manager::{PluginManager, PluginRuntimeState};
+use crate::logging;
+use crate::plugin_manager::PluginManager;
+use crate::plugin_status;
+use crate::renderer::camera::CameraView;
+use crate::replay::{self, Player, Recorder};
 use crate::renderer::Renderer;
 use crate::scene;
 
 use engine_ecs::World;
-use engine_shared::input_types::{InputState, PriorityLayer};
+use engine_shared::input_types::InputState;
 use engine_shared::plugin_api::HostInterface;
 
 /// Simple, best-effort file logger for fatal errors.
@@ -47,9 +61,10 @@ impl PlatformRunner {
 
     pub fn start(mut self) {
         let event_loop = EventLoop::new().unwrap();
+        let window_config = &self.app.project.window;
         let window = WindowBuilder::new()
-            .with_title(&self.app.window_title)
-            .with_inner_size(winit::dpi::LogicalSize::new(1280.0, 720.0))
+            .with_title(&window_config.title)
+            .with_inner_size(winit::dpi::LogicalSize::new(window_config.width, window_config.height))
             .build(&event_loop)
             .unwrap();
 
@@ -62,13 +77,14 @@ impl PlatformRunner {
         scene::setup_default_world(&mut world);
         let host_interface: HostInterface = host::create_interface();
 
-        let mut plugin_manager = PluginManager::new(&self.app.plugin_path);
+        let mut plugin_manager = PluginManager::new(&self.app.plugin_paths);
         plugin_manager.initial_load(&mut world, &host_interface);
 
-        // Engine loop + input poller
-        const SIM_DT: f32 = 1.0 / 60.0;
-        let mut engine_loop = EngineLoop::new(SIM_DT);
+        // Engine loop + input pollers
+        let mut engine_loop = EngineLoop::new(self.app.project.simulation.dt());
+        self.start_replay(&mut engine_loop, &mut world, &host_interface, &mut plugin_manager);
         let mut input_poller = InputPoller::new();
+        let mut gamepad_poller = GamepadPoller::new(&mut self.app.gamepads);
 
         event_loop
             .run(move |event, elwt| {
@@ -77,18 +93,38 @@ impl PlatformRunner {
                 // Give GUI first shot at all window events (for focus, etc.).
                 if let Event::WindowEvent { event: ref w_event, .. } = event {
                     self.app.gui.handle_event(&window, w_event);
+
+                    // Key Bindings window waiting for an input: it takes the next press.
+                    if self.app.bindings_ui.is_capturing() {
+                        if let Some(binding) = bindings_ui::binding_from_event(w_event) {
+                            self.app.bindings_ui.capture(binding, &mut self.app.input_map);
+                            return;
+                        }
+                    }
                 }
 
                 match event {
                     Event::WindowEvent { event: win_event, .. } => {
                         match win_event {
-                            WindowEvent::CloseRequested => elwt.exit(),
+                            WindowEvent::CloseRequested => {
+                                cvars::save_if_dirty(Path::new(CVARS_FILE));
+                                elwt.exit();
+                            }
 
-                            // Low-level input: delegate to InputPoller unless GUI owns keyboard.
-                            WindowEvent::KeyboardInput { .. } => {
-                                if !self.app.gui.wants_keyboard_input() {
-                                    input_poller.handle_event(&win_event);
-                                }
+                            // Clicks and scrolls over egui windows stay in the GUI
+                            // (releases always go through, so nothing sticks).
+                            WindowEvent::MouseInput { state: ElementState::Pressed, .. }
+                            | WindowEvent::MouseWheel { .. }
+                                if self.app.gui.wants_pointer_input() => {}
+
+                            // Keys always reach the poller; while the GUI wants the keyboard
+                            // the TextEntry input context keeps them from the bindings.
+                            WindowEvent::KeyboardInput { .. }
+                            | WindowEvent::MouseInput { .. }
+                            | WindowEvent::MouseWheel { .. }
+                            | WindowEvent::CursorMoved { .. }
+                            | WindowEvent::CursorLeft { .. } => {
+                                input_poller.handle_event(&win_event);
                             }
 
                             WindowEvent::Resized(size) => renderer.resize(size),
@@ -97,29 +133,62 @@ impl PlatformRunner {
                                 // --- RENDER PHASE ---
 
                                 let mut inspector_open = self.app.gui.show_inspector;
+                                let mut console_open = self.app.gui.show_console;
+                                let show_plugin_panels = self.app.gui.show_plugin_panels;
+                                let mut bindings_open = self.app.gui.show_bindings;
+                                let mut bindings_request = None;
                                 let (primitives, textures_delta) =
                                     self.app.gui.draw(&window, |ctx| {
                                         // Input inspector UI.
                                         inspector::show(
                                             ctx,
                                             &self.app.arbiter,
+                                            &mut self.app.gamepads,
+                                            &self.app.triggers,
+                                            &self.app.contexts,
                                             &mut inspector_open,
                                         );
 
+                                        // Rebinding UI.
+                                        if bindings_open {
+                                            bindings_request = bindings_ui::show(
+                                                ctx,
+                                                &mut self.app.bindings_ui,
+                                                &mut self.app.input_map,
+                                                &self.app.profile_file.conflicts,
+                                                &self.app.profile_file.path,
+                                                &mut bindings_open,
+                                            );
+                                        }
+
+                                        // Host + plugin log console.
+                                        console::show(
+                                            ctx,
+                                            &mut self.app.console,
+                                            &mut console_open,
+                                        );
+
+                                        // Plugin status / reload history + plugin-contributed debug panels.
+                                        if show_plugin_panels {
+                                            plugin_status::show(ctx, &plugin_manager);
+                                            plugin_manager.draw_debug_ui(ctx, &mut world);
+                                        }
+
                                         // Plugin runtime errors overlay.
-                                        if let PluginRuntimeState::PausedError(msg) =
-                                            &plugin_manager.runtime_state
-                                        {
+                                        let errors = plugin_manager.errors();
+                                        if !errors.is_empty() {
                                             egui::Window::new("CRITICAL ERROR")
                                                 .default_pos([400.0, 100.0])
                                                 .show(ctx, |ui| {
-                                                    ui.colored_label(
-                                                        egui::Color32::RED,
-                                                        format!(
-                                                            "Plugin Error: {}",
-                                                            msg
-                                                        ),
-                                                    );
+                                                    for (name, msg) in &errors {
+                                                        ui.colored_label(
+                                                            egui::Color32::RED,
+                                                            format!(
+                                                                "Plugin '{}' Error: {}",
+                                                                name, msg
+                                                            ),
+                                                        );
+                                                    }
                                                     ui.label(
                                                         "Fix source code and press F5 to reload.",
                                                     );
@@ -127,6 +196,15 @@ impl PlatformRunner {
                                         }
                                     });
                                 self.app.gui.show_inspector = inspector_open;
+                                self.app.gui.show_console = console_open;
+                                self.app.gui.show_bindings = bindings_open;
+                                if let Some(request) = bindings_request {
+                                    let result = match request {
+                                        BindingsRequest::Save => self.app.save_input_profile().map(|()| "Saved."),
+                                        BindingsRequest::Reload => self.app.reload_input_profile().map(|()| "Reloaded."),
+                                    };
+                                    self.app.bindings_ui.status = Some(result.map(str::to_string));
+                                }
 
                                 // Robust surface error handling (parity with original App::run).
                                 match renderer.render(
@@ -142,19 +220,19 @@ impl PlatformRunner {
                                     }
                                     Err(wgpu::SurfaceError::Lost)
                                     | Err(wgpu::SurfaceError::Outdated) => {
-                                        eprintln!(
+                                        tracing::warn!(
                                             "[Renderer] Surface lost/outdated. Reconfiguring swapchain."
                                         );
                                         renderer.resize(window.inner_size());
                                     }
                                     Err(wgpu::SurfaceError::OutOfMemory) => {
                                         let msg = "[Renderer] FATAL: Out of GPU memory. Exiting.";
-                                        eprintln!("{msg}");
+                                        tracing::error!("{msg}");
                                         log_fatal_error_to_file(msg);
                                         elwt.exit();
                                     }
                                     Err(wgpu::SurfaceError::Timeout) => {
-                                        eprintln!(
+                                        tracing::warn!(
                                             "[Renderer] Surface timeout. Skipping this frame."
                                         );
                                     }
@@ -168,28 +246,82 @@ impl PlatformRunner {
                     Event::AboutToWait => {
                         // --- UPDATE PHASE ---
 
-                        // 1) Time step
+                        // 1) Time step (also advances the console's frame grouping)
+                        logging::begin_frame();
                         let frame_dt = engine_loop.tick_timer();
 
-                        // 2) Input resolution: raw → Arbiter → final InputState
-                        input_poller.synchronize_with_arbiter(
-                            &mut self.app.arbiter,
-                            &self.app.input_map,
-                        );
-
-                        // Optional Reflex test: P key triggers a Reflex-layer movement override.
-                        // This preserves the original behavior from the monolithic App::run.
-                        if input_poller.is_key_active(KeyCode::KeyP) {
-                            self.app.arbiter.add_movement(MovementSignal {
-                                layer: PriorityLayer::Reflex,
-                                vector: Vec2::ZERO,
-                                weight: 1.0,
-                            });
+                        // 2) Console commands submitted last frame (may edit cvars / World)
+                        for line in self.app.console.take_submitted() {
+                            commands::execute(&line, &mut world);
                         }
 
-                        let final_input_state = self.app.arbiter.resolve();
-
-                        // 3) Engine internal actions (Inspector / Hot reload), edge-triggered.
+                        // 3) Input resolution: raw → Arbiter → final InputState
+                        if self.app.profile_file.changed_on_disk() {
+                            let result = self.app.reload_input_profile();
+                            if let Err(e) = &result {
+                                tracing::error!("❌ {e}");
+                            }
+                            self.app.bindings_ui.status = Some(result.map(|()| "Reloaded after an edit.".to_string()));
+                        }
+                        self.apply_plugin_bindings();
+                        if let Some(deadzone) = cvars::get_f32("input.deadzone") {
+                            self.app.arbiter.deadzone = deadzone;
+                        }
+                        gamepad_poller.poll(&mut self.app.gamepads);
+                        if self.app.bindings_ui.is_capturing() {
+                            let held = self.app.gamepads.connected().find_map(|d| d.buttons.first().copied());
+                            if let Some(button) = held {
+                                self.app.bindings_ui.capture(Binding::Pad(button), &mut self.app.input_map);
+                            }
+                        }
+                        // Input contexts (menus, text entry) first: they take the
+                        // inputs they bind from gameplay.
+                        self.app.contexts.apply_requests();
+                        self.app
+                            .contexts
+                            .set_active(contexts::TEXT_ENTRY, self.app.gui.wants_keyboard_input());
+                        let now = Instant::now();
+                        let all_held = input_poller.held_inputs().chain(self.app.gamepads.held_inputs()).collect();
+                        self.app.arbiter.clear();
+                        let claimed = self.app.contexts.feed_arbiter(
+                            &mut self.app.arbiter,
+                            &input_poller,
+                            &self.app.gamepads,
+                            &all_held,
+                            now,
+                        );
+                        let held = all_held.difference(&claimed).copied().collect();
+
+                        // Chords, holds, taps and sequences, before the plain bindings.
+                        self.app.triggers.update(&self.app.input_map, &held, now);
+                        let skip = claimed.union(self.app.triggers.consumed()).copied().collect();
+                        input_poller.feed_arbiter(&mut self.app.arbiter, &self.app.input_map, &skip);
+                        self.app
+                            .gamepads
+                            .feed_arbiter(&mut self.app.arbiter, &self.app.input_map, &skip);
+                        self.app.triggers.feed_arbiter(&mut self.app.arbiter);
+                        // Plugin-submitted signals (knockback, cutscene walks) on any layer.
+                        self.app.injected.feed_arbiter(&mut self.app.arbiter);
+
+                        let mut final_input_state = self.app.arbiter.resolve();
+                        let size = window.inner_size();
+                        input_poller.write_pointer_axes(
+                            &mut final_input_state,
+                            &self.app.last_input_state,
+                            &CameraView::from_world(&world),
+                            Vec2::new(size.width as f32, size.height as f32),
+                        );
+                        let (scroll, motion) = input_poller.pointer_motion();
+                        let axis_inputs = AxisInputs {
+                            held: &held,
+                            gamepads: &self.app.gamepads,
+                            scroll,
+                            motion,
+                        };
+                        axes::write_axes(&mut final_input_state, &self.app.input_map, &axis_inputs);
+                        final_input_state.set_edges_from(&self.app.last_input_state);
+
+                        // 4) Engine internal actions (Inspector / Hot reload), edge-triggered.
                         self.handle_engine_actions(
                             &final_input_state,
                             &mut plugin_manager,
@@ -197,25 +329,90 @@ impl PlatformRunner {
                             &host_interface,
                         );
 
-                        // 4) Fixed-step simulation.
-                        engine_loop.update_simulation(
+                        // Scripts reload as soon as they're saved.
+                        plugin_manager.reload_saved_scripts(&mut world, &host_interface);
+
+                        // 5) Fixed-step simulation.
+                        let ticks = engine_loop.update_simulation(
                             frame_dt,
                             &mut world,
                             &mut plugin_manager,
                             &final_input_state,
                         );
+                        input_poller.end_frame(ticks);
+                        self.app.triggers.end_frame(ticks);
+                        self.app.contexts.end_frame(ticks);
+                        self.app.injected.end_frame(ticks, frame_dt);
 
-                        // 5) Store for next-frame edge detection and request redraw.
+                        // 6) Store for next-frame edge detection and request redraw.
                         self.app.last_input_state = final_input_state;
                         window.request_redraw();
                     }
 
+                    // Plugins get `on_unload` while the World is still alive.
+                    Event::LoopExiting => {
+                        if let Err(e) = engine_loop.finish_recording() {
+                            tracing::error!("❌ {e}");
+                        }
+                        plugin_manager.shutdown(&mut world);
+                    }
+
                     _ => {}
                 }
             })
             .unwrap();
     }
 
+    /// `--record` / `--replay`: starts recording from the World as loaded, or
+    /// rewinds it to the recording's starting point. Either way the plugins
+    /// are handed the recording's seed before the first tick.
+    fn start_replay(
+        &self,
+        engine_loop: &mut EngineLoop,
+        world: &mut World,
+        host_interface: &HostInterface,
+        plugin_manager: &mut PluginManager,
+    ) {
+        let config = &self.app.project.replay;
+        if let Some(path) = &config.play {
+            match Player::load(path) {
+                Ok(player) => {
+                    player.restore(world, host_interface, plugin_manager);
+                    replay::publish_seed(player.seed());
+                    engine_loop.replay(player);
+                }
+                Err(e) => tracing::error!("❌ {e}"),
+            }
+        } else if let Some(path) = &config.record {
+            let dt = self.app.project.simulation.dt();
+            let recorder = Recorder::start(path.clone(), dt, world, plugin_manager);
+            replay::publish_seed(recorder.seed());
+            engine_loop.record(recorder);
+        }
+    }
+
+    /// Default keys, buttons and triggers suggested by plugins via
+    /// `HostInterface::bind_default_key` and `bind_context_key`.
+    fn apply_plugin_bindings(&mut self) {
+        for PendingBinding { context, action, binding } in input::ffi::take_pending_bindings() {
+            if let Some(context) = context {
+                self.app.apply_context_binding(&context, action, binding);
+                continue;
+            }
+            if self.app.profile_actions.contains(&action) {
+                tracing::debug!("Default binding {binding:?} for action {action} skipped: set by the input profile");
+                continue;
+            }
+            // Kept in the base map too, so a profile reload doesn't lose it.
+            let _ = self.app.base_map.bind_spec_default(binding.clone(), action);
+            if let Err(existing) = self.app.input_map.bind_spec_default(binding.clone(), action) {
+                tracing::warn!(
+                    "⚠️ Default binding {binding:?} for action {action} ignored: already bound to action {existing}"
+                );
+            }
+        }
+    }
+
     /// Edge-triggered engine actions (Inspector toggle, Hot reload),
     /// split out to keep the main loop readable.
     fn handle_engine_actions(
@@ -225,22 +422,28 @@ impl PlatformRunner {
         world: &mut World,
         host_interface: &HostInterface,
     ) {
-        let toggle_now = current_state.is_active(self.app.engine_toggle_inspector)
-            && !self
-                .app
-                .last_input_state
-                .is_active(self.app.engine_toggle_inspector);
-
-        let reload_now = current_state.is_active(self.app.engine_request_hot_reload)
-            && !self
-                .app
-                .last_input_state
-                .is_active(self.app.engine_request_hot_reload);
+        let toggle_now = current_state.just_pressed(self.app.engine_toggle_inspector);
+        let console_now = current_state.just_pressed(self.app.engine_toggle_console);
+        let panels_now = current_state.just_pressed(self.app.engine_toggle_plugin_panels);
+        let bindings_now = current_state.just_pressed(self.app.engine_toggle_bindings);
+        let reload_now = current_state.just_pressed(self.app.engine_request_hot_reload);
 
         if toggle_now {
             self.app.gui.toggle_inspector();
         }
 
+        if console_now {
+            self.app.gui.toggle_console();
+        }
+
+        if panels_now {
+            self.app.gui.toggle_plugin_panels();
+        }
+
+        if bindings_now {
+            self.app.gui.toggle_bindings();
+        }
+
         if reload_now {
             plugin_manager.try_hot_reload(world, host_interface);
         }
@@ -3,19 +3,35 @@
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 
 use libloading::{Library, Symbol};
 
+use crate::commands;
+use crate::message_bus;
+use crate::plugin_manifest::{self, PluginManifest};
+use crate::plugin_sandbox::SandboxedPlugin;
+use crate::plugin_ui;
+use crate::plugin_script::{self, ScriptPlugin};
+use crate::plugin_wasm::WasmPlugin;
+use crate::plugin_watchdog::{GuardedWorker, UpdateBudget};
+
 use engine_ecs::World;
 use engine_shared::input_types::InputState;
 use engine_shared::plugin_api::{
+    AbiInfo,
+    DebugUi,
     FFIResult,
     FFIBuffer,
     HostContext,
     HostInterface,
     PluginApi,
     StateEnvelope,
+    API_VERSION_SYMBOL,
+    CREATE_GAME_SYMBOL,
     SNAPSHOT_MAGIC_HEADER,
 };
 
@@ -30,38 +46,381 @@ pub enum PluginRuntimeState {
     PausedError(String),
 }
 
+/// How a plugin is executed (in-process dylib, sandboxed child process, ...).
+/// Mirrors the `PluginApi` vtable so `PluginInstance` drives every backend the same way.
+pub trait PluginBackend {
+    fn on_load(&mut self, world: &mut World, host_interface: &HostInterface) -> FFIResult;
+    fn on_update(&mut self, world: &mut World, input: &InputState, dt: f32) -> FFIResult;
+
+    fn get_state_len(&mut self) -> usize;
+    fn save_state(&mut self, buffer: &mut [u8]) -> FFIResult;
+    fn load_state(&mut self, buffer: &mut [u8]) -> FFIResult;
+
+    fn schema_hash(&mut self) -> u64;
+    fn state_version(&mut self) -> u32;
+
+    // Lifecycle hooks (see `PluginApi` for the reload order).
+    fn on_before_reload(&mut self, _world: &mut World) -> FFIResult {
+        FFIResult::Success
+    }
+    fn on_after_reload(&mut self, _world: &mut World) -> FFIResult {
+        FFIResult::Success
+    }
+    fn on_unload(&mut self, _world: &mut World) -> FFIResult {
+        FFIResult::Success
+    }
+
+    /// Releases plugin state and whatever hosts the code (library, process).
+    /// Always preceded by `on_unload`.
+    fn unload(self: Box<Self>);
+
+    /// More detail on the last non-Success result, if the backend has any.
+    fn fault(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Last known-good state kept by the backend itself, for restarting after
+    /// it died mid-tick (a sandboxed process's checkpoint). Matches the World
+    /// the host kept for that tick. In-process backends are checkpointed by
+    /// `PluginInstance` instead.
+    fn recovery_state(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// True if a failed `on_update` never writes to the host World,
+    /// so `PluginInstance` can skip its pre-tick World and state checkpoints.
+    fn isolates_world(&self) -> bool {
+        false
+    }
+
+    /// Whether `on_debug_ui` draws anything (the host skips the window otherwise).
+    fn has_debug_ui(&self) -> bool {
+        false
+    }
+
+    fn on_debug_ui(&mut self, _world: &mut World, _ui: &DebugUi) -> FFIResult {
+        FFIResult::Success
+    }
+}
+
+impl PluginBackend for PluginHandle {
+    fn on_load(&mut self, world: &mut World, host_interface: &HostInterface) -> FFIResult {
+        (self.api.on_load)(
+            self.api.state,
+            world as *mut _ as *mut HostContext,
+            host_interface as *const HostInterface,
+        )
+    }
+
+    fn on_update(&mut self, world: &mut World, input: &InputState, dt: f32) -> FFIResult {
+        (self.api.on_update)(
+            self.api.state,
+            world as *mut _ as *mut HostContext,
+            input as *const InputState,
+            dt,
+        )
+    }
+
+    fn get_state_len(&mut self) -> usize {
+        (self.api.get_state_len)(self.api.state)
+    }
+
+    fn save_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+        let ffi_buffer = FFIBuffer {
+            ptr: buffer.as_mut_ptr(),
+            len: buffer.len(),
+        };
+        (self.api.save_state)(self.api.state, ffi_buffer)
+    }
+
+    fn load_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+        let ffi_buffer = FFIBuffer {
+            ptr: buffer.as_mut_ptr(),
+            len: buffer.len(),
+        };
+        (self.api.load_state)(self.api.state, ffi_buffer)
+    }
+
+    fn schema_hash(&mut self) -> u64 {
+        (self.api.get_schema_hash)()
+    }
+
+    fn state_version(&mut self) -> u32 {
+        (self.api.get_state_version)()
+    }
+
+    fn on_before_reload(&mut self, world: &mut World) -> FFIResult {
+        match self.api.on_before_reload {
+            Some(hook) => hook(self.api.state, world as *mut _ as *mut HostContext),
+            None => FFIResult::Success,
+        }
+    }
+
+    fn on_after_reload(&mut self, world: &mut World) -> FFIResult {
+        match self.api.on_after_reload {
+            Some(hook) => hook(self.api.state, world as *mut _ as *mut HostContext),
+            None => FFIResult::Success,
+        }
+    }
+
+    fn on_unload(&mut self, world: &mut World) -> FFIResult {
+        (self.api.on_unload)(self.api.state, world as *mut _ as *mut HostContext)
+    }
+
+    fn has_debug_ui(&self) -> bool {
+        self.api.on_debug_ui.is_some()
+    }
+
+    fn on_debug_ui(&mut self, world: &mut World, ui: &DebugUi) -> FFIResult {
+        match self.api.on_debug_ui {
+            Some(draw) => draw(self.api.state, world as *mut _ as *mut HostContext, ui),
+            None => FFIResult::Success,
+        }
+    }
+
+    fn unload(self: Box<Self>) {
+        (self.api.drop_state)(self.api.state);
+        // Close the library before deleting its copy (Windows refuses otherwise).
+        drop(self.lib);
+        remove_copy(&self.path);
+    }
+}
+
+/// Owns every loaded plugin, kept in dependency/update order.
 pub struct PluginManager {
-    pub plugin: PluginHandle,
+    pub plugins: Vec<PluginInstance>,
+    /// Manifests refused before loading (duplicate name, missing dependency, cycle).
+    pub rejected: Vec<(PluginManifest, String)>,
+}
+
+impl PluginManager {
+    /// `sources` may mix library paths, `.toml` manifests and manifest directories.
+    pub fn new<S: AsRef<str>>(sources: &[S]) -> Self {
+        // Copies left behind by an earlier process with our PID that didn't shut down cleanly.
+        clean_copies_dir();
+
+        let mut manifests = Vec::new();
+        let mut rejected = Vec::new();
+
+        for source in sources {
+            let source = Path::new(source.as_ref());
+            for found in plugin_manifest::discover(source) {
+                match found {
+                    Ok(m) => manifests.push(m),
+                    Err(e) => {
+                        tracing::error!("❌ {e}");
+                        rejected.push((PluginManifest::for_library(source), e));
+                    }
+                }
+            }
+        }
+
+        let (ordered, refused) = plugin_manifest::resolve_order(manifests);
+        for (m, reason) in &refused {
+            tracing::error!("❌ Plugin '{}' not loaded: {reason}", m.name);
+        }
+        rejected.extend(refused);
+
+        Self {
+            plugins: ordered.into_iter().map(PluginInstance::new).collect(),
+            rejected,
+        }
+    }
+
+    pub fn initial_load(&mut self, world: &mut World, host_interface: &HostInterface) {
+        for plugin in &mut self.plugins {
+            plugin.scoped(|p| p.initial_load(world, host_interface));
+        }
+    }
+
+    /// Ticks every running plugin in order; a paused plugin is simply skipped.
+    /// Bus messages published during the previous tick are delivered first.
+    pub fn update(&mut self, world: &mut World, input: &InputState, dt: f32) {
+        message_bus::advance();
+        for plugin in &mut self.plugins {
+            plugin.scoped(|p| p.update(world, input, dt));
+        }
+    }
+
+    /// Unloads every plugin (dependents first) and removes the loaded library copies.
+    pub fn shutdown(&mut self, world: &mut World) {
+        for plugin in self.plugins.iter_mut().rev() {
+            plugin.scoped(|p| p.unload(world));
+            message_bus::remove_subscriber(&plugin.manifest.name);
+        }
+        clean_copies_dir();
+    }
+
+    /// Draws the debug panel of every running plugin that has one.
+    pub fn draw_debug_ui(&mut self, ctx: &egui::Context, world: &mut World) {
+        for plugin in &mut self.plugins {
+            plugin.scoped(|p| p.draw_debug_ui(ctx, world));
+        }
+    }
+
+    /// Reloads each plugin whose library changed on disk or that is paused.
+    /// Returns true if at least one plugin was reloaded.
+    pub fn try_hot_reload(&mut self, world: &mut World, host_interface: &HostInterface) -> bool {
+        let mut any = false;
+        for plugin in &mut self.plugins {
+            if plugin.needs_reload() {
+                any |= plugin.scoped(|p| p.try_hot_reload(world, host_interface));
+            }
+        }
+        any
+    }
+
+    /// Reloads scripts whose file changed since they were loaded. Polled every
+    /// frame; compiled plugins wait for an explicit reload (F5).
+    pub fn reload_saved_scripts(&mut self, world: &mut World, host_interface: &HostInterface) -> bool {
+        let mut any = false;
+        for plugin in &mut self.plugins {
+            if plugin_script::is_script(&plugin.manifest.library) && plugin.source_changed() {
+                any |= plugin.scoped(|p| p.try_hot_reload(world, host_interface));
+            }
+        }
+        any
+    }
+
+    /// `(plugin name, saved state)` of every running plugin (replay recordings).
+    pub fn save_states(&mut self) -> Vec<(String, Vec<u8>)> {
+        self.plugins
+            .iter_mut()
+            .filter(|p| matches!(p.runtime_state, PluginRuntimeState::Running))
+            .filter_map(|p| Some((p.manifest.name.clone(), p.save_plugin_state()?)))
+            .collect()
+    }
+
+    /// Loads states from `save_states` back into the plugins of the same name.
+    pub fn restore_states(
+        &mut self,
+        world: &mut World,
+        host_interface: &HostInterface,
+        states: &[(String, Vec<u8>)],
+    ) {
+        for (name, bytes) in states {
+            let Some(instance) = self.plugins.iter_mut().find(|p| &p.manifest.name == name) else {
+                tracing::warn!("⚠️ Saved state for plugin '{name}' skipped: not loaded");
+                continue;
+            };
+            if !bytes.is_empty() {
+                instance.scoped(|p| p.restore_saved_state(world, host_interface, bytes));
+            }
+        }
+    }
+
+    /// `(plugin name, message)` for every plugin currently in PausedError or rejected.
+    pub fn errors(&self) -> Vec<(&str, &str)> {
+        let paused = self.plugins.iter().filter_map(|p| match &p.runtime_state {
+            PluginRuntimeState::PausedError(msg) => Some((p.manifest.name.as_str(), msg.as_str())),
+            PluginRuntimeState::Running => None,
+        });
+        let rejected = self
+            .rejected
+            .iter()
+            .map(|(m, reason)| (m.name.as_str(), reason.as_str()));
+        paused.chain(rejected).collect()
+    }
+}
+
+/// A single plugin with its own library, lifecycle and error state.
+pub struct PluginInstance {
+    pub manifest: PluginManifest,
+    /// `None` when the load was refused (missing file, ABI mismatch).
+    pub plugin: Option<Box<dyn PluginBackend>>,
     pub runtime_state: PluginRuntimeState,
-    plugin_source_path: PathBuf,
+    /// Modification time of the source library when it was last loaded.
+    loaded_modified: Option<SystemTime>,
     last_reload: Option<Instant>,
     reload_debounce: Duration,
+    /// Plugin state saved right before the current tick, matching the World
+    /// checkpoint; F5 restarts from it after a panic.
+    pre_tick_state: Option<Vec<u8>>,
+    /// Buffer the next pre-tick save is written into; it replaces
+    /// `pre_tick_state` only if the save succeeds.
+    spare_state: Vec<u8>,
+    /// Most recent reloads, oldest first (at most `RELOAD_HISTORY_LEN`).
+    pub reload_history: VecDeque<ReloadEvent>,
+    /// `on_update` timings against `manifest.update_budget_ms`.
+    pub budget: UpdateBudget,
+    /// Thread running `on_update` when `manifest.guarded` (started on first use).
+    worker: Option<GuardedWorker>,
 }
 
-impl PluginManager {
-    pub fn new(path: &str) -> Self {
-        let source_path = Path::new(path).to_path_buf();
-        let plugin = unsafe { load_plugin(&source_path).expect("Failed to load initial plugin") };
+const RELOAD_HISTORY_LEN: usize = 32;
+
+/// One hot-reload attempt, kept for the editor's plugin panel.
+#[derive(Debug, Clone)]
+pub struct ReloadEvent {
+    pub at: SystemTime,
+    pub duration: Duration,
+    /// What happened to the saved state, or why the reload failed.
+    pub outcome: Result<StateRestore, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateRestore {
+    Restored,
+    /// Nothing to restore (stateless plugin, or no checkpoint after a crash).
+    NoState,
+    /// State was saved but not applied (version / schema mismatch, load error).
+    Discarded(String),
+}
+
+impl PluginInstance {
+    pub fn new(manifest: PluginManifest) -> Self {
+        let loaded_modified = modified_time(&manifest.library);
+        let (plugin, runtime_state) = match load_backend(&manifest) {
+            Ok(p) => (Some(p), PluginRuntimeState::Running),
+            Err(e) => {
+                tracing::error!("❌ Failed to load plugin '{}': {e}", manifest.name);
+                (
+                    None,
+                    PluginRuntimeState::PausedError(format!("Failed to load plugin: {e}")),
+                )
+            }
+        };
+
+        let budget = UpdateBudget::new(Duration::from_secs_f32(manifest.update_budget_ms.max(0.0) / 1000.0));
 
         Self {
+            manifest,
             plugin,
-            runtime_state: PluginRuntimeState::Running,
-            plugin_source_path: source_path,
+            runtime_state,
+            loaded_modified,
             last_reload: None,
             reload_debounce: Duration::from_millis(500),
+            pre_tick_state: None,
+            spare_state: Vec::new(),
+            reload_history: VecDeque::new(),
+            budget,
+            worker: None,
         }
     }
 
-    pub fn initial_load(&self, world: &mut World, host_interface: &HostInterface) {
-        unsafe {
-            let res = (self.plugin.api.on_load)(
-                self.plugin.api.state,
-                world as *mut _ as *mut HostContext,
-                host_interface as *const HostInterface,
-            );
-            if res != FFIResult::Success {
-                eprintln!("⚠️ Warning: Plugin initial load returned {:?}", res);
-            }
+    /// Runs `f` on behalf of this plugin, for host services that need to know
+    /// their caller (the message bus).
+    pub fn scoped<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let name = self.manifest.name.clone();
+        message_bus::as_plugin(&name, || f(self))
+    }
+
+    fn needs_reload(&self) -> bool {
+        matches!(self.runtime_state, PluginRuntimeState::PausedError(_)) || self.source_changed()
+    }
+
+    fn source_changed(&self) -> bool {
+        modified_time(&self.manifest.library) != self.loaded_modified
+    }
+
+    pub fn initial_load(&mut self, world: &mut World, host_interface: &HostInterface) {
+        let Some(plugin) = &mut self.plugin else { return };
+
+        let res = commands::with_owner(&self.manifest.name, || {
+            plugin.on_load(world, host_interface)
+        });
+        if res != FFIResult::Success {
+            tracing::warn!("⚠️ Warning: Plugin initial load returned {:?}", res);
         }
     }
 
@@ -69,54 +428,193 @@ impl PluginManager {
         if matches!(self.runtime_state, PluginRuntimeState::PausedError(_)) {
             return;
         }
+        let Some(plugin) = &self.plugin else { return };
+
+        // A guarded plugin may be abandoned mid-tick, so it always needs a rollback point.
+        if self.manifest.guarded || !plugin.isolates_world() {
+            if !self.checkpoint_state() {
+                return;
+            }
+            // Copy-on-write: only the storages this tick touches are copied.
+            world.begin_checkpoint();
+        }
 
-        let res = unsafe {
-            (self.plugin.api.on_update)(
-                self.plugin.api.state,
-                world as *mut _ as *mut HostContext,
-                input as *const InputState,
-                dt,
-            )
+        let started = Instant::now();
+        let res = if self.manifest.guarded {
+            match self.update_guarded(world, input, dt) {
+                Some(res) => res,
+                None => {
+                    // Nothing ran on this World (a timed-out tick's World was
+                    // replaced), so there is nothing to undo.
+                    world.commit_checkpoint();
+                    return;
+                }
+            }
+        } else {
+            let Some(plugin) = &mut self.plugin else { return };
+            plugin.on_update(world, input, dt)
         };
 
+        if let Some(streak) = self.budget.record(started.elapsed()) {
+            tracing::warn!(
+                "⏱️ Plugin '{}' over its {:.1} ms update budget for {streak} ticks in a row (last {:.2} ms, worst {:.2} ms)",
+                self.manifest.name,
+                self.manifest.update_budget_ms,
+                self.budget.last.as_secs_f64() * 1000.0,
+                self.budget.worst.as_secs_f64() * 1000.0,
+            );
+        }
+
+        if res != FFIResult::PanicDetected {
+            world.commit_checkpoint();
+        }
+
+        let Some(plugin) = &mut self.plugin else { return };
         match res {
             FFIResult::Success => {}
             FFIResult::PanicDetected => {
-                eprintln!("❌ Plugin PANIC during update. Entering PausedError.");
-                self.runtime_state =
-                    PluginRuntimeState::PausedError("Panic during update".into());
+                let reason = plugin
+                    .fault()
+                    .unwrap_or_else(|| "Panic during update".into());
+                tracing::error!(
+                    "❌ Plugin '{}' PANIC during update ({reason}). Entering PausedError.",
+                    self.manifest.name
+                );
+                // Undo whatever the panicking systems half-wrote; F5 resumes from
+                // this World plus the matching pre-tick (or backend recovery) state.
+                message_bus::retract(&self.manifest.name);
+                if world.rollback_checkpoint() {
+                    tracing::warn!("⚠️ World rolled back to the start of the failed tick.");
+                }
+                self.runtime_state = PluginRuntimeState::PausedError(reason);
             }
             other => {
-                eprintln!("⚠️ Plugin on_update returned {:?}", other);
+                tracing::warn!("⚠️ Plugin on_update returned {:?}", other);
             }
         }
     }
 
+    /// `on_update` on the worker thread, waiting at most `update_timeout_ms`.
+    /// `None` if the plugin didn't run (no worker thread) or was abandoned; the
+    /// World is then at its pre-tick state.
+    fn update_guarded(&mut self, world: &mut World, input: &InputState, dt: f32) -> Option<FFIResult> {
+        if self.worker.is_none() {
+            match GuardedWorker::spawn(&self.manifest.name) {
+                Ok(worker) => self.worker = Some(worker),
+                Err(e) => {
+                    tracing::error!("❌ Cannot start worker for plugin '{}': {e}", self.manifest.name);
+                    self.runtime_state = PluginRuntimeState::PausedError(format!("No worker thread: {e}"));
+                    return None;
+                }
+            }
+        }
+        let worker = self.worker.as_ref()?;
+        let plugin = self.plugin.take()?;
+        let timeout = Duration::from_millis(self.manifest.update_timeout_ms);
+        // The World's own checkpoint leaves with it, and never comes back on a timeout.
+        let before = world.clone();
+
+        match worker.run(plugin, std::mem::take(world), input, dt, timeout) {
+            Some((plugin, ticked, res)) => {
+                self.plugin = Some(plugin);
+                *world = ticked;
+                Some(res)
+            }
+            None => {
+                // The worker keeps the plugin and the World it was given. Its
+                // commands point into that plugin, so they go too.
+                self.worker = None;
+                commands::remove_owned_by(&self.manifest.name);
+                message_bus::retract(&self.manifest.name);
+                *world = before;
+
+                let reason = format!("on_update timed out after {} ms", self.manifest.update_timeout_ms);
+                tracing::error!(
+                    "❌ Plugin '{}' {reason}. Entering PausedError; its thread is abandoned.",
+                    self.manifest.name
+                );
+                self.runtime_state = PluginRuntimeState::PausedError(reason);
+                None
+            }
+        }
+    }
+
+    pub fn draw_debug_ui(&mut self, ctx: &egui::Context, world: &mut World) {
+        if matches!(self.runtime_state, PluginRuntimeState::PausedError(_)) {
+            return;
+        }
+        let Some(plugin) = self.plugin.as_mut().filter(|p| p.has_debug_ui()) else { return };
+
+        let mut result = FFIResult::Success;
+        egui::Window::new(format!("🔌 {}", self.manifest.name))
+            .id(egui::Id::new(("plugin_debug_ui", &self.manifest.name)))
+            .default_width(260.0)
+            .show(ctx, |ui| {
+                result = plugin_ui::with_debug_ui(ui, |debug_ui| plugin.on_debug_ui(world, debug_ui));
+            });
+
+        if result == FFIResult::PanicDetected {
+            tracing::error!(
+                "❌ Plugin '{}' PANIC in on_debug_ui. Entering PausedError.",
+                self.manifest.name
+            );
+            self.runtime_state = PluginRuntimeState::PausedError("Panic in on_debug_ui".into());
+        }
+    }
+
+    /// Saves the plugin's state into `pre_tick_state`, reusing buffers. If there
+    /// is nothing to save or the save fails, the last good state is kept.
+    /// False if the plugin panicked (it is then paused).
+    fn checkpoint_state(&mut self) -> bool {
+        let Some(plugin) = &mut self.plugin else { return false };
+        let len = plugin.get_state_len();
+        if len == 0 {
+            return true;
+        }
+        let mut buffer = std::mem::take(&mut self.spare_state);
+        buffer.resize(len, 0);
+        match plugin.save_state(&mut buffer) {
+            FFIResult::Success => {
+                self.spare_state = self.pre_tick_state.replace(buffer).unwrap_or_default();
+            }
+            FFIResult::PanicDetected => {
+                tracing::error!(
+                    "❌ Plugin '{}' PANIC during its pre-tick save. Entering PausedError.",
+                    self.manifest.name
+                );
+                self.spare_state = buffer;
+                self.runtime_state = PluginRuntimeState::PausedError("Panic during save".into());
+                return false;
+            }
+            other => {
+                tracing::warn!("⚠️ Pre-tick save_state failed: {:?}", other);
+                self.spare_state = buffer;
+            }
+        }
+        true
+    }
+
     fn save_plugin_state(&mut self) -> Option<Vec<u8>> {
+        let plugin = self.plugin.as_mut()?;
         let mut retry_count = 0;
         let max_retries = 3;
 
         loop {
             if retry_count >= max_retries {
-                eprintln!(
+                tracing::warn!(
                     "⚠️ Aborting save after {} retries (state growing too fast).",
                     retry_count
                 );
                 return None;
             }
 
-            let required_len = (self.plugin.api.get_state_len)(self.plugin.api.state);
+            let required_len = plugin.get_state_len();
             if required_len == 0 {
                 return Some(Vec::new());
             }
 
             let mut buffer = vec![0u8; required_len];
-            let ffi_buffer = FFIBuffer {
-                ptr: buffer.as_mut_ptr(),
-                len: buffer.len(),
-            };
-
-            let result = (self.plugin.api.save_state)(self.plugin.api.state, ffi_buffer);
+            let result = plugin.save_state(&mut buffer);
 
             match result {
                 FFIResult::Success => return Some(buffer),
@@ -125,13 +623,13 @@ impl PluginManager {
                     continue;
                 }
                 FFIResult::PanicDetected => {
-                    eprintln!("❌ Plugin PANIC during save! State may be corrupt.");
+                    tracing::error!("❌ Plugin PANIC during save! State may be corrupt.");
                     self.runtime_state =
                         PluginRuntimeState::PausedError("Panic during save".into());
                     return None;
                 }
                 other => {
-                    eprintln!("⚠️ save_state failed: {:?}", other);
+                    tracing::warn!("⚠️ save_state failed: {:?}", other);
                     return None;
                 }
             }
@@ -151,135 +649,233 @@ impl PluginManager {
         }
         self.last_reload = Some(now);
 
-        println!("🔄 Hot Reload requested...");
+        tracing::info!("🔄 Hot Reload requested for '{}'...", self.manifest.name);
+
+        let outcome = self.reload(world, host_interface);
+        let reloaded = outcome.is_ok();
+
+        if self.reload_history.len() >= RELOAD_HISTORY_LEN {
+            self.reload_history.pop_front();
+        }
+        self.reload_history.push_back(ReloadEvent {
+            at: SystemTime::now(),
+            duration: now.elapsed(),
+            outcome,
+        });
+        reloaded
+    }
+
+    fn reload(
+        &mut self,
+        world: &mut World,
+        host_interface: &HostInterface,
+    ) -> Result<StateRestore, String> {
+        let was_running = matches!(self.runtime_state, PluginRuntimeState::Running);
+
+        // 1. BEFORE RELOAD (only a healthy plugin is asked to prepare)
+        if was_running {
+            if let Some(old) = &mut self.plugin {
+                let res = old.on_before_reload(world);
+                if res != FFIResult::Success {
+                    tracing::warn!("⚠️ on_before_reload returned {:?}", res);
+                }
+            }
+        }
 
-        // 1. SAVE STATE (if currently running)
-        let snapshot = if matches!(self.runtime_state, PluginRuntimeState::Running) {
+        // 2. SAVE STATE (if currently running; otherwise the last pre-tick checkpoint)
+        let snapshot = if was_running {
             self.save_plugin_state()
         } else {
-            None
+            let pre_tick_state = self.pre_tick_state.clone();
+            pre_tick_state.or_else(|| self.plugin.as_mut().and_then(|p| p.recovery_state()))
         };
 
-        // 2. UNLOAD OLD
-        unsafe {
-            (self.plugin.api.drop_state)(self.plugin.api.state);
-        }
-        let old_path = self.plugin.path.clone();
-        let _ = fs::remove_file(&old_path);
-
-        // 3. LOAD NEW
-        let new_plugin = match unsafe { load_plugin(&self.plugin_source_path) } {
+        // 3. LOAD + VALIDATE NEW (the old plugin stays intact if this is refused)
+        let source_modified = modified_time(&self.manifest.library);
+        let new_plugin = match load_backend(&self.manifest) {
             Ok(p) => p,
             Err(e) => {
-                eprintln!("❌ Failed to load new plugin: {e}");
-                self.runtime_state =
-                    PluginRuntimeState::PausedError(format!("Failed to load new plugin: {e}"));
-                return false;
+                // Don't retry this version of the file until it changes again (or F5).
+                self.loaded_modified = source_modified;
+                let reason = format!("Failed to load new plugin: {e}");
+                tracing::error!("❌ {reason}");
+                self.runtime_state = PluginRuntimeState::PausedError(reason.clone());
+                return Err(reason);
             }
         };
-        self.plugin = new_plugin;
-
-        // 4. RESTORE STATE (Version + Hash guarded)
-        if let Some(mut bytes) = snapshot {
-            if !bytes.is_empty() && bytes.len() >= std::mem::size_of::<StateEnvelope>() {
-                let header_size = std::mem::size_of::<StateEnvelope>();
-                let mut envelope = StateEnvelope {
-                    magic_header: 0,
-                    state_version: 0,
-                    schema_hash: 0,
-                    payload_len: 0,
-                };
-
-                unsafe {
-                    std::ptr::copy_nonoverlapping(
-                        bytes.as_ptr(),
-                        &mut envelope as *mut StateEnvelope as *mut u8,
-                        header_size,
-                    );
-                }
 
-                if envelope.magic_header == SNAPSHOT_MAGIC_HEADER {
-                    let expected_version = (self.plugin.api.get_state_version)();
-                    let expected_hash = (self.plugin.api.get_schema_hash)();
-
-                    if envelope.state_version != expected_version {
-                        // Case A: explicit version mismatch → discard
-                        eprintln!(
-                            "⚠️ Version mismatch (Saved: {}, New: {}). Discarding state.",
-                            envelope.state_version, expected_version
-                        );
-                    } else if envelope.schema_hash != expected_hash {
-                        // Case B: hash mismatch but version equal → likely forgot to bump version
-                        eprintln!(
-                            "🛑 CRITICAL SAFETY: Schema hash mismatch! \
-                             Versions match ({}), but layout differs. Using default state.",
-                            envelope.state_version
-                        );
-                    } else {
-                        // Case C: version + hash agree → attempt restore
-                        let ffi_buffer = FFIBuffer {
-                            ptr: bytes.as_mut_ptr(),
-                            len: bytes.len(),
-                        };
-
-                        let res = (self.plugin.api.load_state)(
-                            self.plugin.api.state,
-                            ffi_buffer,
-                        );
-
-                        match res {
-                            FFIResult::Success => {
-                                println!("✅ State restored successfully.");
-                            }
-                            FFIResult::SchemaMismatch => {
-                                eprintln!(
-                                    "⚠️ Schema mismatch reported by plugin. Using default state."
-                                );
-                            }
-                            FFIResult::PanicDetected => {
-                                eprintln!(
-                                    "❌ Plugin PANIC during load_state. Entering PausedError."
-                                );
-                                self.runtime_state = PluginRuntimeState::PausedError(
-                                    "Panic during load_state".into(),
-                                );
-                                return false;
-                            }
-                            other => {
-                                eprintln!(
-                                    "⚠️ load_state failed ({:?}). Using default state.",
-                                    other
-                                );
-                            }
-                        }
-                    }
-                }
+        // 4. UNLOAD OLD (on_unload, drop state, close library, delete its copy)
+        self.unload(world);
+        self.pre_tick_state = None;
+        let plugin = self.plugin.insert(new_plugin);
+        self.loaded_modified = source_modified;
+
+        // 5. RESTORE STATE (Version + Hash guarded)
+        let restore = match snapshot {
+            Some(mut bytes) if !bytes.is_empty() => restore_state(plugin.as_mut(), &mut bytes),
+            _ => Ok(StateRestore::NoState),
+        };
+        let restore = match restore {
+            Ok(restore) => restore,
+            Err(reason) => {
+                self.runtime_state = PluginRuntimeState::PausedError(reason.clone());
+                return Err(reason);
             }
+        };
+
+        // 6. REBIND HOST RESOURCES
+        let res = commands::with_owner(&self.manifest.name, || {
+            plugin.on_load(world, host_interface)
+        });
+        if res != FFIResult::Success {
+            tracing::warn!("⚠️ on_load failed after reload ({:?})", res);
         }
 
-        // 5. REBIND HOST RESOURCES
-        unsafe {
-            let res = (self.plugin.api.on_load)(
-                self.plugin.api.state,
-                world as *mut _ as *mut HostContext,
-                host_interface as *const HostInterface,
-            );
+        // 7. AFTER RELOAD
+        let res = plugin.on_after_reload(world);
+        if res != FFIResult::Success {
+            tracing::warn!("⚠️ on_after_reload returned {:?}", res);
+        }
+
+        self.runtime_state = PluginRuntimeState::Running;
+        Ok(restore)
+    }
+
+    /// Applies a `save_states` blob. Loading a state drops what the plugin bound
+    /// in `on_load` (action IDs, host callbacks), so it runs again, as after a reload.
+    fn restore_saved_state(&mut self, world: &mut World, host_interface: &HostInterface, bytes: &[u8]) {
+        let Some(plugin) = &mut self.plugin else { return };
+        if let Err(reason) = restore_state(plugin.as_mut(), &mut bytes.to_vec()) {
+            self.runtime_state = PluginRuntimeState::PausedError(reason);
+            return;
+        }
+
+        let res = commands::with_owner(&self.manifest.name, || {
+            plugin.on_load(world, host_interface)
+        });
+        if res != FFIResult::Success {
+            tracing::warn!("⚠️ on_load failed after restoring state ({:?})", res);
+        }
+    }
+
+    /// Runs `on_unload` and releases the current backend. Its console commands
+    /// go first, since they point into the library being closed.
+    pub fn unload(&mut self, world: &mut World) {
+        commands::remove_owned_by(&self.manifest.name);
+        if let Some(mut old) = self.plugin.take() {
+            let res = old.on_unload(world);
             if res != FFIResult::Success {
-                eprintln!("⚠️ on_load failed after reload ({:?})", res);
+                tracing::warn!("⚠️ Plugin '{}' on_unload returned {:?}", self.manifest.name, res);
             }
+            old.unload();
         }
+    }
+}
 
-        self.runtime_state = PluginRuntimeState::Running;
-        true
+/// Applies a saved state blob to a freshly loaded plugin, guarded by the
+/// envelope's version and schema hash. `Err` only if the plugin panicked.
+fn restore_state(plugin: &mut dyn PluginBackend, bytes: &mut [u8]) -> Result<StateRestore, String> {
+    let header_size = std::mem::size_of::<StateEnvelope>();
+    if bytes.len() < header_size {
+        return Ok(StateRestore::Discarded("Truncated state envelope".into()));
+    }
+    let mut envelope = StateEnvelope {
+        magic_header: 0,
+        state_version: 0,
+        schema_hash: 0,
+        payload_len: 0,
+    };
+
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            bytes.as_ptr(),
+            &mut envelope as *mut StateEnvelope as *mut u8,
+            header_size,
+        );
+    }
+
+    if envelope.magic_header != SNAPSHOT_MAGIC_HEADER {
+        return Ok(StateRestore::Discarded("Bad magic header".into()));
+    }
+
+    let expected_version = plugin.state_version();
+    let expected_hash = plugin.schema_hash();
+
+    if envelope.state_version != expected_version {
+        // Case A: explicit version mismatch → discard
+        tracing::warn!(
+            "⚠️ Version mismatch (Saved: {}, New: {}). Discarding state.",
+            envelope.state_version, expected_version
+        );
+        return Ok(StateRestore::Discarded(format!(
+            "Version mismatch (saved {}, new {expected_version})",
+            envelope.state_version
+        )));
+    }
+    if envelope.schema_hash != expected_hash {
+        // Case B: hash mismatch but version equal → likely forgot to bump version
+        tracing::error!(
+            "🛑 CRITICAL SAFETY: Schema hash mismatch! \
+             Versions match ({}), but layout differs. Using default state.",
+            envelope.state_version
+        );
+        return Ok(StateRestore::Discarded("Schema hash mismatch".into()));
+    }
+
+    // Case C: version + hash agree → attempt restore
+    match plugin.load_state(bytes) {
+        FFIResult::Success => {
+            tracing::info!("✅ State restored successfully.");
+            Ok(StateRestore::Restored)
+        }
+        FFIResult::SchemaMismatch => {
+            tracing::warn!("⚠️ Schema mismatch reported by plugin. Using default state.");
+            Ok(StateRestore::Discarded("Schema mismatch reported by plugin".into()))
+        }
+        FFIResult::PanicDetected => {
+            tracing::error!("❌ Plugin PANIC during load_state. Entering PausedError.");
+            Err("Panic during load_state".into())
+        }
+        other => {
+            tracing::warn!("⚠️ load_state failed ({:?}). Using default state.", other);
+            Ok(StateRestore::Discarded(format!("load_state returned {other:?}")))
+        }
     }
 }
 
-unsafe fn load_plugin(path: &Path) -> Result<PluginHandle, Box<dyn std::error::Error>> {
+/// Instantiates the backend selected by the manifest (`.wasm` libraries are always sandboxed).
+fn load_backend(manifest: &PluginManifest) -> Result<Box<dyn PluginBackend>, Box<dyn std::error::Error>> {
+    if manifest.library.extension() == Some(OsStr::new("wasm")) {
+        Ok(Box::new(WasmPlugin::load(&manifest.library)?))
+    } else if plugin_script::is_script(&manifest.library) {
+        Ok(Box::new(ScriptPlugin::load(&manifest.library)?))
+    } else if manifest.sandbox {
+        Ok(Box::new(SandboxedPlugin::spawn(&manifest.library)?))
+    } else {
+        Ok(Box::new(unsafe { load_plugin(&manifest.library)? }))
+    }
+}
+
+pub(crate) unsafe fn load_plugin(path: &Path) -> Result<PluginHandle, Box<dyn std::error::Error>> {
     let copy_path = unique_copy_path(path)?;
     fs::copy(path, &copy_path)?;
 
-    let lib = Library::new(&copy_path)?;
-    let create_fn: Symbol<extern "C" fn() -> PluginApi> = lib.get(b"_create_game")?;
+    let lib = match Library::new(&copy_path) {
+        Ok(lib) => lib,
+        Err(e) => {
+            remove_copy(&copy_path);
+            return Err(e.into());
+        }
+    };
+
+    // Handshake BEFORE `_create_game`: a mismatched vtable would crash on first call.
+    if let Err(e) = check_abi(&lib) {
+        drop(lib);
+        remove_copy(&copy_path);
+        return Err(e.into());
+    }
+
+    let create_fn: Symbol<extern "C" fn() -> PluginApi> = lib.get(CREATE_GAME_SYMBOL)?;
     let api = create_fn();
 
     Ok(PluginHandle {
@@ -289,13 +885,516 @@ unsafe fn load_plugin(path: &Path) -> Result<PluginHandle, Box<dyn std::error::E
     })
 }
 
+/// Resolves `_engine_api_version` and compares it with the host's own `AbiInfo`.
+unsafe fn check_abi(lib: &Library) -> Result<(), String> {
+    let version_fn: Symbol<extern "C" fn() -> AbiInfo> =
+        lib.get(API_VERSION_SYMBOL).map_err(|_| {
+            "Incompatible plugin: missing `_engine_api_version` export \
+             (rebuild it against the current engine_shared)"
+                .to_string()
+        })?;
+
+    abi_compatible(&version_fn(), &AbiInfo::current())
+}
+
+fn abi_compatible(plugin: &AbiInfo, host: &AbiInfo) -> Result<(), String> {
+    if plugin.api_version != host.api_version {
+        return Err(format!(
+            "Incompatible plugin: API version {} (host expects {})",
+            plugin.api_version, host.api_version
+        ));
+    }
+    if plugin.plugin_api_size != host.plugin_api_size
+        || plugin.host_interface_size != host.host_interface_size
+    {
+        return Err(format!(
+            "Incompatible plugin: vtable layout differs \
+             (PluginApi {} vs {} bytes, HostInterface {} vs {} bytes)",
+            plugin.plugin_api_size,
+            host.plugin_api_size,
+            plugin.host_interface_size,
+            host.host_interface_size
+        ));
+    }
+    Ok(())
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Loaded libraries are copies, so the build can overwrite the original while
+/// it's in use. They live outside the build output to keep it clean, in one
+/// directory per process so no instance deletes another's copies.
+///
+/// Each process also holds `<pid>.lock` next to its directory for as long as
+/// it runs, so a later startup can tell what a crashed process left behind.
+fn copies_root() -> PathBuf {
+    std::env::temp_dir().join("rust_engine_plugins")
+}
+
+fn copies_dir() -> PathBuf {
+    copies_dir_of(std::process::id())
+}
+
+fn copies_dir_of(pid: u32) -> PathBuf {
+    copies_root().join(pid.to_string())
+}
+
+fn copies_lock_of(pid: u32) -> PathBuf {
+    copies_root().join(format!("{pid}.lock"))
+}
+
+/// Creates and locks `<pid>.lock`; the lock lasts as long as the returned file.
+fn lock_copies_of(pid: u32) -> std::io::Result<fs::File> {
+    fs::create_dir_all(copies_root())?;
+    let file = fs::File::create(copies_lock_of(pid))?;
+    file.lock()?;
+    Ok(file)
+}
+
+/// Takes this process's lock (once), before its copies directory is created.
+fn hold_copies_lock() {
+    static LOCK: OnceLock<Option<fs::File>> = OnceLock::new();
+    LOCK.get_or_init(|| {
+        lock_copies_of(std::process::id())
+            .map_err(|e| tracing::warn!("⚠️ Cannot lock the plugin copies directory: {e}"))
+            .ok()
+    });
+}
+
 fn unique_copy_path(original: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let ts = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    static NEXT_COPY: AtomicU32 = AtomicU32::new(0);
+
+    hold_copies_lock();
+    let dir = copies_dir();
+    fs::create_dir_all(&dir)?;
     let stem = original
         .file_stem()
         .and_then(OsStr::to_str)
         .unwrap_or("plugin");
     let ext = original.extension().and_then(OsStr::to_str).unwrap_or("dll");
+    let n = NEXT_COPY.fetch_add(1, Ordering::Relaxed);
+
+    Ok(dir.join(format!("{stem}_{n}.{ext}")))
+}
+
+/// Copies that couldn't be deleted yet (e.g. still mapped on Windows).
+static STALE_COPIES: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+fn remove_copy(path: &Path) {
+    if fs::remove_file(path).is_err() && path.exists() {
+        STALE_COPIES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(path.to_path_buf());
+    }
+}
+
+/// Best-effort removal of this process's copies directory, and of those left
+/// by processes that are gone.
+fn clean_copies_dir() {
+    STALE_COPIES.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    hold_copies_lock();
+    clean_copies_dir_of(std::process::id());
+
+    let Ok(entries) = fs::read_dir(copies_root()) else { return };
+    let pids: BTreeSet<u32> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            name.strip_suffix(".lock").unwrap_or(&name).parse().ok()
+        })
+        .collect();
+    for pid in pids.into_iter().filter(|&pid| pid != std::process::id()) {
+        clean_copies_of_dead(pid);
+    }
+}
+
+/// Removes the copies directory and lock of process `pid` if it no longer
+/// holds the lock: it exited, crashed or was killed (or is a sandbox child
+/// that did so without cleaning up).
+pub(crate) fn clean_copies_of_dead(pid: u32) {
+    let alive = fs::File::options()
+        .write(true)
+        .open(copies_lock_of(pid))
+        .is_ok_and(|lock| lock.try_lock().is_err());
+    if !alive {
+        clean_copies_dir_of(pid);
+        let _ = fs::remove_file(copies_lock_of(pid));
+    }
+}
+
+/// Removes the copies directory of process `pid`.
+fn clean_copies_dir_of(pid: u32) {
+    let dir = copies_dir_of(pid);
+    let Ok(entries) = fs::read_dir(&dir) else { return };
+    let removed = entries.flatten().filter(|e| fs::remove_file(e.path()).is_ok()).count();
+    let _ = fs::remove_dir(&dir);
+    if removed > 0 {
+        tracing::debug!("Removed {removed} stale plugin copies from {}", dir.display());
+    }
+}
+
+#[cfg(test)]
+mod abi_tests {
+    use super::*;
+
+    #[test]
+    fn abi_mismatch_is_refused() {
+        let host = AbiInfo::current();
+        assert!(abi_compatible(&host, &host).is_ok());
+
+        let old_version = AbiInfo { api_version: host.api_version + 1, ..host };
+        assert!(abi_compatible(&old_version, &host).is_err());
+
+        let grown_iface = AbiInfo {
+            host_interface_size: host.host_interface_size + 8,
+            ..host
+        };
+        assert!(abi_compatible(&grown_iface, &host).is_err());
+    }
+}
+
+#[cfg(test)]
+mod rollback_tests {
+    use super::*;
+    use crate::world_snapshot::WorldSnapshot;
+    use engine_shared::{CEnemy, CTransform};
+    use glam::Vec2;
+
+    /// Moves every transform and spawns an entity, then reports a panic.
+    struct HalfWritingPlugin;
+
+    /// A game-specific component, not one of `scene::setup_default_world`'s.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Inventory(Vec<&'static str>);
+
+    /// Keeps a tick counter as its state and adds to every inventory; on its
+    /// second tick it also registers a storage, then panics.
+    #[derive(Default)]
+    struct LootingPlugin {
+        ticks: u32,
+    }
+
+    impl PluginBackend for LootingPlugin {
+        fn on_load(&mut self, _: &mut World, _: &HostInterface) -> FFIResult {
+            FFIResult::Success
+        }
+        fn on_update(&mut self, world: &mut World, _: &InputState, _: f32) -> FFIResult {
+            self.ticks += 1;
+            for (_, inventory) in world.query_mut::<Inventory>().unwrap().iter_mut() {
+                inventory.0.push("gold");
+            }
+            if self.ticks < 2 {
+                return FFIResult::Success;
+            }
+            world.register_component::<u64>();
+            FFIResult::PanicDetected
+        }
+        fn get_state_len(&mut self) -> usize {
+            4
+        }
+        fn save_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+            buffer.copy_from_slice(&self.ticks.to_le_bytes());
+            FFIResult::Success
+        }
+        fn load_state(&mut self, _: &mut [u8]) -> FFIResult {
+            FFIResult::Success
+        }
+        fn schema_hash(&mut self) -> u64 {
+            0
+        }
+        fn state_version(&mut self) -> u32 {
+            0
+        }
+        fn unload(self: Box<Self>) {}
+    }
+
+    /// Saves its tick count once; then has nothing to save, then fails to save
+    /// (scribbling on the buffer) and panics in that tick.
+    #[derive(Default)]
+    struct FailingSaver {
+        ticks: u32,
+    }
+
+    impl PluginBackend for FailingSaver {
+        fn on_load(&mut self, _: &mut World, _: &HostInterface) -> FFIResult {
+            FFIResult::Success
+        }
+        fn on_update(&mut self, _: &mut World, _: &InputState, _: f32) -> FFIResult {
+            self.ticks += 1;
+            if self.ticks < 3 {
+                FFIResult::Success
+            } else {
+                FFIResult::PanicDetected
+            }
+        }
+        fn get_state_len(&mut self) -> usize {
+            if self.ticks == 1 {
+                0
+            } else {
+                4
+            }
+        }
+        fn save_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+            if self.ticks == 0 {
+                buffer.copy_from_slice(&self.ticks.to_le_bytes());
+                return FFIResult::Success;
+            }
+            buffer.fill(0xFF);
+            FFIResult::Error
+        }
+        fn load_state(&mut self, _: &mut [u8]) -> FFIResult {
+            FFIResult::Success
+        }
+        fn schema_hash(&mut self) -> u64 {
+            0
+        }
+        fn state_version(&mut self) -> u32 {
+            0
+        }
+        fn unload(self: Box<Self>) {}
+    }
 
-    Ok(original.with_file_name(format!("{stem}_loaded_{ts}.{ext}")))
+    impl PluginBackend for HalfWritingPlugin {
+        fn on_load(&mut self, _: &mut World, _: &HostInterface) -> FFIResult {
+            FFIResult::Success
+        }
+        fn on_update(&mut self, world: &mut World, _: &InputState, _: f32) -> FFIResult {
+            if let Some(transforms) = world.query_mut::<CTransform>() {
+                for (_, t) in transforms.iter_mut() {
+                    t.pos += Vec2::splat(1000.0);
+                }
+            }
+            let e = world.spawn();
+            world.add_component(e, CEnemy { speed: 1.0 });
+            FFIResult::PanicDetected
+        }
+        fn get_state_len(&mut self) -> usize {
+            0
+        }
+        fn save_state(&mut self, _: &mut [u8]) -> FFIResult {
+            FFIResult::Success
+        }
+        fn load_state(&mut self, _: &mut [u8]) -> FFIResult {
+            FFIResult::Success
+        }
+        fn schema_hash(&mut self) -> u64 {
+            0
+        }
+        fn state_version(&mut self) -> u32 {
+            0
+        }
+        fn unload(self: Box<Self>) {}
+    }
+
+    /// Writes to the World, then never returns.
+    struct RunawayPlugin;
+
+    impl PluginBackend for RunawayPlugin {
+        fn on_load(&mut self, _: &mut World, _: &HostInterface) -> FFIResult {
+            FFIResult::Success
+        }
+        fn on_update(&mut self, world: &mut World, _: &InputState, _: f32) -> FFIResult {
+            world.spawn();
+            loop {
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+        fn get_state_len(&mut self) -> usize {
+            0
+        }
+        fn save_state(&mut self, _: &mut [u8]) -> FFIResult {
+            FFIResult::Success
+        }
+        fn load_state(&mut self, _: &mut [u8]) -> FFIResult {
+            FFIResult::Success
+        }
+        fn schema_hash(&mut self) -> u64 {
+            0
+        }
+        fn state_version(&mut self) -> u32 {
+            0
+        }
+        fn unload(self: Box<Self>) {}
+    }
+
+    fn instance(manifest: PluginManifest, plugin: impl PluginBackend + 'static) -> PluginInstance {
+        let budget = UpdateBudget::new(Duration::from_secs_f32(manifest.update_budget_ms / 1000.0));
+        PluginInstance {
+            manifest,
+            plugin: Some(Box::new(plugin)),
+            runtime_state: PluginRuntimeState::Running,
+            loaded_modified: None,
+            last_reload: None,
+            reload_debounce: Duration::ZERO,
+            pre_tick_state: None,
+            spare_state: Vec::new(),
+            reload_history: VecDeque::new(),
+            budget,
+            worker: None,
+        }
+    }
+
+    #[test]
+    fn panicking_tick_is_rolled_back() {
+        let mut world = World::new();
+        crate::scene::setup_default_world(&mut world);
+        let player = world.spawn();
+        world.add_component(player, CTransform::default());
+        let before = WorldSnapshot::capture(&world);
+
+        let manifest = PluginManifest::for_library(Path::new("half_writer.so"));
+        let mut instance = instance(manifest, HalfWritingPlugin);
+        instance.update(&mut world, &InputState::default(), 1.0 / 60.0);
+
+        assert!(matches!(instance.runtime_state, PluginRuntimeState::PausedError(_)));
+        assert_eq!(world.get_component::<CTransform>(player).unwrap().pos, Vec2::ZERO);
+        assert_eq!(world.query::<CEnemy>().map_or(0, |q| q.iter().count()), 0);
+        assert_eq!(
+            bincode::serialize(&WorldSnapshot::capture(&world)).unwrap(),
+            bincode::serialize(&before).unwrap()
+        );
+    }
+
+    #[test]
+    fn every_component_storage_is_rolled_back() {
+        let mut world = World::new();
+        world.register_component::<Inventory>();
+        let hero = world.spawn();
+        world.add_component(hero, Inventory(vec!["sword"]));
+
+        let manifest = PluginManifest::for_library(Path::new("looter.so"));
+        let mut instance = instance(manifest, LootingPlugin::default());
+        instance.update(&mut world, &InputState::default(), 1.0 / 60.0);
+        assert_eq!(world.get_component::<Inventory>(hero).unwrap().0, ["sword", "gold"]);
+
+        // The second tick's write and its new storage are undone, and the
+        // state F5 restarts from is the one that goes with this World.
+        instance.update(&mut world, &InputState::default(), 1.0 / 60.0);
+        assert!(matches!(instance.runtime_state, PluginRuntimeState::PausedError(_)));
+        assert_eq!(world.get_component::<Inventory>(hero).unwrap().0, ["sword", "gold"]);
+        assert!(world.query::<u64>().is_none());
+        assert_eq!(instance.pre_tick_state.as_deref(), Some(&1u32.to_le_bytes()[..]));
+    }
+
+    #[test]
+    fn failed_pre_tick_saves_keep_the_last_good_state() {
+        let mut world = World::new();
+        let manifest = PluginManifest::for_library(Path::new("failing_saver.so"));
+        let mut instance = instance(manifest, FailingSaver::default());
+        for _ in 0..3 {
+            instance.update(&mut world, &InputState::default(), 1.0 / 60.0);
+        }
+
+        assert!(matches!(instance.runtime_state, PluginRuntimeState::PausedError(_)));
+        assert_eq!(instance.pre_tick_state.as_deref(), Some(&0u32.to_le_bytes()[..]));
+    }
+
+    #[test]
+    fn guarded_runaway_update_is_abandoned() {
+        let mut world = World::new();
+        crate::scene::setup_default_world(&mut world);
+        let player = world.spawn();
+        world.add_component(player, CTransform::default());
+        let before = bincode::serialize(&WorldSnapshot::capture(&world)).unwrap();
+
+        let mut manifest = PluginManifest::for_library(Path::new("runaway.so"));
+        manifest.guarded = true;
+        manifest.update_timeout_ms = 100;
+        let mut instance = instance(manifest, RunawayPlugin);
+
+        let started = Instant::now();
+        instance.update(&mut world, &InputState::default(), 1.0 / 60.0);
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(matches!(instance.runtime_state, PluginRuntimeState::PausedError(_)));
+        assert!(instance.plugin.is_none());
+        assert_eq!(bincode::serialize(&WorldSnapshot::capture(&world)).unwrap(), before);
+    }
+}
+
+#[cfg(test)]
+mod lifecycle_tests {
+    use super::*;
+    use crate::{host, scene};
+
+    /// Each hook publishes "<version>:<hook> <ticks>" on the `lifecycle` topic.
+    fn script(version: &str) -> String {
+        let hook = |name: &str| {
+            format!(r#"fn {name}() {{ publish("lifecycle", "{version}:{name} " + this.ticks); }}"#)
+        };
+        format!(
+            "fn init() {{ #{{ ticks: 0 }} }}\nfn on_update(dt) {{ this.ticks += 1; }}\n{}\n{}\n{}\n{}\n",
+            hook("on_load"),
+            hook("on_before_reload"),
+            hook("on_after_reload"),
+            hook("on_unload"),
+        )
+    }
+
+    fn lifecycle_events() -> Vec<String> {
+        message_bus::advance();
+        message_bus::take_inbox("lifecycle_test")
+            .into_iter()
+            .map(|m| String::from_utf8(m.payload).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn load_reload_unload_run_in_order() {
+        let dir = std::env::temp_dir().join(format!("plugin_lifecycle_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("lifecycle.rhai");
+        fs::write(&path, script("v1")).unwrap();
+        message_bus::subscribe("lifecycle_test", "lifecycle");
+
+        let mut world = World::new();
+        scene::setup_default_world(&mut world);
+        let host_interface = host::create_interface();
+        let mut manager = PluginManager::new(&[path.to_string_lossy()]);
+        manager.initial_load(&mut world, &host_interface);
+        manager.update(&mut world, &InputState::default(), 1.0 / 60.0);
+        manager.update(&mut world, &InputState::default(), 1.0 / 60.0);
+        assert_eq!(lifecycle_events(), ["v1:on_load 0"]);
+
+        // The new version gets the old one's state before its own on_load.
+        fs::write(&path, script("v2")).unwrap();
+        let touched = SystemTime::now() + Duration::from_secs(2);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(touched).unwrap();
+        assert!(manager.try_hot_reload(&mut world, &host_interface));
+        assert_eq!(
+            lifecycle_events(),
+            ["v1:on_before_reload 2", "v1:on_unload 2", "v2:on_load 2", "v2:on_after_reload 2"]
+        );
+
+        // `shutdown` also drops the plugin's undelivered messages, so unload it first.
+        manager.plugins[0].scoped(|p| p.unload(&mut world));
+        assert_eq!(lifecycle_events(), ["v2:on_unload 2"]);
+        manager.shutdown(&mut world);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn startup_removes_only_dead_instances_copies() {
+        // Made-up PIDs, each with a copy and a held lock while it is set up.
+        let (dead, live) = (u32::MAX - 1, u32::MAX);
+        let mut locks = Vec::new();
+        for pid in [dead, live] {
+            locks.push(lock_copies_of(pid).unwrap());
+            fs::create_dir_all(copies_dir_of(pid)).unwrap();
+            fs::write(copies_dir_of(pid).join("plugin_0.so"), b"").unwrap();
+        }
+        // `dead` crashes: its lock goes, its files stay until the next startup.
+        drop(locks.remove(0));
+        PluginManager::new::<&str>(&[]);
+        assert!(!copies_dir_of(dead).exists());
+        assert!(!copies_lock_of(dead).exists());
+        assert!(copies_dir_of(live).join("plugin_0.so").exists());
+
+        drop(locks);
+        clean_copies_of_dead(live);
+        assert!(!copies_dir_of(live).exists());
+    }
 }
@@ -0,0 +1,312 @@
+// crates/engine_core/src/plugin_manifest.rs
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::project;
+
+/// Per-plugin metadata, read from a `*.toml` manifest next to the library.
+///
+/// ```toml
+/// name = "gameplay"
+/// version = "0.1.0"
+/// library = "../target/debug/libgame_plugin.so"
+/// depends_on = ["core"]
+/// update_order = 10
+/// sandbox = false
+/// update_budget_ms = 4.0
+/// guarded = false
+/// update_timeout_ms = 500
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(default = "default_version")]
+    pub version: String,
+    /// Library path; relative paths are resolved against the manifest's directory.
+    /// A missing native library is retried under this platform's naming.
+    pub library: PathBuf,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Tie-breaker between plugins with no dependency relation (lower runs first).
+    #[serde(default)]
+    pub update_order: i32,
+    /// Run the plugin in a child process so crashes can't take the editor down.
+    #[serde(default)]
+    pub sandbox: bool,
+    /// `on_update` time above which the plugin counts as over budget (see `plugin_watchdog`).
+    #[serde(default = "default_update_budget_ms")]
+    pub update_budget_ms: f32,
+    /// Run `on_update` on a worker thread, abandoned if it overruns `update_timeout_ms`.
+    #[serde(default)]
+    pub guarded: bool,
+    #[serde(default = "default_update_timeout_ms")]
+    pub update_timeout_ms: u64,
+}
+
+fn default_version() -> String {
+    "0.0.0".to_string()
+}
+
+fn default_update_budget_ms() -> f32 {
+    4.0
+}
+
+fn default_update_timeout_ms() -> u64 {
+    500
+}
+
+impl PluginManifest {
+    /// Implicit manifest for a bare library path (no dependencies, order 0).
+    pub fn for_library(library: &Path) -> Self {
+        let name = library
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(|s| s.strip_prefix("lib").unwrap_or(s))
+            .unwrap_or("plugin")
+            .to_string();
+
+        Self {
+            name,
+            version: default_version(),
+            library: library.to_path_buf(),
+            depends_on: Vec::new(),
+            update_order: 0,
+            sandbox: false,
+            update_budget_ms: default_update_budget_ms(),
+            guarded: false,
+            update_timeout_ms: default_update_timeout_ms(),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read manifest {}: {e}", path.display()))?;
+        let mut manifest: PluginManifest = toml::from_str(&text)
+            .map_err(|e| format!("Invalid manifest {}: {e}", path.display()))?;
+
+        if manifest.library.is_relative() {
+            if let Some(dir) = path.parent() {
+                manifest.library = dir.join(&manifest.library);
+            }
+        }
+        // A manifest written on another OS still finds this platform's build.
+        if !manifest.library.exists() && project::is_library_name(&manifest.library) {
+            manifest.library = project::platform_library(&manifest.library);
+        }
+        Ok(manifest)
+    }
+}
+
+/// Expands a user-supplied plugin source into manifests.
+///
+/// - a directory → every `*.toml` manifest directly inside it
+/// - a `.toml` file → that manifest
+/// - anything else → treated as a library with an implicit manifest
+pub fn discover(source: &Path) -> Vec<Result<PluginManifest, String>> {
+    if source.is_dir() {
+        let entries = match fs::read_dir(source) {
+            Ok(entries) => entries,
+            Err(e) => return vec![Err(format!("Cannot read {}: {e}", source.display()))],
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        return paths.iter().map(|p| PluginManifest::from_file(p)).collect();
+    }
+
+    if source.extension().is_some_and(|ext| ext == "toml") {
+        return vec![PluginManifest::from_file(source)];
+    }
+
+    vec![Ok(PluginManifest::for_library(source))]
+}
+
+/// Orders manifests so every plugin updates after its dependencies.
+///
+/// Returns `(ordered, rejected)`; rejected entries carry the reason
+/// (duplicate name, missing dependency, dependency cycle, or a dependency
+/// that was itself rejected).
+pub fn resolve_order(
+    manifests: Vec<PluginManifest>,
+) -> (Vec<PluginManifest>, Vec<(PluginManifest, String)>) {
+    let mut rejected = Vec::new();
+    let mut by_name: HashMap<String, PluginManifest> = HashMap::new();
+
+    for m in manifests {
+        if by_name.contains_key(&m.name) {
+            let reason = format!("Duplicate plugin name '{}'", m.name);
+            rejected.push((m, reason));
+        } else {
+            by_name.insert(m.name.clone(), m);
+        }
+    }
+
+    // Drop plugins whose dependencies are missing, then those depending on them.
+    let mut rejected_names = HashSet::new();
+    reject_dependents(&mut by_name, &mut rejected, &mut rejected_names);
+
+    // Kahn's algorithm; among ready plugins pick lowest (update_order, name).
+    let mut ordered = Vec::with_capacity(by_name.len());
+    while !by_name.is_empty() {
+        let next = by_name
+            .values()
+            .filter(|m| m.depends_on.iter().all(|d| !by_name.contains_key(d)))
+            .min_by(|a, b| (a.update_order, &a.name).cmp(&(b.update_order, &b.name)))
+            .map(|m| m.name.clone());
+
+        match next {
+            Some(name) => ordered.push(by_name.remove(&name).unwrap()),
+            None => {
+                // Everything left is on a cycle or depends on one.
+                let mut cyclic: Vec<_> = by_name
+                    .keys()
+                    .filter(|name| on_cycle(name, &by_name))
+                    .cloned()
+                    .collect();
+                cyclic.sort();
+                for name in cyclic {
+                    let m = by_name.remove(&name).unwrap();
+                    rejected.push((m, "Dependency cycle".to_string()));
+                    rejected_names.insert(name);
+                }
+                reject_dependents(&mut by_name, &mut rejected, &mut rejected_names);
+            }
+        }
+    }
+
+    (ordered, rejected)
+}
+
+/// Rejects plugins with a missing or rejected dependency until none is left.
+fn reject_dependents(
+    by_name: &mut HashMap<String, PluginManifest>,
+    rejected: &mut Vec<(PluginManifest, String)>,
+    rejected_names: &mut HashSet<String>,
+) {
+    loop {
+        let mut broken: Vec<(String, String)> = by_name
+            .values()
+            .filter_map(|m| {
+                m.depends_on
+                    .iter()
+                    .find(|dep| !by_name.contains_key(*dep))
+                    .map(|dep| (m.name.clone(), dep.clone()))
+            })
+            .collect();
+        if broken.is_empty() {
+            break;
+        }
+        broken.sort();
+        for (name, dep) in broken {
+            let Some(m) = by_name.remove(&name) else { continue };
+            let reason = if rejected_names.contains(&dep) {
+                format!("Depends on rejected plugin '{dep}'")
+            } else {
+                format!("Missing dependency '{dep}'")
+            };
+            rejected.push((m, reason));
+            rejected_names.insert(name);
+        }
+    }
+}
+
+/// True when `name` reaches itself through the dependencies still in `by_name`.
+fn on_cycle(name: &str, by_name: &HashMap<String, PluginManifest>) -> bool {
+    let mut stack: Vec<&str> = by_name[name].depends_on.iter().map(String::as_str).collect();
+    let mut seen = HashSet::new();
+    while let Some(current) = stack.pop() {
+        if current == name {
+            return true;
+        }
+        if !seen.insert(current) {
+            continue;
+        }
+        if let Some(m) = by_name.get(current) {
+            stack.extend(m.depends_on.iter().map(String::as_str));
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(name: &str, deps: &[&str], order: i32) -> PluginManifest {
+        PluginManifest {
+            name: name.to_string(),
+            version: default_version(),
+            library: PathBuf::from(format!("{name}.so")),
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            update_order: order,
+            sandbox: false,
+            update_budget_ms: default_update_budget_ms(),
+            guarded: false,
+            update_timeout_ms: default_update_timeout_ms(),
+        }
+    }
+
+    #[test]
+    fn dependencies_run_first_then_update_order() {
+        let (ordered, rejected) = resolve_order(vec![
+            manifest("debug", &["gameplay"], -10),
+            manifest("ai", &[], 5),
+            manifest("gameplay", &["ai"], 0),
+            manifest("audio", &[], 1),
+        ]);
+
+        let names: Vec<_> = ordered.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["audio", "ai", "gameplay", "debug"]);
+        assert!(rejected.is_empty());
+    }
+
+    #[test]
+    fn missing_dependencies_and_cycles_are_rejected() {
+        let (ordered, rejected) = resolve_order(vec![
+            manifest("a", &["b"], 0),
+            manifest("b", &["a"], 0),
+            manifest("c", &["nope"], 0),
+            manifest("d", &["c"], 0),
+            manifest("e", &[], 0),
+        ]);
+
+        assert_eq!(ordered.len(), 1);
+        assert_eq!(ordered[0].name, "e");
+
+        let mut names: Vec<_> = rejected.iter().map(|(m, _)| m.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn plugins_depending_on_a_cycle_report_the_rejected_dependency() {
+        let (ordered, rejected) = resolve_order(vec![
+            manifest("a", &["b"], 0),
+            manifest("b", &["a"], 0),
+            manifest("ui", &["a"], 0),
+            manifest("hud", &["ui"], 0),
+            manifest("c", &["nope"], 0),
+            manifest("d", &["c"], 0),
+        ]);
+        assert!(ordered.is_empty());
+
+        let mut reasons: Vec<_> = rejected.iter().map(|(m, r)| (m.name.as_str(), r.as_str())).collect();
+        reasons.sort();
+        assert_eq!(reasons, [
+            ("a", "Dependency cycle"),
+            ("b", "Dependency cycle"),
+            ("c", "Missing dependency 'nope'"),
+            ("d", "Depends on rejected plugin 'c'"),
+            ("hud", "Depends on rejected plugin 'ui'"),
+            ("ui", "Depends on rejected plugin 'a'"),
+        ]);
+    }
+}
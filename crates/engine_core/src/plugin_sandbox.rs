@@ -0,0 +1,671 @@
+// crates/engine_core/src/plugin_sandbox.rs
+//! Out-of-process plugin backend.
+//!
+//! The plugin library is loaded by a child copy of the current executable
+//! (`<exe> --plugin-sandbox-child <port> <library>`). The host drives it over a
+//! localhost socket with the same load / update / save_state / load_state calls
+//! as the in-process backend, mirroring the World across on every call.
+//! A segfault, abort or stack overflow only kills the child: the host sees the
+//! connection drop, reports `PausedError`, and F5 restarts the child from the
+//! state of the last successful tick (which matches the host's World, since a
+//! failed tick's World is never applied).
+//!
+//! Host services used inside the child (logging, cvars, console commands) are the
+//! child's own, and plugin debug panels (`on_debug_ui`) are not shown: it reads
+//! persisted cvars at startup, but console edits made in the editor and commands
+//! registered by the plugin don't cross the process boundary. The message bus
+//! does: each reply carries the plugin's subscriptions and what it published, and
+//! each `Update` carries its inbox.
+
+use std::io::{BufReader, BufWriter, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use engine_ecs::World;
+use engine_shared::input_types::{ActionId, AxisId, InputState};
+use engine_shared::plugin_api::{FFIResult, HostInterface};
+
+use crate::commands;
+use crate::cvars::{self, CVARS_FILE};
+use crate::host;
+use crate::input::contexts::{self, ContextRequest};
+use crate::input::ffi::PendingBinding;
+use crate::input::injected::{self, InjectedSignal};
+use crate::input::{self, ActionRegistry, AxisKind, BindingSpec};
+use crate::logging;
+use crate::message_bus::{self, BusMessage};
+use crate::plugin_manager::{self, PluginBackend};
+use crate::scene;
+use crate::world_snapshot::WorldSnapshot;
+
+/// First CLI argument that turns the executable into a sandbox child.
+pub const SANDBOX_CHILD_ARG: &str = "--plugin-sandbox-child";
+
+/// Set by `set_child_executable`; `None` re-launches the running executable.
+static CHILD_EXECUTABLE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Launches sandbox children from `exe`, for hosts whose own `main` doesn't
+/// call `run_child_if_requested` (test binaries).
+pub fn set_child_executable(exe: impl Into<PathBuf>) {
+    *CHILD_EXECUTABLE.lock().unwrap_or_else(|e| e.into_inner()) = Some(exe.into());
+}
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Plugin name inside the child (command owner, bus subscriber).
+const CHILD_PLUGIN: &str = "sandbox";
+
+#[derive(Serialize, Deserialize)]
+enum Request {
+    Load {
+        actions: Vec<(String, ActionId)>,
+        axes: Vec<(String, AxisId, AxisKind)>,
+        world: WorldSnapshot,
+    },
+    Update {
+        world: WorldSnapshot,
+        input: InputState,
+        dt: f32,
+        /// Bus messages delivered to the plugin since its last tick.
+        inbox: Vec<BusMessage>,
+    },
+    SaveState,
+    LoadState(Vec<u8>),
+    /// Runs a lifecycle hook against the mirrored World.
+    Hook {
+        hook: Hook,
+        world: WorldSnapshot,
+    },
+    Shutdown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+enum Hook {
+    BeforeReload,
+    AfterReload,
+    Unload,
+}
+
+/// The child's side of the message bus after a call.
+#[derive(Serialize, Deserialize, Default)]
+struct BusSync {
+    subscriptions: Vec<String>,
+    published: Vec<BusMessage>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum Response {
+    Ready(Result<(u64, u32), String>),
+    /// Reply to `Update`; `state` is the plugin state after a successful tick.
+    Ticked {
+        result: FFIResult,
+        world: WorldSnapshot,
+        state: Option<Vec<u8>>,
+        bus: BusSync,
+        /// Arbiter signals the plugin submitted during the tick.
+        signals: Vec<InjectedSignal>,
+        /// Input context pushes and pops it requested.
+        contexts: Vec<ContextRequest>,
+    },
+    /// Reply to `Load`: also carries the child's action registry and the default
+    /// bindings the plugin suggested, so the host can adopt them.
+    Loaded {
+        result: FFIResult,
+        world: WorldSnapshot,
+        actions: Vec<(String, ActionId)>,
+        /// `(context, action, binding name)`; no context for gameplay bindings.
+        bindings: Vec<(Option<String>, ActionId, String)>,
+        bus: BusSync,
+    },
+    State(Result<Vec<u8>, FFIResult>),
+    Result(FFIResult),
+    Hooked {
+        result: FFIResult,
+        world: WorldSnapshot,
+        bus: BusSync,
+    },
+}
+
+/// Host-side handle to a plugin running in a child process.
+pub struct SandboxedPlugin {
+    child: Child,
+    reader: BufReader<TcpStream>,
+    writer: BufWriter<TcpStream>,
+    schema_hash: u64,
+    state_version: u32,
+    /// Bytes fetched by `get_state_len`, handed out by the next `save_state`.
+    pending_save: Option<Vec<u8>>,
+    last_checkpoint: Option<Vec<u8>>,
+    fault: Option<String>,
+}
+
+impl SandboxedPlugin {
+    pub fn spawn(library: &Path) -> Result<Self, String> {
+        let listener =
+            TcpListener::bind("127.0.0.1:0").map_err(|e| format!("Sandbox bind failed: {e}"))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Sandbox bind failed: {e}"))?
+            .port();
+
+        let exe = match CHILD_EXECUTABLE.lock().unwrap_or_else(|e| e.into_inner()).clone() {
+            Some(exe) => exe,
+            None => std::env::current_exe().map_err(|e| format!("Cannot locate executable: {e}"))?,
+        };
+        let mut child = Command::new(exe)
+            .arg(SANDBOX_CHILD_ARG)
+            .arg(port.to_string())
+            .arg(library)
+            .spawn()
+            .map_err(|e| format!("Cannot spawn sandbox process: {e}"))?;
+
+        let stream = match accept_child(&listener, &mut child) {
+            Ok(stream) => stream,
+            Err(e) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(e);
+            }
+        };
+        let _ = stream.set_nodelay(true);
+        let writer = stream
+            .try_clone()
+            .map_err(|e| format!("Sandbox socket error: {e}"))?;
+
+        let mut sandbox = Self {
+            child,
+            reader: BufReader::new(stream),
+            writer: BufWriter::new(writer),
+            schema_hash: 0,
+            state_version: 0,
+            pending_save: None,
+            last_checkpoint: None,
+            fault: None,
+        };
+
+        match sandbox.receive() {
+            Some(Response::Ready(Ok((hash, version)))) => {
+                sandbox.schema_hash = hash;
+                sandbox.state_version = version;
+                Ok(sandbox)
+            }
+            Some(Response::Ready(Err(e))) => {
+                let _ = sandbox.child.wait();
+                Err(e)
+            }
+            _ => Err(sandbox
+                .fault
+                .take()
+                .unwrap_or_else(|| "Sandbox handshake failed".into())),
+        }
+    }
+
+    fn send(&mut self, request: &Request) -> bool {
+        let ok = bincode::serialize_into(&mut self.writer, request).is_ok()
+            && self.writer.flush().is_ok();
+        if !ok {
+            self.mark_crashed();
+        }
+        ok
+    }
+
+    fn receive(&mut self) -> Option<Response> {
+        match bincode::deserialize_from(&mut self.reader) {
+            Ok(response) => Some(response),
+            Err(_) => {
+                self.mark_crashed();
+                None
+            }
+        }
+    }
+
+    fn call(&mut self, request: &Request) -> Option<Response> {
+        if self.fault.is_some() || !self.send(request) {
+            return None;
+        }
+        self.receive()
+    }
+
+    /// Records why the child went away (exit code / signal) for the error overlay.
+    fn mark_crashed(&mut self) {
+        if self.fault.is_some() {
+            return;
+        }
+
+        // The socket closes slightly before the OS reaps the process.
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let status = loop {
+            match self.child.try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(10)),
+                _ => break None,
+            }
+        };
+
+        let reason = match status {
+            Some(status) => format!("Sandboxed plugin process crashed ({status})"),
+            None => {
+                let _ = self.child.kill();
+                let _ = self.child.wait();
+                "Sandboxed plugin process stopped responding".to_string()
+            }
+        };
+        tracing::error!("❌ {reason}");
+        self.fault = Some(reason);
+    }
+
+    fn hook(&mut self, hook: Hook, world: &mut World) -> FFIResult {
+        if self.fault.is_some() {
+            return FFIResult::PanicDetected;
+        }
+        let request = Request::Hook {
+            hook,
+            world: WorldSnapshot::capture(world),
+        };
+        match self.call(&request) {
+            Some(Response::Hooked { result, world: snapshot, bus }) => {
+                if result == FFIResult::Success {
+                    snapshot.apply(world);
+                }
+                adopt_bus(bus, result == FFIResult::Success);
+                result
+            }
+            _ => FFIResult::PanicDetected,
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        if let Some(Response::State(Ok(bytes))) = self.call(&Request::SaveState) {
+            self.last_checkpoint = Some(bytes);
+        }
+    }
+}
+
+impl PluginBackend for SandboxedPlugin {
+    fn on_load(&mut self, world: &mut World, _host_interface: &HostInterface) -> FFIResult {
+        let request = Request::Load {
+            actions: registry_entries(),
+            axes: axis_entries(),
+            world: WorldSnapshot::capture(world),
+        };
+        match self.call(&request) {
+            Some(Response::Loaded { result, world: snapshot, actions, bindings, bus }) => {
+                snapshot.apply(world);
+                adopt_actions(&actions, &bindings);
+                adopt_bus(bus, true);
+                self.checkpoint();
+                result
+            }
+            _ => FFIResult::PanicDetected,
+        }
+    }
+
+    fn on_update(&mut self, world: &mut World, input: &InputState, dt: f32) -> FFIResult {
+        let request = Request::Update {
+            world: WorldSnapshot::capture(world),
+            input: *input,
+            dt,
+            inbox: message_bus::current_plugin()
+                .map(|name| message_bus::take_inbox(&name))
+                .unwrap_or_default(),
+        };
+        match self.call(&request) {
+            Some(Response::Ticked { result, world: snapshot, state, bus, signals, contexts: context_requests }) => {
+                // A panicking tick may have left the child's World half-mutated.
+                adopt_bus(bus, result == FFIResult::Success);
+                if result == FFIResult::Success {
+                    snapshot.apply(world);
+                    signals.into_iter().for_each(injected::submit);
+                    context_requests.into_iter().for_each(contexts::request);
+                    if state.is_some() {
+                        self.last_checkpoint = state;
+                    }
+                }
+                result
+            }
+            _ => FFIResult::PanicDetected,
+        }
+    }
+
+    fn get_state_len(&mut self) -> usize {
+        match self.call(&Request::SaveState) {
+            Some(Response::State(Ok(bytes))) => {
+                let len = bytes.len();
+                self.pending_save = Some(bytes);
+                len
+            }
+            _ => 0,
+        }
+    }
+
+    fn save_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+        match self.pending_save.take() {
+            Some(bytes) if bytes.len() <= buffer.len() => {
+                buffer[..bytes.len()].copy_from_slice(&bytes);
+                self.last_checkpoint = Some(bytes);
+                FFIResult::Success
+            }
+            Some(_) => FFIResult::BufferTooSmall,
+            None if self.fault.is_some() => FFIResult::PanicDetected,
+            None => FFIResult::Error,
+        }
+    }
+
+    fn load_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+        match self.call(&Request::LoadState(buffer.to_vec())) {
+            Some(Response::Result(result)) => {
+                if result == FFIResult::Success {
+                    self.last_checkpoint = Some(buffer.to_vec());
+                }
+                result
+            }
+            _ => FFIResult::PanicDetected,
+        }
+    }
+
+    fn schema_hash(&mut self) -> u64 {
+        self.schema_hash
+    }
+
+    fn state_version(&mut self) -> u32 {
+        self.state_version
+    }
+
+    fn on_before_reload(&mut self, world: &mut World) -> FFIResult {
+        self.hook(Hook::BeforeReload, world)
+    }
+
+    fn on_after_reload(&mut self, world: &mut World) -> FFIResult {
+        self.hook(Hook::AfterReload, world)
+    }
+
+    fn on_unload(&mut self, world: &mut World) -> FFIResult {
+        self.hook(Hook::Unload, world)
+    }
+
+    fn unload(mut self: Box<Self>) {
+        let mut exited = false;
+        if self.fault.is_none() && self.send(&Request::Shutdown) {
+            let deadline = Instant::now() + Duration::from_secs(2);
+            while !exited && Instant::now() < deadline {
+                exited = matches!(self.child.try_wait(), Ok(Some(_)));
+                if !exited {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            }
+        }
+        if !exited {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+        // A child that crashed or was killed leaves its library copy behind.
+        plugin_manager::clean_copies_of_dead(self.child.id());
+    }
+
+    fn fault(&mut self) -> Option<String> {
+        self.fault.clone()
+    }
+
+    fn recovery_state(&mut self) -> Option<Vec<u8>> {
+        self.last_checkpoint.clone()
+    }
+
+    fn isolates_world(&self) -> bool {
+        true
+    }
+}
+
+fn accept_child(listener: &TcpListener, child: &mut Child) -> Result<TcpStream, String> {
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Sandbox socket error: {e}"))?;
+
+    let deadline = Instant::now() + CONNECT_TIMEOUT;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream
+                    .set_nonblocking(false)
+                    .map_err(|e| format!("Sandbox socket error: {e}"))?;
+                return Ok(stream);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                if let Ok(Some(status)) = child.try_wait() {
+                    return Err(format!("Sandbox process exited during startup ({status})"));
+                }
+                if Instant::now() > deadline {
+                    return Err("Sandbox process did not connect in time".into());
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Err(e) => return Err(format!("Sandbox socket error: {e}")),
+        }
+    }
+}
+
+// ==================================================================================
+// CHILD PROCESS
+// ==================================================================================
+
+/// Call first thing in `main`. If this process was launched as a sandbox
+/// child, it serves the plugin until the host disconnects and then exits.
+pub fn run_child_if_requested() {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() != Some(SANDBOX_CHILD_ARG) {
+        return;
+    }
+
+    // Child logs go to the inherited stderr; the parent's console doesn't see them.
+    logging::init();
+    cvars::load(Path::new(CVARS_FILE));
+
+    let code = match (args.next(), args.next()) {
+        (Some(port), Some(library)) => match child_main(&port, Path::new(&library)) {
+            Ok(()) => 0,
+            Err(e) => {
+                tracing::error!("❌ [sandbox] {e}");
+                1
+            }
+        },
+        _ => {
+            tracing::error!("usage: {SANDBOX_CHILD_ARG} <port> <library>");
+            2
+        }
+    };
+    std::process::exit(code);
+}
+
+fn child_main(port: &str, library: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let stream = TcpStream::connect(format!("127.0.0.1:{port}"))?;
+    let _ = stream.set_nodelay(true);
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = BufWriter::new(stream);
+
+    let mut reply = |response: Response| -> Result<(), Box<dyn std::error::Error>> {
+        bincode::serialize_into(&mut writer, &response)?;
+        writer.flush()?;
+        Ok(())
+    };
+
+    let mut plugin = match unsafe { plugin_manager::load_plugin(library) } {
+        Ok(plugin) => plugin,
+        Err(e) => {
+            reply(Response::Ready(Err(format!("Failed to load plugin: {e}"))))?;
+            return Ok(());
+        }
+    };
+    reply(Response::Ready(Ok((plugin.schema_hash(), plugin.state_version()))))?;
+
+    let mut world = World::new();
+    scene::setup_default_world(&mut world);
+    let host_interface = host::create_interface();
+
+    // A read error means the host went away.
+    while let Ok(request) = bincode::deserialize_from::<_, Request>(&mut reader) {
+        match request {
+            Request::Load { actions, axes, world: snapshot } => {
+                publish_registry(&actions, &axes);
+                snapshot.apply(&mut world);
+                let result = message_bus::as_plugin(CHILD_PLUGIN, || {
+                    commands::with_owner(CHILD_PLUGIN, || plugin.on_load(&mut world, &host_interface))
+                });
+                let bindings = input::ffi::take_pending_bindings()
+                    .into_iter()
+                    .filter_map(|pending| Some((pending.context, pending.action, pending.binding.name()?)))
+                    .collect();
+                reply(Response::Loaded {
+                    result,
+                    world: WorldSnapshot::capture(&world),
+                    actions: registry_entries(),
+                    bindings,
+                    bus: child_bus(),
+                })?;
+            }
+            Request::Update { world: snapshot, input, dt, inbox } => {
+                snapshot.apply(&mut world);
+                message_bus::deliver(CHILD_PLUGIN, inbox);
+                let result =
+                    message_bus::as_plugin(CHILD_PLUGIN, || plugin.on_update(&mut world, &input, dt));
+                let state = (result == FFIResult::Success)
+                    .then(|| save_all(&mut plugin).ok())
+                    .flatten();
+                reply(Response::Ticked {
+                    result,
+                    world: WorldSnapshot::capture(&world),
+                    state,
+                    bus: child_bus(),
+                    signals: injected::take_pending(),
+                    contexts: contexts::take_requests(),
+                })?;
+            }
+            Request::SaveState => {
+                reply(Response::State(save_all(&mut plugin)))?;
+            }
+            Request::LoadState(mut bytes) => {
+                reply(Response::Result(plugin.load_state(&mut bytes)))?;
+            }
+            Request::Hook { hook, world: snapshot } => {
+                snapshot.apply(&mut world);
+                let result = message_bus::as_plugin(CHILD_PLUGIN, || match hook {
+                    Hook::BeforeReload => plugin.on_before_reload(&mut world),
+                    Hook::AfterReload => plugin.on_after_reload(&mut world),
+                    Hook::Unload => plugin.on_unload(&mut world),
+                });
+                reply(Response::Hooked {
+                    result,
+                    world: WorldSnapshot::capture(&world),
+                    bus: child_bus(),
+                })?;
+            }
+            Request::Shutdown => break,
+        }
+    }
+
+    // The host already ran `on_unload` through `Request::Hook` before `Shutdown`.
+    Box::new(plugin).unload();
+    Ok(())
+}
+
+/// Child side: the plugin's subscriptions, and what it published since the last reply.
+fn child_bus() -> BusSync {
+    BusSync {
+        subscriptions: message_bus::subscriptions(CHILD_PLUGIN),
+        published: message_bus::take_published(),
+    }
+}
+
+/// Host side: mirrors the child's subscriptions under the plugin's name and, unless
+/// the call failed (its World is discarded too), publishes its messages.
+fn adopt_bus(bus: BusSync, publish: bool) {
+    let Some(name) = message_bus::current_plugin() else { return };
+    message_bus::set_subscriptions(&name, bus.subscriptions);
+    if publish {
+        for message in bus.published {
+            message_bus::publish(&name, &message.topic, message.schema, &message.payload);
+        }
+    }
+}
+
+fn registry_entries() -> Vec<(String, ActionId)> {
+    input::GLOBAL_REGISTRY
+        .get()
+        .and_then(|m| m.lock().ok().map(|reg| reg.entries()))
+        .unwrap_or_default()
+}
+
+fn axis_entries() -> Vec<(String, AxisId, AxisKind)> {
+    input::GLOBAL_REGISTRY
+        .get()
+        .and_then(|m| m.lock().ok().map(|reg| reg.axis_entries()))
+        .unwrap_or_default()
+}
+
+/// Host side: registers actions the child added during `on_load` and queues its
+/// suggested bindings. The child started from a copy of our registry, so new
+/// names get the same IDs here as long as nothing registered in between.
+fn adopt_actions(actions: &[(String, ActionId)], bindings: &[(Option<String>, ActionId, String)]) {
+    if let Some(mutex) = input::GLOBAL_REGISTRY.get() {
+        let mut reg = mutex.lock().unwrap_or_else(|e| e.into_inner());
+        for (name, child_id) in actions {
+            let id = match reg.register(name) {
+                Ok(id) => id,
+                Err(e) => {
+                    tracing::error!("❌ [sandbox] {e}");
+                    continue;
+                }
+            };
+            if id != *child_id {
+                tracing::error!(
+                    "❌ [sandbox] Action '{name}' has ID {child_id} in the plugin but {id} in the host"
+                );
+            }
+        }
+    }
+    for (context, action, name) in bindings {
+        if let Ok(binding) = BindingSpec::from_name(name) {
+            input::ffi::queue_default_binding(PendingBinding {
+                context: context.clone(),
+                action: *action,
+                binding,
+            });
+        }
+    }
+}
+
+/// Mirrors the host's action and axis IDs so `get_action_id` and
+/// `get_axis_id` answer identically.
+fn publish_registry(actions: &[(String, ActionId)], axes: &[(String, AxisId, AxisKind)]) {
+    let mut registry = ActionRegistry::default();
+    for (name, _) in actions {
+        // The host registry fits in MAX_ACTIONS, so its copy does too.
+        let _ = registry.register(name);
+    }
+    for (name, _, kind) in axes {
+        let _ = registry.register_axis(name, *kind);
+    }
+
+    let mutex = input::GLOBAL_REGISTRY.get_or_init(|| Mutex::new(ActionRegistry::default()));
+    if let Ok(mut reg) = mutex.lock() {
+        *reg = registry;
+    }
+}
+
+fn save_all(plugin: &mut dyn PluginBackend) -> Result<Vec<u8>, FFIResult> {
+    for _ in 0..3 {
+        let mut buffer = vec![0u8; plugin.get_state_len()];
+        if buffer.is_empty() {
+            return Ok(buffer);
+        }
+        match plugin.save_state(&mut buffer) {
+            FFIResult::Success => return Ok(buffer),
+            FFIResult::BufferTooSmall => continue,
+            other => return Err(other),
+        }
+    }
+    Err(FFIResult::BufferTooSmall)
+}
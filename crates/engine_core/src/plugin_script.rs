@@ -0,0 +1,625 @@
+// crates/engine_core/src/plugin_script.rs
+//! Rhai script backend (`.rhai` files) for designer gameplay code.
+//!
+//! A script is a set of functions, all optional. `this` is the script's state:
+//!
+//! ```rhai
+//! fn init() { #{ cooldown: 0.0 } }     // initial state
+//! fn on_load() { register_action("Drop", "KeyM"); }
+//! fn on_update(dt) {
+//!     this.cooldown -= dt;
+//!     if pressed("Drop") && this.cooldown <= 0.0 {
+//!         let p = get(player(), "transform");
+//!         spawn_prefab("marker", p.x, p.y);
+//!         this.cooldown = 0.5;
+//!     }
+//! }
+//! fn on_unload() {}
+//! fn state_version() { 1 }             // bump when `this` changes shape
+//! ```
+//!
+//! `pressed(action)` is true while the action is held; `just_pressed(action)`
+//! and `just_released(action)` only on the tick it changed.
+//!
+//! `cursor()` is the mouse position in world units (`#{ x, y }`) and `scroll()`
+//! the wheel lines scrolled since the last tick. `axis(name)` reads a named
+//! axis from the input profile and `axis2(name)` a 2D one as `#{ x, y }` (0
+//! for unknown names).
+//!
+//! `submit_movement(layer, x, y, duration)` and `submit_action(layer, action,
+//! duration)` push a signal on an Arbiter layer ("Reflex", "Cutscene",
+//! "Control", "Ambient") for `duration` seconds, e.g. knockback on Reflex.
+//!
+//! `push_context(name)` and `pop_context(name)` push and pop an input context
+//! ("Menu"); `bind_context_key(context, action, key)` gives it a default binding
+//! (registering the action).
+//!
+//! `this` survives hot reloads (saved as JSON, so keep it to numbers, strings,
+//! bools, arrays and maps). The World is reachable only through the components
+//! in `COMPONENTS`. A script error, including running past `MAX_OPERATIONS` in
+//! one call, pauses the plugin like a native panic. Scripts reload when saved
+//! (see `PluginManager::reload_saved_scripts`); debug panels aren't available.
+//!
+//! Message bus: `subscribe(topic)`, `unsubscribe(topic)`, `publish(topic, payload)`
+//! and `publish(topic, schema, payload)` with a string or blob payload, and
+//! `messages()`, which drains the inbox as maps of `topic`, `schema`, `sender`,
+//! `payload` (blob) and `text` (payload as UTF-8).
+
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::ptr;
+use std::rc::Rc;
+
+use engine_ecs::{Entity, World};
+use engine_shared::input_types::{InputState, PriorityLayer, ACTION_NOT_FOUND, AXIS_NOT_FOUND};
+use engine_shared::plugin_api::{FFIResult, HostInterface, StateEnvelope, SNAPSHOT_MAGIC_HEADER};
+use engine_shared::{CCamera, CEnemy, CPlayer, CSprite, CTransform, CWorldBounds};
+use glam::Vec2;
+use rhai::{Array, Blob, CallFnOptions, Dynamic, Engine, EvalAltResult, FuncArgs, Map, Scope, AST};
+
+use crate::message_bus;
+use crate::plugin_manager::PluginBackend;
+use crate::prefabs;
+
+/// Components scripts may touch, and whether `set` may write them.
+pub const COMPONENTS: &[(&str, bool)] = &[
+    ("transform", true),
+    ("sprite", true),
+    ("enemy", true),
+    ("camera", true),
+    ("player", false),
+    ("bounds", false),
+];
+
+/// Per-call budget; a runaway loop fails the call instead of hanging the frame.
+const MAX_OPERATIONS: u64 = 5_000_000;
+
+/// Script state is a self-describing map, so the hash never changes;
+/// `state_version()` is the script's only guard.
+const SCRIPT_SCHEMA_HASH: u64 = 0x5C81_97A7_E000_0001;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+pub fn is_script(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "rhai")
+}
+
+/// What the registered functions can reach. `world` is only set during a call.
+struct ScriptContext {
+    world: Cell<*mut World>,
+    input: Cell<InputState>,
+    host: Cell<Option<HostInterface>>,
+}
+
+impl ScriptContext {
+    // The pointer is the `&mut World` of the call in progress, and registered
+    // functions never hold the borrow past their own return.
+    #[allow(clippy::mut_from_ref)]
+    fn world(&self) -> ScriptResult<&mut World> {
+        unsafe { self.world.get().as_mut() }.ok_or_else(|| "World is not available here".into())
+    }
+}
+
+pub struct ScriptPlugin {
+    engine: Engine,
+    ast: AST,
+    ctx: Rc<ScriptContext>,
+    state: Dynamic,
+    fault: Option<String>,
+}
+
+impl ScriptPlugin {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let source =
+            fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+        let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("script");
+        Self::from_source(name, &source)
+    }
+
+    pub fn from_source(name: &str, source: &str) -> Result<Self, String> {
+        let ctx = Rc::new(ScriptContext {
+            world: Cell::new(ptr::null_mut()),
+            input: Cell::new(InputState::default()),
+            host: Cell::new(None),
+        });
+        let engine = build_engine(name, &ctx);
+        let ast = engine.compile(source).map_err(|e| format!("{name}.rhai: {e}"))?;
+
+        let mut plugin = Self {
+            engine,
+            ast,
+            ctx,
+            state: Dynamic::from_map(Map::new()),
+            fault: None,
+        };
+        if plugin.has_fn("init") {
+            let state: Dynamic = plugin
+                .engine
+                .call_fn(&mut Scope::new(), &plugin.ast, "init", ())
+                .map_err(|e| format!("{name}.rhai: init: {e}"))?;
+            if !state.is_map() {
+                return Err(format!("{name}.rhai: init() must return an object map"));
+            }
+            plugin.state = state;
+        }
+        Ok(plugin)
+    }
+
+    fn has_fn(&self, name: &str) -> bool {
+        self.ast.iter_functions().any(|f| f.name == name)
+    }
+
+    /// Calls script function `name` (if defined) with `this` bound to the state.
+    fn call(&mut self, world: *mut World, name: &str, args: impl FuncArgs) -> FFIResult {
+        if !self.has_fn(name) {
+            return FFIResult::Success;
+        }
+        self.ctx.world.set(world);
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        let result =
+            self.engine
+                .call_fn_with_options::<Dynamic>(options, &mut Scope::new(), &self.ast, name, args);
+        self.ctx.world.set(ptr::null_mut());
+
+        match result {
+            Ok(_) => FFIResult::Success,
+            Err(e) => {
+                self.fault = Some(format!("Script error in {name}: {e}"));
+                FFIResult::PanicDetected
+            }
+        }
+    }
+
+    fn encode_state(&self, state: &Dynamic) -> Vec<u8> {
+        let json = match state.read_lock::<Map>() {
+            Some(map) => rhai::format_map_as_json(&map),
+            None => "{}".to_string(),
+        };
+        let envelope = StateEnvelope {
+            magic_header: SNAPSHOT_MAGIC_HEADER,
+            state_version: self.script_state_version(),
+            schema_hash: SCRIPT_SCHEMA_HASH,
+            payload_len: json.len() as u64,
+        };
+
+        let header_size = std::mem::size_of::<StateEnvelope>();
+        let mut bytes = vec![0u8; header_size + json.len()];
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                &envelope as *const StateEnvelope as *const u8,
+                bytes.as_mut_ptr(),
+                header_size,
+            );
+        }
+        bytes[header_size..].copy_from_slice(json.as_bytes());
+        bytes
+    }
+
+    fn script_state_version(&self) -> u32 {
+        if !self.has_fn("state_version") {
+            return 1;
+        }
+        self.engine
+            .call_fn::<i64>(&mut Scope::new(), &self.ast, "state_version", ())
+            .map_or(1, |v| v as u32)
+    }
+}
+
+impl PluginBackend for ScriptPlugin {
+    fn on_load(&mut self, world: &mut World, host_interface: &HostInterface) -> FFIResult {
+        self.ctx.host.set(Some(*host_interface));
+        self.call(world, "on_load", ())
+    }
+
+    fn on_update(&mut self, world: &mut World, input: &InputState, dt: f32) -> FFIResult {
+        self.ctx.input.set(*input);
+        self.call(world, "on_update", (dt as f64,))
+    }
+
+    fn get_state_len(&mut self) -> usize {
+        self.encode_state(&self.state).len()
+    }
+
+    fn save_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+        let bytes = self.encode_state(&self.state);
+        if buffer.len() < bytes.len() {
+            return FFIResult::BufferTooSmall;
+        }
+        buffer[..bytes.len()].copy_from_slice(&bytes);
+        FFIResult::Success
+    }
+
+    fn load_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+        let header_size = std::mem::size_of::<StateEnvelope>();
+        if buffer.len() < header_size {
+            return FFIResult::Error;
+        }
+        let mut envelope = StateEnvelope {
+            magic_header: 0,
+            state_version: 0,
+            schema_hash: 0,
+            payload_len: 0,
+        };
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                buffer.as_ptr(),
+                &mut envelope as *mut StateEnvelope as *mut u8,
+                header_size,
+            );
+        }
+        let Some(payload) = buffer[header_size..].get(..envelope.payload_len as usize) else {
+            return FFIResult::Error;
+        };
+        let Ok(json) = std::str::from_utf8(payload) else {
+            return FFIResult::SchemaMismatch;
+        };
+        match self.engine.parse_json(json, true) {
+            Ok(map) => {
+                self.state = Dynamic::from_map(map);
+                FFIResult::Success
+            }
+            Err(_) => FFIResult::SchemaMismatch,
+        }
+    }
+
+    fn schema_hash(&mut self) -> u64 {
+        SCRIPT_SCHEMA_HASH
+    }
+
+    fn state_version(&mut self) -> u32 {
+        self.script_state_version()
+    }
+
+    fn on_before_reload(&mut self, world: &mut World) -> FFIResult {
+        self.call(world, "on_before_reload", ())
+    }
+
+    fn on_after_reload(&mut self, world: &mut World) -> FFIResult {
+        self.call(world, "on_after_reload", ())
+    }
+
+    fn on_unload(&mut self, world: &mut World) -> FFIResult {
+        self.call(world, "on_unload", ())
+    }
+
+    fn unload(self: Box<Self>) {}
+
+    fn fault(&mut self) -> Option<String> {
+        self.fault.clone()
+    }
+}
+
+// ----------------------------------------------------------------------------------
+// Script API
+// ----------------------------------------------------------------------------------
+
+fn build_engine(name: &str, ctx: &Rc<ScriptContext>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+
+    let target = name.to_string();
+    engine.on_print(move |text| tracing::info!(target: "script", "[{target}] {text}"));
+    let target = name.to_string();
+    engine.on_debug(move |text, _, pos| tracing::debug!(target: "script", "[{target}:{pos}] {text}"));
+
+    let c = ctx.clone();
+    engine.register_fn("query", move |component: &str| -> ScriptResult<Array> {
+        Ok(entities_with(c.world()?, component)?
+            .into_iter()
+            .map(|e| Dynamic::from_int(e.to_bits() as i64))
+            .collect())
+    });
+    let c = ctx.clone();
+    engine.register_fn("get", move |entity: i64, component: &str| -> ScriptResult<Dynamic> {
+        let map = read_component(c.world()?, Entity::from_bits(entity as u64), component)?;
+        Ok(map.map_or(Dynamic::UNIT, Dynamic::from_map))
+    });
+    let c = ctx.clone();
+    engine.register_fn("set", move |entity: i64, component: &str, fields: Map| -> ScriptResult<()> {
+        write_component(c.world()?, Entity::from_bits(entity as u64), component, &fields)
+    });
+    let c = ctx.clone();
+    engine.register_fn("player", move || -> ScriptResult<i64> {
+        let world = c.world()?;
+        let player = world.query::<CPlayer>().and_then(|q| q.iter().next().map(|(e, _)| *e));
+        Ok(player.map_or(-1, |e| e.to_bits() as i64))
+    });
+    let c = ctx.clone();
+    engine.register_fn("spawn_prefab", move |prefab: &str, x: f64, y: f64| -> ScriptResult<i64> {
+        let entity = prefabs::spawn(c.world()?, prefab, Vec2::new(x as f32, y as f32))
+            .ok_or_else(|| format!("Unknown prefab '{prefab}' (known: {})", prefabs::PREFABS.join(", ")))?;
+        Ok(entity.to_bits() as i64)
+    });
+    let c = ctx.clone();
+    engine.register_fn("pressed", move |action: &str| -> bool {
+        let Some(host) = c.host.get() else { return false };
+        let id = (host.get_action_id)(action.as_ptr(), action.len());
+        id != ACTION_NOT_FOUND && c.input.get().is_active(id)
+    });
+    let c = ctx.clone();
+    engine.register_fn("just_pressed", move |action: &str| -> bool {
+        let Some(host) = c.host.get() else { return false };
+        let id = (host.get_action_id)(action.as_ptr(), action.len());
+        id != ACTION_NOT_FOUND && c.input.get().just_pressed(id)
+    });
+    let c = ctx.clone();
+    engine.register_fn("just_released", move |action: &str| -> bool {
+        let Some(host) = c.host.get() else { return false };
+        let id = (host.get_action_id)(action.as_ptr(), action.len());
+        id != ACTION_NOT_FOUND && c.input.get().just_released(id)
+    });
+    let c = ctx.clone();
+    engine.register_fn("cursor", move || -> Map {
+        let pos = c.input.get().cursor_world();
+        let mut map = Map::new();
+        map.insert("x".into(), (pos.x as f64).into());
+        map.insert("y".into(), (pos.y as f64).into());
+        map
+    });
+    let c = ctx.clone();
+    engine.register_fn("scroll", move || -> f64 { c.input.get().scroll() as f64 });
+    let c = ctx.clone();
+    engine.register_fn("axis", move |axis: &str| -> f64 {
+        let Some(host) = c.host.get() else { return 0.0 };
+        match (host.get_axis_id)(axis.as_ptr(), axis.len()) {
+            AXIS_NOT_FOUND => 0.0,
+            id => c.input.get().get_axis(id as usize) as f64,
+        }
+    });
+    let c = ctx.clone();
+    engine.register_fn("axis2", move |axis: &str| -> Map {
+        let value = c
+            .host
+            .get()
+            .map(|host| (host.get_axis_id)(axis.as_ptr(), axis.len()))
+            .filter(|&id| id != AXIS_NOT_FOUND)
+            .map_or(Vec2::ZERO, |id| c.input.get().get_axis_2d(id));
+        let mut map = Map::new();
+        map.insert("x".into(), (value.x as f64).into());
+        map.insert("y".into(), (value.y as f64).into());
+        map
+    });
+    let c = ctx.clone();
+    engine.register_fn("submit_movement", move |layer: &str, x: f64, y: f64, duration: f64| -> ScriptResult<bool> {
+        let layer = layer_from_name(layer)?;
+        let Some(host) = c.host.get() else { return Ok(false) };
+        Ok((host.submit_movement)(layer as u32, x as f32, y as f32, 1.0, duration as f32))
+    });
+    let c = ctx.clone();
+    engine.register_fn("submit_action", move |layer: &str, action: &str, duration: f64| -> ScriptResult<bool> {
+        let layer = layer_from_name(layer)?;
+        let Some(host) = c.host.get() else { return Ok(false) };
+        let id = (host.get_action_id)(action.as_ptr(), action.len());
+        Ok(id != ACTION_NOT_FOUND && (host.submit_action)(layer as u32, id, duration as f32))
+    });
+    let c = ctx.clone();
+    engine.register_fn("push_context", move |name: &str| -> bool {
+        let Some(host) = c.host.get() else { return false };
+        (host.push_input_context)(name.as_ptr(), name.len())
+    });
+    let c = ctx.clone();
+    engine.register_fn("pop_context", move |name: &str| -> bool {
+        let Some(host) = c.host.get() else { return false };
+        (host.pop_input_context)(name.as_ptr(), name.len())
+    });
+    let c = ctx.clone();
+    engine.register_fn("bind_context_key", move |context: &str, action: &str, key: &str| -> bool {
+        let Some(host) = c.host.get() else { return false };
+        let id = (host.register_action)(action.as_ptr(), action.len());
+        id != ACTION_NOT_FOUND && (host.bind_context_key)(context.as_ptr(), context.len(), id, key.as_ptr(), key.len())
+    });
+    let c = ctx.clone();
+    engine.register_fn("register_action", move |action: &str, default_key: &str| -> bool {
+        let Some(host) = c.host.get() else { return false };
+        let id = (host.register_action)(action.as_ptr(), action.len());
+        if id == ACTION_NOT_FOUND {
+            return false;
+        }
+        (host.bind_default_key)(id, default_key.as_ptr(), default_key.len());
+        true
+    });
+
+    engine.register_fn("subscribe", |topic: &str| -> ScriptResult<()> {
+        message_bus::subscribe(&bus_caller()?, topic);
+        Ok(())
+    });
+    engine.register_fn("unsubscribe", |topic: &str| -> ScriptResult<()> {
+        message_bus::unsubscribe(&bus_caller()?, topic);
+        Ok(())
+    });
+    engine.register_fn("publish", |topic: &str, text: &str| -> ScriptResult<()> {
+        message_bus::publish(&bus_caller()?, topic, 0, text.as_bytes());
+        Ok(())
+    });
+    engine.register_fn("publish", |topic: &str, payload: Blob| -> ScriptResult<()> {
+        message_bus::publish(&bus_caller()?, topic, 0, &payload);
+        Ok(())
+    });
+    engine.register_fn("publish", |topic: &str, schema: i64, payload: Blob| -> ScriptResult<()> {
+        message_bus::publish(&bus_caller()?, topic, schema as u64, &payload);
+        Ok(())
+    });
+    engine.register_fn("messages", || -> ScriptResult<Array> {
+        Ok(message_bus::take_inbox(&bus_caller()?)
+            .into_iter()
+            .map(|m| {
+                let mut map = Map::new();
+                map.insert("text".into(), String::from_utf8_lossy(&m.payload).into_owned().into());
+                map.insert("topic".into(), m.topic.into());
+                map.insert("schema".into(), (m.schema as i64).into());
+                map.insert("sender".into(), m.sender.into());
+                map.insert("payload".into(), Dynamic::from_blob(m.payload));
+                Dynamic::from_map(map)
+            })
+            .collect())
+    });
+    engine
+}
+
+fn layer_from_name(name: &str) -> ScriptResult<PriorityLayer> {
+    PriorityLayer::ALL
+        .into_iter()
+        .find(|layer| format!("{layer:?}").eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown layer '{name}' (Reflex, Cutscene, Control or Ambient)").into())
+}
+
+fn bus_caller() -> ScriptResult<String> {
+    message_bus::current_plugin().ok_or_else(|| "The message bus is not available here".into())
+}
+
+fn check_component(component: &str, write: bool) -> ScriptResult<()> {
+    match COMPONENTS.iter().find(|(name, _)| *name == component) {
+        None => Err(format!("Unknown component '{component}'").into()),
+        Some((_, false)) if write => Err(format!("Component '{component}' is read-only").into()),
+        Some(_) => Ok(()),
+    }
+}
+
+fn entities_with(world: &World, component: &str) -> ScriptResult<Vec<Entity>> {
+    fn ids<T: 'static>(world: &World) -> Vec<Entity> {
+        world
+            .query::<T>()
+            .map(|set| set.iter().map(|(e, _)| *e).collect())
+            .unwrap_or_default()
+    }
+    check_component(component, false)?;
+    Ok(match component {
+        "transform" => ids::<CTransform>(world),
+        "sprite" => ids::<CSprite>(world),
+        "enemy" => ids::<CEnemy>(world),
+        "camera" => ids::<CCamera>(world),
+        "player" => ids::<CPlayer>(world),
+        _ => ids::<CWorldBounds>(world),
+    })
+}
+
+fn read_component(world: &World, entity: Entity, component: &str) -> ScriptResult<Option<Map>> {
+    check_component(component, false)?;
+    let fields: Option<Vec<(&str, f32)>> = match component {
+        "transform" => world.get_component::<CTransform>(entity).map(|t| {
+            vec![
+                ("x", t.pos.x),
+                ("y", t.pos.y),
+                ("scale_x", t.scale.x),
+                ("scale_y", t.scale.y),
+                ("rotation", t.rotation),
+            ]
+        }),
+        "sprite" => world.get_component::<CSprite>(entity).map(|s| {
+            vec![("r", s.color.x), ("g", s.color.y), ("b", s.color.z), ("a", s.color.w)]
+        }),
+        "enemy" => world.get_component::<CEnemy>(entity).map(|e| vec![("speed", e.speed)]),
+        "camera" => world
+            .get_component::<CCamera>(entity)
+            .map(|c| vec![("zoom", c.zoom), ("smoothness", c.smoothness)]),
+        "player" => world.get_component::<CPlayer>(entity).map(|_| Vec::new()),
+        _ => world
+            .get_component::<CWorldBounds>(entity)
+            .map(|b| vec![("width", b.width), ("height", b.height)]),
+    };
+    Ok(fields.map(|fields| {
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.into(), Dynamic::from_float(v as f64)))
+            .collect()
+    }))
+}
+
+/// Inserts or updates a component; fields missing from `fields` keep their current value.
+fn write_component(world: &mut World, entity: Entity, component: &str, fields: &Map) -> ScriptResult<()> {
+    fn insert<T: 'static>(world: &mut World, entity: Entity, value: T) -> ScriptResult<()> {
+        let set = world.query_mut::<T>().ok_or("Component is not registered")?;
+        set.insert(entity, value);
+        Ok(())
+    }
+    let field = |key: &str, current: f32| -> f32 {
+        fields
+            .get(key)
+            .and_then(|v| v.as_float().ok().or_else(|| v.as_int().ok().map(|i| i as f64)))
+            .map_or(current, |v| v as f32)
+    };
+
+    check_component(component, true)?;
+    match component {
+        "transform" => {
+            let t = world.get_component::<CTransform>(entity).copied().unwrap_or_default();
+            let t = CTransform {
+                pos: Vec2::new(field("x", t.pos.x), field("y", t.pos.y)),
+                scale: Vec2::new(field("scale_x", t.scale.x), field("scale_y", t.scale.y)),
+                rotation: field("rotation", t.rotation),
+            };
+            insert(world, entity, t)
+        }
+        "sprite" => {
+            let mut s = world.get_component::<CSprite>(entity).copied().unwrap_or_default();
+            s.color.x = field("r", s.color.x);
+            s.color.y = field("g", s.color.y);
+            s.color.z = field("b", s.color.z);
+            s.color.w = field("a", s.color.w);
+            insert(world, entity, s)
+        }
+        "enemy" => {
+            let speed = world.get_component::<CEnemy>(entity).map_or(100.0, |e| e.speed);
+            insert(world, entity, CEnemy { speed: field("speed", speed) })
+        }
+        _ => {
+            let c = world.get_component::<CCamera>(entity).copied().unwrap_or_default();
+            let c = CCamera {
+                zoom: field("zoom", c.zoom),
+                smoothness: field("smoothness", c.smoothness),
+            };
+            insert(world, entity, c)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{host, scene};
+
+    const SCRIPT: &str = r#"
+        fn init() { #{ ticks: 0 } }
+        fn on_update(dt) {
+            this.ticks += 1;
+            for e in query("enemy") {
+                let t = get(e, "transform");
+                set(e, "transform", #{ x: t.x + 10.0 });
+            }
+            if this.ticks == 2 { spawn_prefab("enemy", 0.0, 0.0); }
+            if this.ticks == 5 { set(player(), "player", #{}); }
+        }
+    "#;
+
+    #[test]
+    fn script_drives_world_and_reports_errors() {
+        let mut world = World::new();
+        scene::setup_default_world(&mut world);
+        let player = world.spawn();
+        world.add_component(player, CPlayer);
+
+        let mut plugin = ScriptPlugin::from_source("test", SCRIPT).unwrap();
+        assert_eq!(plugin.on_load(&mut world, &host::create_interface()), FFIResult::Success);
+        let input = InputState::default();
+        for _ in 0..4 {
+            assert_eq!(plugin.on_update(&mut world, &input, 0.016), FFIResult::Success);
+        }
+
+        // Spawned on tick 2, moved on ticks 3 and 4.
+        let (&enemy, _) = world.query::<CEnemy>().unwrap().iter().next().unwrap();
+        assert_eq!(world.get_component::<CTransform>(enemy).unwrap().pos.x, 20.0);
+
+        let mut state = vec![0u8; plugin.get_state_len()];
+        assert_eq!(plugin.save_state(&mut state), FFIResult::Success);
+        let mut reloaded = ScriptPlugin::from_source("test", SCRIPT).unwrap();
+        assert_eq!(reloaded.load_state(&mut state), FFIResult::Success);
+        assert_eq!(reloaded.state.read_lock::<Map>().unwrap()["ticks"].as_int(), Ok(4));
+
+        // Tick 5 writes a read-only component.
+        assert_eq!(plugin.on_update(&mut world, &input, 0.016), FFIResult::PanicDetected);
+        assert!(plugin.fault().unwrap().contains("read-only"));
+    }
+}
@@ -0,0 +1,86 @@
+// crates/engine_core/src/plugin_status.rs
+//! "Plugins" window: runtime state of every loaded plugin and its recent reloads.
+
+use std::time::SystemTime;
+
+use egui::{Color32, Context};
+
+use crate::message_bus;
+use crate::plugin_manager::{PluginManager, PluginRuntimeState, StateRestore};
+
+pub fn show(ctx: &Context, manager: &PluginManager) {
+    egui::Window::new("Plugins")
+        .default_pos([20.0, 400.0])
+        .default_width(320.0)
+        .show(ctx, |ui| {
+            if manager.plugins.is_empty() && manager.rejected.is_empty() {
+                ui.label("No plugins loaded.");
+            }
+
+            let (queued, subscribers) = message_bus::stats();
+            ui.label(format!("Message bus: {queued} queued, {subscribers} subscribers"));
+            ui.separator();
+
+            for plugin in &manager.plugins {
+                let (color, state) = match &plugin.runtime_state {
+                    PluginRuntimeState::Running => (Color32::GREEN, "Running".to_string()),
+                    PluginRuntimeState::PausedError(msg) => (Color32::RED, format!("Paused: {msg}")),
+                };
+                ui.horizontal(|ui| {
+                    ui.strong(&plugin.manifest.name);
+                    ui.colored_label(color, state);
+                });
+
+                let budget = &plugin.budget;
+                let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+                ui.colored_label(
+                    if budget.is_over() { Color32::YELLOW } else { Color32::GRAY },
+                    format!(
+                        "Update {:.2} ms (worst {:.2}, budget {:.1}, {} over){}",
+                        ms(budget.last),
+                        ms(budget.worst),
+                        ms(budget.budget),
+                        budget.overruns,
+                        if plugin.manifest.guarded { ", guarded" } else { "" },
+                    ),
+                );
+
+                let history = &plugin.reload_history;
+                ui.collapsing(format!("Reloads ({})", history.len()), |ui| {
+                    if history.is_empty() {
+                        ui.label("None yet.");
+                    }
+                    // Newest first.
+                    for event in history.iter().rev() {
+                        let ago = SystemTime::now()
+                            .duration_since(event.at)
+                            .map(|d| d.as_secs())
+                            .unwrap_or(0);
+                        let (color, outcome) = match &event.outcome {
+                            Ok(StateRestore::Restored) => (Color32::GREEN, "state restored".to_string()),
+                            Ok(StateRestore::NoState) => (Color32::GRAY, "no state".to_string()),
+                            Ok(StateRestore::Discarded(why)) => {
+                                (Color32::YELLOW, format!("state discarded: {why}"))
+                            }
+                            Err(e) => (Color32::RED, e.clone()),
+                        };
+                        ui.colored_label(
+                            color,
+                            format!(
+                                "{ago}s ago, {:.1} ms - {outcome}",
+                                event.duration.as_secs_f64() * 1000.0
+                            ),
+                        );
+                    }
+                });
+                ui.separator();
+            }
+
+            for (manifest, reason) in &manager.rejected {
+                ui.horizontal(|ui| {
+                    ui.strong(&manifest.name);
+                    ui.colored_label(Color32::RED, format!("Rejected: {reason}"));
+                });
+            }
+        });
+}
@@ -0,0 +1,217 @@
+// crates/engine_core/src/plugin_ui.rs
+//! Host side of `DebugUi`: each callback forwards to the egui `Ui` that the
+//! opaque `DebugUiContext` points at for the duration of `on_debug_ui`.
+
+use egui::{Color32, Sense, Shape, Stroke, Ui};
+use engine_shared::plugin_api::{DebugUi, DebugUiContext};
+
+use crate::logging;
+
+/// Builds a `DebugUi` over `ui` and runs `f` with it. The vtable must not outlive `f`.
+pub fn with_debug_ui<R>(ui: &mut Ui, f: impl FnOnce(&DebugUi) -> R) -> R {
+    let debug_ui = DebugUi {
+        ctx: ui as *mut Ui as *mut DebugUiContext,
+        label: ui_label,
+        heading: ui_heading,
+        separator: ui_separator,
+        button: ui_button,
+        checkbox: ui_checkbox,
+        slider_f32: ui_slider_f32,
+        slider_i32: ui_slider_i32,
+        plot_lines: ui_plot_lines,
+    };
+    f(&debug_ui)
+}
+
+unsafe fn ui<'a>(ctx: *mut DebugUiContext) -> &'a mut Ui {
+    &mut *(ctx as *mut Ui)
+}
+
+unsafe fn text<'a>(ptr: *const u8, len: usize) -> &'a str {
+    logging::str_from_raw(ptr, len).unwrap_or("<invalid utf-8>")
+}
+
+extern "C" fn ui_label(ctx: *mut DebugUiContext, text_ptr: *const u8, text_len: usize) {
+    unsafe { ui(ctx).label(text(text_ptr, text_len)) };
+}
+
+extern "C" fn ui_heading(ctx: *mut DebugUiContext, text_ptr: *const u8, text_len: usize) {
+    unsafe { ui(ctx).heading(text(text_ptr, text_len)) };
+}
+
+extern "C" fn ui_separator(ctx: *mut DebugUiContext) {
+    unsafe { ui(ctx).separator() };
+}
+
+extern "C" fn ui_button(ctx: *mut DebugUiContext, text_ptr: *const u8, text_len: usize) -> bool {
+    unsafe { ui(ctx).button(text(text_ptr, text_len)).clicked() }
+}
+
+extern "C" fn ui_checkbox(
+    ctx: *mut DebugUiContext,
+    text_ptr: *const u8,
+    text_len: usize,
+    value: *mut bool,
+) -> bool {
+    if value.is_null() {
+        return false;
+    }
+    unsafe { ui(ctx).checkbox(&mut *value, text(text_ptr, text_len)).changed() }
+}
+
+extern "C" fn ui_slider_f32(
+    ctx: *mut DebugUiContext,
+    text_ptr: *const u8,
+    text_len: usize,
+    value: *mut f32,
+    min: f32,
+    max: f32,
+) -> bool {
+    if value.is_null() {
+        return false;
+    }
+    unsafe {
+        ui(ctx)
+            .add(egui::Slider::new(&mut *value, min..=max).text(text(text_ptr, text_len)))
+            .changed()
+    }
+}
+
+extern "C" fn ui_slider_i32(
+    ctx: *mut DebugUiContext,
+    text_ptr: *const u8,
+    text_len: usize,
+    value: *mut i32,
+    min: i32,
+    max: i32,
+) -> bool {
+    if value.is_null() {
+        return false;
+    }
+    unsafe {
+        ui(ctx)
+            .add(egui::Slider::new(&mut *value, min..=max).text(text(text_ptr, text_len)))
+            .changed()
+    }
+}
+
+extern "C" fn ui_plot_lines(
+    ctx: *mut DebugUiContext,
+    text_ptr: *const u8,
+    text_len: usize,
+    values: *const f32,
+    count: usize,
+) {
+    let ui = unsafe { ui(ctx) };
+    let label = unsafe { text(text_ptr, text_len) };
+    let values: &[f32] = if values.is_null() {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(values, count) }
+    };
+
+    let (min, max) = values
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    match values.last() {
+        Some(last) => ui.label(format!("{label}: {last:.2} (min {min:.2}, max {max:.2})")),
+        None => ui.label(format!("{label}: no samples")),
+    };
+
+    let width = ui.available_width().clamp(120.0, 320.0);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, 48.0), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    if values.len() < 2 {
+        return;
+    }
+    let span = (max - min).max(f32::EPSILON);
+    let step = rect.width() / (values.len() - 1) as f32;
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| egui::pos2(rect.left() + i as f32 * step, rect.bottom() - (v - min) / span * rect.height()))
+        .collect();
+    painter.add(Shape::line(points, Stroke::new(1.5, Color32::LIGHT_GREEN)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Event, Modifiers, PointerButton, Pos2, RawInput};
+
+    /// Runs one egui frame where `f` draws through the vtable, at the top-left
+    /// of the screen. Returns the text drawn.
+    fn frame(ctx: &egui::Context, events: Vec<Event>, f: impl FnOnce(&DebugUi)) -> Vec<String> {
+        let input = RawInput {
+            events,
+            screen_rect: Some(egui::Rect::from_min_size(Pos2::ZERO, egui::vec2(800.0, 600.0))),
+            ..Default::default()
+        };
+        let output = ctx.run(input, |ctx| {
+            egui::CentralPanel::default()
+                .frame(egui::Frame::none())
+                .show(ctx, |ui| with_debug_ui(ui, f));
+        });
+        output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                Shape::Text(text) => Some(text.galley.text().to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn click_at(pos: Pos2) -> Vec<Event> {
+        let button = |pressed| Event::PointerButton { pos, button: PointerButton::Primary, pressed, modifiers: Modifiers::NONE };
+        vec![Event::PointerMoved(pos), button(true), button(false)]
+    }
+
+    #[test]
+    fn every_widget_draws_and_reports_no_change_without_input() {
+        let ctx = egui::Context::default();
+        let (mut paused, mut speed, mut count) = (false, 2.5f32, 3i32);
+        let samples = [1.0f32, 4.0, 2.0];
+        let invalid = [0xFFu8, 0xFE];
+
+        let drawn = frame(&ctx, Vec::new(), |d| {
+            (d.heading)(d.ctx, "Spawner".as_ptr(), 7);
+            (d.label)(d.ctx, invalid.as_ptr(), invalid.len());
+            (d.separator)(d.ctx);
+            assert!(!(d.button)(d.ctx, "Reset".as_ptr(), 5));
+            assert!(!(d.checkbox)(d.ctx, "Paused".as_ptr(), 6, &mut paused));
+            assert!(!(d.slider_f32)(d.ctx, "Speed".as_ptr(), 5, &mut speed, 0.0, 10.0));
+            assert!(!(d.slider_i32)(d.ctx, "Count".as_ptr(), 5, &mut count, 0, 10));
+            (d.plot_lines)(d.ctx, "Frame ms".as_ptr(), 8, samples.as_ptr(), samples.len());
+            (d.plot_lines)(d.ctx, "Empty".as_ptr(), 5, std::ptr::null(), 4);
+            // Null value pointers are ignored rather than written through.
+            assert!(!(d.checkbox)(d.ctx, "Null".as_ptr(), 4, std::ptr::null_mut()));
+            assert!(!(d.slider_f32)(d.ctx, "Null".as_ptr(), 4, std::ptr::null_mut(), 0.0, 1.0));
+            assert!(!(d.slider_i32)(d.ctx, "Null".as_ptr(), 4, std::ptr::null_mut(), 0, 1));
+        });
+
+        for text in ["Spawner", "<invalid utf-8>", "Reset", "Paused", "Speed", "Count", "Empty: no samples"] {
+            assert!(drawn.iter().any(|t| t == text), "{text:?} not drawn in {drawn:?}");
+        }
+        assert!(drawn.iter().any(|t| t == "Frame ms: 2.00 (min 1.00, max 4.00)"));
+        assert_eq!((paused, speed, count), (false, 2.5, 3));
+    }
+
+    #[test]
+    fn clicking_a_checkbox_writes_through_to_the_plugin() {
+        let ctx = egui::Context::default();
+        let mut paused = false;
+        let mut checkbox = |events| {
+            let mut changed = false;
+            frame(&ctx, events, |d| changed = (d.checkbox)(d.ctx, "Paused".as_ptr(), 6, &mut paused));
+            changed
+        };
+
+        // Widgets are hit-tested against the previous frame's layout.
+        assert!(!checkbox(Vec::new()));
+        assert!(checkbox(click_at(egui::pos2(6.0, 6.0))));
+        assert!(paused);
+    }
+}
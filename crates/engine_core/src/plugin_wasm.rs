@@ -0,0 +1,919 @@
+// crates/engine_core/src/plugin_wasm.rs
+//! WebAssembly plugin backend (`.wasm` libraries), run by the embedded wasmi interpreter.
+//!
+//! The module implements the `PluginApi` lifecycle as exports and reaches the
+//! host through imports that forward to the `HostInterface` given in `on_load`
+//! (see `engine_shared::wasm_abi` for the contract). A wasm plugin can only
+//! touch its own linear memory, so a bad plugin traps instead of corrupting the
+//! host; traps are reported like panics. The module is read into memory once,
+//! so hot reload needs no library copy.
+//!
+//! Not available to wasm plugins: debug panels (`on_debug_ui`).
+
+use std::ffi::c_void;
+use std::fs;
+use std::path::Path;
+use std::ptr;
+
+use engine_ecs::{Entity, World};
+use engine_shared::input_types::{InputState, ACTION_NOT_FOUND, AXIS_NOT_FOUND};
+use engine_shared::plugin_api::{
+    BusMessageHeader, FFIResult, HostContext, HostInterface, StateEnvelope, SNAPSHOT_MAGIC_HEADER,
+};
+use engine_shared::wasm_abi::{self, WasmComponent, IMPORT_MODULE};
+use engine_shared::{CCamera, CEnemy, CPlayer, CSprite, CTransform, CWorldBounds, ENGINE_API_VERSION};
+use glam::{Vec2, Vec4};
+use wasmi::{Caller, Engine, Linker, Memory, Module, Store, TypedFunc};
+
+use crate::plugin_manager::PluginBackend;
+
+/// Host-side handle to a loaded wasm module.
+pub struct WasmPlugin {
+    /// Boxed so console-command trampolines can point at it.
+    inner: Box<Inner>,
+}
+
+struct Inner {
+    store: Store<HostState>,
+    memory: Memory,
+    exports: Exports,
+    fault: Option<String>,
+}
+
+/// What the imports can reach. `world` is only set while the host is calling in.
+struct HostState {
+    world: *mut World,
+    host: Option<HostInterface>,
+    plugin: *mut Inner,
+    /// `user_data` of the console commands this plugin registered (boxed so
+    /// their addresses survive the Vec growing).
+    #[allow(clippy::vec_box)]
+    commands: Vec<Box<WasmCommand>>,
+}
+
+struct WasmCommand {
+    plugin: *mut Inner,
+    id: i32,
+}
+
+struct Exports {
+    alloc: TypedFunc<i32, i32>,
+    on_load: TypedFunc<(), i32>,
+    on_update: TypedFunc<(i32, f32), i32>,
+    get_state_len: TypedFunc<(), i32>,
+    save_state: TypedFunc<(i32, i32), i32>,
+    load_state: TypedFunc<(i32, i32), i32>,
+    schema_hash: TypedFunc<(), i64>,
+    state_version: TypedFunc<(), i32>,
+    on_unload: Option<TypedFunc<(), i32>>,
+    on_before_reload: Option<TypedFunc<(), i32>>,
+    on_after_reload: Option<TypedFunc<(), i32>>,
+    on_command: Option<TypedFunc<(i32, i32, i32), ()>>,
+}
+
+impl WasmPlugin {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Instantiates a module (binary `.wasm`) and checks its API version.
+    pub fn from_bytes(wasm: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, wasm)?;
+        let mut store = Store::new(
+            &engine,
+            HostState {
+                world: ptr::null_mut(),
+                host: None,
+                plugin: ptr::null_mut(),
+                commands: Vec::new(),
+            },
+        );
+        let mut linker = Linker::new(&engine);
+        define_imports(&mut linker)?;
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+
+        let version = instance
+            .get_typed_func::<(), i32>(&store, wasm_abi::EXPORT_API_VERSION)
+            .map_err(|_| "Incompatible plugin: missing `engine_api_version` export")?
+            .call(&mut store, ())?;
+        if version as u32 != ENGINE_API_VERSION {
+            return Err(format!(
+                "Incompatible plugin: API version {version} (host expects {ENGINE_API_VERSION})"
+            )
+            .into());
+        }
+
+        let memory = instance
+            .get_memory(&store, wasm_abi::EXPORT_MEMORY)
+            .ok_or("Incompatible plugin: missing `memory` export")?;
+
+        macro_rules! required {
+            ($name:expr) => {
+                instance
+                    .get_typed_func(&store, $name)
+                    .map_err(|e| format!("Incompatible plugin: export `{}`: {e}", $name))?
+            };
+        }
+        macro_rules! optional {
+            ($name:expr) => {
+                instance.get_typed_func(&store, $name).ok()
+            };
+        }
+        let exports = Exports {
+            alloc: required!(wasm_abi::EXPORT_ALLOC),
+            on_load: required!(wasm_abi::EXPORT_ON_LOAD),
+            on_update: required!(wasm_abi::EXPORT_ON_UPDATE),
+            get_state_len: required!(wasm_abi::EXPORT_GET_STATE_LEN),
+            save_state: required!(wasm_abi::EXPORT_SAVE_STATE),
+            load_state: required!(wasm_abi::EXPORT_LOAD_STATE),
+            schema_hash: required!(wasm_abi::EXPORT_SCHEMA_HASH),
+            state_version: required!(wasm_abi::EXPORT_STATE_VERSION),
+            on_unload: optional!(wasm_abi::EXPORT_ON_UNLOAD),
+            on_before_reload: optional!(wasm_abi::EXPORT_ON_BEFORE_RELOAD),
+            on_after_reload: optional!(wasm_abi::EXPORT_ON_AFTER_RELOAD),
+            on_command: optional!(wasm_abi::EXPORT_ON_COMMAND),
+        };
+
+        let mut inner = Box::new(Inner {
+            store,
+            memory,
+            exports,
+            fault: None,
+        });
+        let plugin: *mut Inner = &mut *inner;
+        inner.store.data_mut().plugin = plugin;
+
+        Ok(Self { inner })
+    }
+}
+
+impl Inner {
+    /// Runs `f` with `world` reachable from the imports. A trap becomes the fault.
+    fn invoke<R>(
+        &mut self,
+        world: *mut World,
+        f: impl FnOnce(&mut Store<HostState>, &Exports, Memory) -> Result<R, wasmi::Error>,
+    ) -> Result<R, FFIResult> {
+        self.store.data_mut().world = world;
+        let result = f(&mut self.store, &self.exports, self.memory);
+        self.store.data_mut().world = ptr::null_mut();
+
+        result.map_err(|e| {
+            self.fault = Some(format!("wasm trap: {e}"));
+            FFIResult::PanicDetected
+        })
+    }
+
+    fn hook(&mut self, world: &mut World, pick: fn(&Exports) -> Option<&TypedFunc<(), i32>>) -> FFIResult {
+        let Some(func) = pick(&self.exports).cloned() else {
+            return FFIResult::Success;
+        };
+        match self.invoke(world, |store, _, _| func.call(store, ())) {
+            Ok(res) => FFIResult::from_u32(res as u32),
+            Err(res) => res,
+        }
+    }
+
+    fn run_command(&mut self, world: *mut World, id: i32, args: &[u8]) {
+        let result = self.invoke(world, |store, exports, memory| {
+            let Some(on_command) = &exports.on_command else { return Ok(()) };
+            let ptr = exports.alloc.call(&mut *store, args.len() as i32)?;
+            memory.write(&mut *store, ptr as u32 as usize, args)?;
+            on_command.call(store, (id, ptr, args.len() as i32))
+        });
+        if result.is_err() {
+            tracing::error!(
+                "❌ Wasm plugin trapped in console command: {}",
+                self.fault.as_deref().unwrap_or("unknown")
+            );
+        }
+    }
+}
+
+impl PluginBackend for WasmPlugin {
+    fn on_load(&mut self, world: &mut World, host_interface: &HostInterface) -> FFIResult {
+        self.inner.store.data_mut().host = Some(*host_interface);
+        match self.inner.invoke(world, |store, exports, _| exports.on_load.call(store, ())) {
+            Ok(res) => FFIResult::from_u32(res as u32),
+            Err(res) => res,
+        }
+    }
+
+    fn on_update(&mut self, world: &mut World, input: &InputState, dt: f32) -> FFIResult {
+        let input = wasm_abi::encode_input(input);
+        let result = self.inner.invoke(world, |store, exports, memory| {
+            let ptr = exports.alloc.call(&mut *store, input.len() as i32)?;
+            memory.write(&mut *store, ptr as u32 as usize, &input)?;
+            exports.on_update.call(store, (ptr, dt))
+        });
+        match result {
+            Ok(res) => FFIResult::from_u32(res as u32),
+            Err(res) => res,
+        }
+    }
+
+    // The module saves and loads its raw state; the host adds the `StateEnvelope`
+    // around it, so `restore_state` can check it like any other plugin's.
+    fn get_state_len(&mut self) -> usize {
+        let lengths = self.inner.invoke(ptr::null_mut(), |store, exports, memory| {
+            let len = exports.get_state_len.call(&mut *store, ())?;
+            Ok((len.max(0) as usize, memory.data(&*store).len()))
+        });
+        match lengths {
+            Ok((0, _)) | Err(_) => 0,
+            // The state is staged in guest memory, so it can't be larger than that.
+            Ok((len, memory_size)) if len > memory_size => {
+                tracing::warn!("⚠️ wasm plugin state length {len} exceeds its memory ({memory_size} bytes)");
+                0
+            }
+            Ok((len, _)) => std::mem::size_of::<StateEnvelope>() + len,
+        }
+    }
+
+    fn save_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+        let header_size = std::mem::size_of::<StateEnvelope>();
+        if buffer.len() < header_size {
+            return FFIResult::BufferTooSmall;
+        }
+        let (header, payload) = buffer.split_at_mut(header_size);
+        let result = self.inner.invoke(ptr::null_mut(), |store, exports, memory| {
+            let ptr = exports.alloc.call(&mut *store, payload.len() as i32)?;
+            let res = FFIResult::from_u32(
+                exports.save_state.call(&mut *store, (ptr, payload.len() as i32))? as u32,
+            );
+            if res == FFIResult::Success {
+                memory.read(&*store, ptr as u32 as usize, payload)?;
+            }
+            Ok(res)
+        });
+        let res = result.unwrap_or_else(|res| res);
+        if res != FFIResult::Success {
+            return res;
+        }
+
+        let envelope = StateEnvelope {
+            magic_header: SNAPSHOT_MAGIC_HEADER,
+            state_version: self.state_version(),
+            schema_hash: self.schema_hash(),
+            payload_len: payload.len() as u64,
+        };
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                &envelope as *const StateEnvelope as *const u8,
+                header.as_mut_ptr(),
+                header_size,
+            );
+        }
+        FFIResult::Success
+    }
+
+    fn load_state(&mut self, buffer: &mut [u8]) -> FFIResult {
+        let header_size = std::mem::size_of::<StateEnvelope>();
+        if buffer.len() < header_size {
+            return FFIResult::Error;
+        }
+        let mut envelope = StateEnvelope {
+            magic_header: 0,
+            state_version: 0,
+            schema_hash: 0,
+            payload_len: 0,
+        };
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                buffer.as_ptr(),
+                &mut envelope as *mut StateEnvelope as *mut u8,
+                header_size,
+            );
+        }
+        let Some(payload) = buffer[header_size..].get(..envelope.payload_len as usize) else {
+            return FFIResult::Error;
+        };
+
+        let result = self.inner.invoke(ptr::null_mut(), |store, exports, memory| {
+            let ptr = exports.alloc.call(&mut *store, payload.len() as i32)?;
+            memory.write(&mut *store, ptr as u32 as usize, payload)?;
+            exports.load_state.call(store, (ptr, payload.len() as i32))
+        });
+        match result {
+            Ok(res) => FFIResult::from_u32(res as u32),
+            Err(res) => res,
+        }
+    }
+
+    fn schema_hash(&mut self) -> u64 {
+        self.inner
+            .invoke(ptr::null_mut(), |store, exports, _| exports.schema_hash.call(store, ()))
+            .map_or(0, |hash| hash as u64)
+    }
+
+    fn state_version(&mut self) -> u32 {
+        self.inner
+            .invoke(ptr::null_mut(), |store, exports, _| exports.state_version.call(store, ()))
+            .map_or(0, |version| version as u32)
+    }
+
+    fn on_before_reload(&mut self, world: &mut World) -> FFIResult {
+        self.inner.hook(world, |e| e.on_before_reload.as_ref())
+    }
+
+    fn on_after_reload(&mut self, world: &mut World) -> FFIResult {
+        self.inner.hook(world, |e| e.on_after_reload.as_ref())
+    }
+
+    fn on_unload(&mut self, world: &mut World) -> FFIResult {
+        self.inner.hook(world, |e| e.on_unload.as_ref())
+    }
+
+    fn unload(self: Box<Self>) {
+        // Dropping the store frees the instance and its memory.
+    }
+
+    fn fault(&mut self) -> Option<String> {
+        self.inner.fault.clone()
+    }
+}
+
+/// `ConsoleCommandFn` for commands registered by a wasm plugin.
+extern "C" fn wasm_command(
+    user_data: *mut c_void,
+    host_ctx: *mut HostContext,
+    args_ptr: *const u8,
+    args_len: usize,
+) {
+    // Commands are removed before their plugin unloads, so both pointers are live.
+    let command = unsafe { &*(user_data as *const WasmCommand) };
+    let args = if args_ptr.is_null() {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(args_ptr, args_len) }
+    };
+    unsafe { (*command.plugin).run_command(host_ctx as *mut World, command.id, args) };
+}
+
+// ----------------------------------------------------------------------------------
+// Imports
+// ----------------------------------------------------------------------------------
+
+/// Copies `len` bytes at `ptr` out of guest memory. The range is checked
+/// against the memory first, so a bogus `len` can't make the host allocate.
+fn read_bytes(caller: &Caller<'_, HostState>, ptr: i32, len: i32) -> Option<Vec<u8>> {
+    let memory = caller.get_export(wasm_abi::EXPORT_MEMORY)?.into_memory()?;
+    let start = ptr as u32 as usize;
+    let end = start.checked_add(usize::try_from(len).ok()?)?;
+    memory.data(caller).get(start..end).map(<[u8]>::to_vec)
+}
+
+fn write_bytes(caller: &mut Caller<'_, HostState>, ptr: i32, bytes: &[u8]) -> bool {
+    let Some(memory) = caller
+        .get_export(wasm_abi::EXPORT_MEMORY)
+        .and_then(|e| e.into_memory())
+    else {
+        return false;
+    };
+    memory.write(caller, ptr as u32 as usize, bytes).is_ok()
+}
+
+fn world<'a>(caller: &Caller<'_, HostState>) -> Option<&'a mut World> {
+    // Only non-null while the host is inside a call with `&mut World` in hand.
+    unsafe { caller.data().world.as_mut() }
+}
+
+fn define_imports(linker: &mut Linker<HostState>) -> Result<(), wasmi::Error> {
+    linker.func_wrap(IMPORT_MODULE, "get_action_id", |caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+        match (caller.data().host, read_bytes(&caller, ptr, len)) {
+            (Some(host), Some(name)) => (host.get_action_id)(name.as_ptr(), name.len()) as i32,
+            _ => ACTION_NOT_FOUND as i32,
+        }
+    })?;
+    linker.func_wrap(IMPORT_MODULE, "register_action", |caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+        match (caller.data().host, read_bytes(&caller, ptr, len)) {
+            (Some(host), Some(name)) => (host.register_action)(name.as_ptr(), name.len()) as i32,
+            _ => ACTION_NOT_FOUND as i32,
+        }
+    })?;
+    linker.func_wrap(IMPORT_MODULE, "get_axis_id", |caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+        match (caller.data().host, read_bytes(&caller, ptr, len)) {
+            (Some(host), Some(name)) => (host.get_axis_id)(name.as_ptr(), name.len()) as i32,
+            _ => AXIS_NOT_FOUND as i32,
+        }
+    })?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "bind_default_key",
+        |caller: Caller<'_, HostState>, action: i32, ptr: i32, len: i32| -> i32 {
+            match (caller.data().host, read_bytes(&caller, ptr, len)) {
+                (Some(host), Some(key)) => (host.bind_default_key)(action as u32, key.as_ptr(), key.len()) as i32,
+                _ => 0,
+            }
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "bind_context_key",
+        |caller: Caller<'_, HostState>, c_ptr: i32, c_len: i32, action: i32, k_ptr: i32, k_len: i32| -> i32 {
+            let host = caller.data().host;
+            match (host, read_bytes(&caller, c_ptr, c_len), read_bytes(&caller, k_ptr, k_len)) {
+                (Some(host), Some(context), Some(key)) => {
+                    (host.bind_context_key)(context.as_ptr(), context.len(), action as u32, key.as_ptr(), key.len())
+                        as i32
+                }
+                _ => 0,
+            }
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "push_input_context",
+        |caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+            match (caller.data().host, read_bytes(&caller, ptr, len)) {
+                (Some(host), Some(name)) => (host.push_input_context)(name.as_ptr(), name.len()) as i32,
+                _ => 0,
+            }
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "pop_input_context",
+        |caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+            match (caller.data().host, read_bytes(&caller, ptr, len)) {
+                (Some(host), Some(name)) => (host.pop_input_context)(name.as_ptr(), name.len()) as i32,
+                _ => 0,
+            }
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "submit_movement",
+        |caller: Caller<'_, HostState>, layer: i32, x: f32, y: f32, weight: f32, duration: f32| -> i32 {
+            caller
+                .data()
+                .host
+                .is_some_and(|host| (host.submit_movement)(layer as u32, x, y, weight, duration)) as i32
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "submit_action",
+        |caller: Caller<'_, HostState>, layer: i32, action: i32, duration: f32| -> i32 {
+            caller
+                .data()
+                .host
+                .is_some_and(|host| (host.submit_action)(layer as u32, action as u32, duration)) as i32
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "log",
+        |caller: Caller<'_, HostState>, level: i32, t_ptr: i32, t_len: i32, m_ptr: i32, m_len: i32| {
+            let Some(host) = caller.data().host else { return };
+            let target = read_bytes(&caller, t_ptr, t_len).unwrap_or_default();
+            let Some(msg) = read_bytes(&caller, m_ptr, m_len) else { return };
+            (host.log)(level as u32, target.as_ptr(), target.len(), msg.as_ptr(), msg.len());
+        },
+    )?;
+    linker.func_wrap(IMPORT_MODULE, "spawn_enemy", |caller: Caller<'_, HostState>, x: f32, y: f32| {
+        if let (Some(host), Some(world)) = (caller.data().host, world(&caller)) {
+            (host.spawn_enemy)(world as *mut World as *mut HostContext, x, y);
+        }
+    })?;
+
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "cvar_register",
+        |caller: Caller<'_, HostState>,
+         kind: i32,
+         flags: i32,
+         n_ptr: i32,
+         n_len: i32,
+         d_ptr: i32,
+         d_len: i32,
+         desc_ptr: i32,
+         desc_len: i32|
+         -> i32 {
+            let Some(host) = caller.data().host else { return 0 };
+            let (Some(name), Some(default)) =
+                (read_bytes(&caller, n_ptr, n_len), read_bytes(&caller, d_ptr, d_len))
+            else {
+                return 0;
+            };
+            let desc = read_bytes(&caller, desc_ptr, desc_len).unwrap_or_default();
+            (host.cvar_register)(
+                kind as u32,
+                flags as u32,
+                name.as_ptr(),
+                name.len(),
+                default.as_ptr(),
+                default.len(),
+                desc.as_ptr(),
+                desc.len(),
+            ) as i32
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "cvar_get_f64",
+        |caller: Caller<'_, HostState>, ptr: i32, len: i32, fallback: f64| -> f64 {
+            match (caller.data().host, read_bytes(&caller, ptr, len)) {
+                (Some(host), Some(name)) => (host.cvar_get_f64)(name.as_ptr(), name.len(), fallback),
+                _ => fallback,
+            }
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "cvar_get_str",
+        |mut caller: Caller<'_, HostState>, n_ptr: i32, n_len: i32, buf_ptr: i32, buf_len: i32| -> i32 {
+            let (Some(host), Some(name)) = (caller.data().host, read_bytes(&caller, n_ptr, n_len)) else {
+                return 0;
+            };
+            let mut buf = vec![0u8; buf_len.max(0) as usize];
+            let full_len = (host.cvar_get_str)(name.as_ptr(), name.len(), buf.as_mut_ptr(), buf.len());
+            buf.truncate(full_len);
+            if !write_bytes(&mut caller, buf_ptr, &buf) {
+                return 0;
+            }
+            full_len as i32
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "cvar_set",
+        |caller: Caller<'_, HostState>, n_ptr: i32, n_len: i32, v_ptr: i32, v_len: i32| -> i32 {
+            let Some(host) = caller.data().host else { return 0 };
+            match (read_bytes(&caller, n_ptr, n_len), read_bytes(&caller, v_ptr, v_len)) {
+                (Some(name), Some(value)) => {
+                    (host.cvar_set)(name.as_ptr(), name.len(), value.as_ptr(), value.len()) as i32
+                }
+                _ => 0,
+            }
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "register_command",
+        |mut caller: Caller<'_, HostState>, n_ptr: i32, n_len: i32, d_ptr: i32, d_len: i32, id: i32| -> i32 {
+            let Some(host) = caller.data().host else { return 0 };
+            let Some(name) = read_bytes(&caller, n_ptr, n_len) else { return 0 };
+            let desc = read_bytes(&caller, d_ptr, d_len).unwrap_or_default();
+
+            let command = Box::new(WasmCommand {
+                plugin: caller.data().plugin,
+                id,
+            });
+            let user_data = &*command as *const WasmCommand as *mut c_void;
+            let ok = (host.register_command)(
+                name.as_ptr(),
+                name.len(),
+                desc.as_ptr(),
+                desc.len(),
+                wasm_command,
+                user_data,
+            );
+            if ok {
+                caller.data_mut().commands.push(command);
+            }
+            ok as i32
+        },
+    )?;
+
+    // Message bus.
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "bus_publish",
+        |caller: Caller<'_, HostState>, t_ptr: i32, t_len: i32, schema: i64, p_ptr: i32, p_len: i32| -> i32 {
+            let Some(host) = caller.data().host else { return 0 };
+            let (Some(topic), Some(payload)) = (read_bytes(&caller, t_ptr, t_len), read_bytes(&caller, p_ptr, p_len))
+            else {
+                return 0;
+            };
+            (host.bus_publish)(topic.as_ptr(), topic.len(), schema as u64, payload.as_ptr(), payload.len()) as i32
+        },
+    )?;
+    linker.func_wrap(IMPORT_MODULE, "bus_subscribe", |caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+        match (caller.data().host, read_bytes(&caller, ptr, len)) {
+            (Some(host), Some(topic)) => (host.bus_subscribe)(topic.as_ptr(), topic.len()) as i32,
+            _ => 0,
+        }
+    })?;
+    linker.func_wrap(IMPORT_MODULE, "bus_unsubscribe", |caller: Caller<'_, HostState>, ptr: i32, len: i32| -> i32 {
+        match (caller.data().host, read_bytes(&caller, ptr, len)) {
+            (Some(host), Some(topic)) => (host.bus_unsubscribe)(topic.as_ptr(), topic.len()) as i32,
+            _ => 0,
+        }
+    })?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "bus_receive",
+        |mut caller: Caller<'_, HostState>, header_ptr: i32, buf_ptr: i32, buf_len: i32| -> i32 {
+            let Some(host) = caller.data().host else { return 0 };
+            let mut header = BusMessageHeader::default();
+            let mut buf = vec![0u8; buf_len.max(0) as usize];
+            let copied = (host.bus_receive)(&mut header, buf.as_mut_ptr(), buf.len());
+            if !write_bytes(&mut caller, header_ptr, &wasm_abi::encode_bus_header(&header)) {
+                return 0;
+            }
+            if copied {
+                buf.truncate(header.topic_len + header.payload_len);
+                return write_bytes(&mut caller, buf_ptr, &buf) as i32;
+            }
+            0
+        },
+    )?;
+
+    // World access.
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "world_query",
+        |mut caller: Caller<'_, HostState>, component: i32, out_ptr: i32, out_cap: i32| -> i32 {
+            let (Some(world), Some(kind)) = (world(&caller), WasmComponent::from_u32(component as u32)) else {
+                return 0;
+            };
+            let ids = entities_with(world, kind);
+            let bytes: Vec<u8> = ids
+                .iter()
+                .take(out_cap.max(0) as usize)
+                .flat_map(|id| id.to_le_bytes())
+                .collect();
+            if !write_bytes(&mut caller, out_ptr, &bytes) {
+                return 0;
+            }
+            ids.len() as i32
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "world_get",
+        |mut caller: Caller<'_, HostState>, entity: i64, component: i32, out_ptr: i32| -> i32 {
+            let (Some(world), Some(kind)) = (world(&caller), WasmComponent::from_u32(component as u32)) else {
+                return 0;
+            };
+            let Some(floats) = read_component(world, Entity::from_bits(entity as u64), kind) else {
+                return 0;
+            };
+            let bytes: Vec<u8> = floats.iter().flat_map(|f| f.to_le_bytes()).collect();
+            write_bytes(&mut caller, out_ptr, &bytes) as i32
+        },
+    )?;
+    linker.func_wrap(
+        IMPORT_MODULE,
+        "world_set",
+        |caller: Caller<'_, HostState>, entity: i64, component: i32, data_ptr: i32| -> i32 {
+            let (Some(world), Some(kind)) = (world(&caller), WasmComponent::from_u32(component as u32)) else {
+                return 0;
+            };
+            let Some(bytes) = read_bytes(&caller, data_ptr, (kind.float_count() * 4) as i32) else {
+                return 0;
+            };
+            let floats: Vec<f32> = bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect();
+            write_component(world, Entity::from_bits(entity as u64), kind, &floats) as i32
+        },
+    )?;
+    linker.func_wrap(IMPORT_MODULE, "world_spawn", |caller: Caller<'_, HostState>| -> i64 {
+        match world(&caller) {
+            Some(world) => world.spawn().to_bits() as i64,
+            None => -1,
+        }
+    })?;
+    Ok(())
+}
+
+fn entities_with(world: &World, kind: WasmComponent) -> Vec<u64> {
+    fn ids<T: 'static>(world: &World) -> Vec<u64> {
+        world
+            .query::<T>()
+            .map(|set| set.iter().map(|(e, _)| e.to_bits()).collect())
+            .unwrap_or_default()
+    }
+    match kind {
+        WasmComponent::Transform => ids::<CTransform>(world),
+        WasmComponent::Player => ids::<CPlayer>(world),
+        WasmComponent::Enemy => ids::<CEnemy>(world),
+        WasmComponent::Sprite => ids::<CSprite>(world),
+        WasmComponent::Camera => ids::<CCamera>(world),
+        WasmComponent::WorldBounds => ids::<CWorldBounds>(world),
+    }
+}
+
+fn read_component(world: &World, entity: Entity, kind: WasmComponent) -> Option<Vec<f32>> {
+    Some(match kind {
+        WasmComponent::Transform => {
+            let t = world.get_component::<CTransform>(entity)?;
+            vec![t.pos.x, t.pos.y, t.scale.x, t.scale.y, t.rotation]
+        }
+        WasmComponent::Player => {
+            world.get_component::<CPlayer>(entity)?;
+            Vec::new()
+        }
+        WasmComponent::Enemy => vec![world.get_component::<CEnemy>(entity)?.speed],
+        WasmComponent::Sprite => world.get_component::<CSprite>(entity)?.color.to_array().to_vec(),
+        WasmComponent::Camera => {
+            let c = world.get_component::<CCamera>(entity)?;
+            vec![c.zoom, c.smoothness]
+        }
+        WasmComponent::WorldBounds => {
+            let b = world.get_component::<CWorldBounds>(entity)?;
+            vec![b.width, b.height]
+        }
+    })
+}
+
+/// Inserts or overwrites a component (`f` holds `kind.float_count()` values).
+fn write_component(world: &mut World, entity: Entity, kind: WasmComponent, f: &[f32]) -> bool {
+    fn insert<T: 'static>(world: &mut World, entity: Entity, component: T) -> bool {
+        match world.query_mut::<T>() {
+            Some(set) => {
+                set.insert(entity, component);
+                true
+            }
+            None => false,
+        }
+    }
+    match kind {
+        WasmComponent::Transform => insert(
+            world,
+            entity,
+            CTransform {
+                pos: Vec2::new(f[0], f[1]),
+                scale: Vec2::new(f[2], f[3]),
+                rotation: f[4],
+            },
+        ),
+        WasmComponent::Player => insert(world, entity, CPlayer),
+        WasmComponent::Enemy => insert(world, entity, CEnemy { speed: f[0] }),
+        WasmComponent::Sprite => insert(
+            world,
+            entity,
+            CSprite {
+                color: Vec4::new(f[0], f[1], f[2], f[3]),
+            },
+        ),
+        WasmComponent::Camera => insert(
+            world,
+            entity,
+            CCamera {
+                zoom: f[0],
+                smoothness: f[1],
+            },
+        ),
+        WasmComponent::WorldBounds => insert(
+            world,
+            entity,
+            CWorldBounds {
+                width: f[0],
+                height: f[1],
+            },
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin_manager::{PluginInstance, StateRestore};
+    use crate::plugin_manifest::PluginManifest;
+    use crate::{host, scene};
+
+    /// Moves every transform 1 unit right per tick and keeps a tick counter as its state.
+    fn mover_plugin() -> Vec<u8> {
+        let wat = format!(
+            r#"(module
+              (import "engine" "world_query" (func $world_query (param i32 i32 i32) (result i32)))
+              (import "engine" "world_get" (func $world_get (param i64 i32 i32) (result i32)))
+              (import "engine" "world_set" (func $world_set (param i64 i32 i32) (result i32)))
+              (memory (export "memory") 1)
+              (global $ticks (mut i32) (i32.const 0))
+
+              (func (export "engine_api_version") (result i32) (i32.const {ENGINE_API_VERSION}))
+              (func (export "plugin_alloc") (param i32) (result i32) (i32.const 1024))
+              (func (export "on_load") (result i32) (i32.const 0))
+              (func (export "on_update") (param $input i32) (param $dt f32) (result i32)
+                (local $n i32) (local $i i32) (local $e i64)
+                (local.set $n (call $world_query (i32.const 0) (i32.const 2048) (i32.const 16)))
+                (block $done
+                  (loop $next
+                    (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+                    (local.set $e (i64.load (i32.add (i32.const 2048) (i32.shl (local.get $i) (i32.const 3)))))
+                    (drop (call $world_get (local.get $e) (i32.const 0) (i32.const 4096)))
+                    (f32.store (i32.const 4096) (f32.add (f32.load (i32.const 4096)) (f32.const 1)))
+                    (drop (call $world_set (local.get $e) (i32.const 0) (i32.const 4096)))
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br $next)))
+                (global.set $ticks (i32.add (global.get $ticks) (i32.const 1)))
+                (i32.const 0))
+              (func (export "get_state_len") (result i32) (i32.const 4))
+              (func (export "save_state") (param $ptr i32) (param $len i32) (result i32)
+                (if (i32.lt_u (local.get $len) (i32.const 4)) (then (return (i32.const 1))))
+                (i32.store (local.get $ptr) (global.get $ticks))
+                (i32.const 0))
+              (func (export "load_state") (param $ptr i32) (param $len i32) (result i32)
+                (global.set $ticks (i32.load (local.get $ptr)))
+                (i32.const 0))
+              (func (export "get_schema_hash") (result i64) (i64.const 42))
+              (func (export "get_state_version") (result i32) (i32.const 1)))"#
+        );
+        wat::parse_str(wat).unwrap()
+    }
+
+    /// Passes lengths and pointers past its 1-page memory to the host; `on_load`
+    /// returns 1 if any of them is accepted.
+    fn out_of_bounds_plugin() -> Vec<u8> {
+        let not_found = ACTION_NOT_FOUND as i32;
+        let wat = format!(
+            r#"(module
+              (import "engine" "register_action" (func $register_action (param i32 i32) (result i32)))
+              (memory (export "memory") 1)
+
+              (func (export "engine_api_version") (result i32) (i32.const {ENGINE_API_VERSION}))
+              (func (export "plugin_alloc") (param i32) (result i32) (i32.const 1024))
+              (func (export "on_load") (result i32)
+                (if (i32.ne (call $register_action (i32.const 0) (i32.const 0x7fffffff)) (i32.const {not_found}))
+                  (then (return (i32.const 1))))
+                (if (i32.ne (call $register_action (i32.const 65530) (i32.const 10)) (i32.const {not_found}))
+                  (then (return (i32.const 1))))
+                (if (i32.ne (call $register_action (i32.const -1) (i32.const 2)) (i32.const {not_found}))
+                  (then (return (i32.const 1))))
+                (i32.const 0))
+              (func (export "on_update") (param i32 f32) (result i32) (i32.const 0))
+              (func (export "get_state_len") (result i32) (i32.const 0x7fffffff))
+              (func (export "save_state") (param i32 i32) (result i32) (i32.const 0))
+              (func (export "load_state") (param i32 i32) (result i32) (i32.const 0))
+              (func (export "get_schema_hash") (result i64) (i64.const 0))
+              (func (export "get_state_version") (result i32) (i32.const 1)))"#
+        );
+        wat::parse_str(wat).unwrap()
+    }
+
+    #[test]
+    fn lengths_past_guest_memory_are_refused() {
+        let mut world = World::new();
+        let mut plugin = WasmPlugin::from_bytes(&out_of_bounds_plugin()).unwrap();
+        assert_eq!(plugin.on_load(&mut world, &host::create_interface()), FFIResult::Success);
+        assert_eq!(plugin.get_state_len(), 0);
+    }
+
+    #[test]
+    fn wasm_plugin_drives_world_and_round_trips_state() {
+        let mut world = World::new();
+        scene::setup_default_world(&mut world);
+        let entity = world.spawn();
+        world.add_component(entity, CTransform::default());
+
+        let mut plugin = WasmPlugin::from_bytes(&mover_plugin()).unwrap();
+        assert_eq!(plugin.on_load(&mut world, &host::create_interface()), FFIResult::Success);
+        for _ in 0..2 {
+            assert_eq!(plugin.on_update(&mut world, &InputState::default(), 0.016), FFIResult::Success);
+        }
+        assert_eq!(world.get_component::<CTransform>(entity).unwrap().pos.x, 2.0);
+        assert_eq!((plugin.schema_hash(), plugin.state_version()), (42, 1));
+
+        let header_size = std::mem::size_of::<StateEnvelope>();
+        let mut state = vec![0u8; plugin.get_state_len()];
+        assert_eq!(plugin.save_state(&mut state), FFIResult::Success);
+        assert_eq!(state[header_size..], 2i32.to_le_bytes());
+
+        state[header_size..].copy_from_slice(&7i32.to_le_bytes());
+        assert_eq!(plugin.load_state(&mut state), FFIResult::Success);
+        state.fill(0);
+        assert_eq!(plugin.save_state(&mut state), FFIResult::Success);
+        assert_eq!(state[header_size..], 7i32.to_le_bytes());
+        // Bare module bytes are refused rather than read as an envelope.
+        assert_eq!(plugin.load_state(&mut 7i32.to_le_bytes()), FFIResult::Error);
+    }
+
+    #[test]
+    fn wasm_state_survives_hot_reload() {
+        let dir = std::env::temp_dir().join(format!("plugin_wasm_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let library = dir.join("mover.wasm");
+        fs::write(&library, mover_plugin()).unwrap();
+
+        let mut world = World::new();
+        scene::setup_default_world(&mut world);
+        let entity = world.spawn();
+        world.add_component(entity, CTransform::default());
+        let host_interface = host::create_interface();
+
+        let mut instance = PluginInstance::new(PluginManifest::for_library(&library));
+        instance.scoped(|p| {
+            p.initial_load(&mut world, &host_interface);
+            for _ in 0..2 {
+                p.update(&mut world, &InputState::default(), 0.016);
+            }
+            assert!(p.try_hot_reload(&mut world, &host_interface));
+        });
+        let reload = instance.reload_history.back().unwrap();
+        assert_eq!(reload.outcome, Ok(StateRestore::Restored));
+
+        let plugin = instance.plugin.as_deref_mut().unwrap();
+        let mut state = vec![0u8; plugin.get_state_len()];
+        assert_eq!(plugin.save_state(&mut state), FFIResult::Success);
+        assert_eq!(state[std::mem::size_of::<StateEnvelope>()..], 2i32.to_le_bytes());
+
+        instance.scoped(|p| p.unload(&mut world));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
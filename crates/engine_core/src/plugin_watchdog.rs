@@ -0,0 +1,146 @@
+// crates/engine_core/src/plugin_watchdog.rs
+//! Update time budgets, and the worker thread that runs guarded plugins.
+//!
+//! Every plugin's `on_update` is timed against its manifest's `update_budget_ms`;
+//! a plugin that stays over budget gets a warning in the console. A `guarded`
+//! plugin updates on its own worker thread instead, and the main thread waits at
+//! most `update_timeout_ms` for it. A plugin that misses that deadline (e.g. an
+//! infinite loop) is abandoned to its thread, which keeps the plugin and the copy
+//! of the World it was given; the editor carries on from the pre-tick World.
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use engine_ecs::World;
+use engine_shared::input_types::InputState;
+use engine_shared::plugin_api::FFIResult;
+
+use crate::message_bus;
+use crate::plugin_manager::PluginBackend;
+
+/// Consecutive over-budget ticks before the first warning.
+const WARN_STREAK: u32 = 30;
+/// While the streak lasts, warn again every this many ticks.
+const WARN_REPEAT: u32 = 300;
+
+/// Per-plugin `on_update` timings against its budget.
+#[derive(Debug, Clone)]
+pub struct UpdateBudget {
+    pub budget: Duration,
+    pub last: Duration,
+    /// Slowest tick since load.
+    pub worst: Duration,
+    /// Ticks over budget since load.
+    pub overruns: u64,
+    streak: u32,
+}
+
+impl UpdateBudget {
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            last: Duration::ZERO,
+            worst: Duration::ZERO,
+            overruns: 0,
+            streak: 0,
+        }
+    }
+
+    /// Records one tick. Returns the current streak of over-budget ticks when a
+    /// warning is due (a single slow tick, e.g. a hitch, never warns).
+    pub fn record(&mut self, elapsed: Duration) -> Option<u32> {
+        self.last = elapsed;
+        self.worst = self.worst.max(elapsed);
+
+        if elapsed <= self.budget {
+            self.streak = 0;
+            return None;
+        }
+        self.overruns += 1;
+        self.streak += 1;
+
+        let due = self.streak == WARN_STREAK
+            || (self.streak > WARN_STREAK && (self.streak - WARN_STREAK).is_multiple_of(WARN_REPEAT));
+        due.then_some(self.streak)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.streak > 0
+    }
+}
+
+/// Carries the plugin and the tick's World across threads. Only one side
+/// touches them at a time: the caller gives them up until the worker replies,
+/// and never gets them back if it doesn't.
+struct Handoff<T>(T);
+unsafe impl<T> Send for Handoff<T> {}
+
+type Job = Handoff<(Box<dyn PluginBackend>, World, InputState, f32)>;
+type Done = Handoff<(Box<dyn PluginBackend>, World, FFIResult)>;
+
+/// Dedicated thread running one guarded plugin's `on_update`.
+pub struct GuardedWorker {
+    jobs: Sender<Job>,
+    done: Receiver<Done>,
+}
+
+impl GuardedWorker {
+    pub fn spawn(plugin_name: &str) -> std::io::Result<Self> {
+        let (jobs, job_rx) = mpsc::channel::<Job>();
+        let (done_tx, done) = mpsc::channel::<Done>();
+        let name = plugin_name.to_string();
+
+        thread::Builder::new()
+            .name(format!("plugin-{plugin_name}"))
+            .spawn(move || {
+                while let Ok(Handoff((mut plugin, mut world, input, dt))) = job_rx.recv() {
+                    let result = message_bus::as_plugin(&name, || plugin.on_update(&mut world, &input, dt));
+                    // The host gave up on this call: drop everything with the thread.
+                    if done_tx.send(Handoff((plugin, world, result))).is_err() {
+                        break;
+                    }
+                }
+            })?;
+
+        Ok(Self { jobs, done })
+    }
+
+    /// Runs `on_update` on the worker. `None` if it didn't return within
+    /// `timeout`; the plugin and `world` then stay with the (stuck) worker.
+    pub fn run(
+        &self,
+        plugin: Box<dyn PluginBackend>,
+        world: World,
+        input: &InputState,
+        dt: f32,
+        timeout: Duration,
+    ) -> Option<(Box<dyn PluginBackend>, World, FFIResult)> {
+        self.jobs.send(Handoff((plugin, world, *input, dt))).ok()?;
+        match self.done.recv_timeout(timeout) {
+            Ok(Handoff(done)) => Some(done),
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_warns_only_on_sustained_overruns() {
+        let mut budget = UpdateBudget::new(Duration::from_millis(4));
+        let slow = Duration::from_millis(5);
+
+        assert_eq!(budget.record(slow), None);
+        assert_eq!(budget.record(Duration::from_millis(1)), None);
+
+        let warnings: Vec<u32> = (0..WARN_STREAK + WARN_REPEAT)
+            .filter_map(|_| budget.record(slow))
+            .collect();
+        assert_eq!(warnings, [WARN_STREAK, WARN_STREAK + WARN_REPEAT]);
+        assert_eq!(budget.overruns, 1 + (WARN_STREAK + WARN_REPEAT) as u64);
+        assert_eq!(budget.worst, slow);
+    }
+}
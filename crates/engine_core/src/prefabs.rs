@@ -0,0 +1,34 @@
+// crates/engine_core/src/prefabs.rs
+//! Named entity templates that plugins and scripts can spawn by name.
+
+use engine_ecs::{Entity, World};
+use engine_shared::{CEnemy, CSprite, CTransform};
+use glam::{Vec2, Vec4};
+
+/// Names accepted by `spawn`.
+pub const PREFABS: &[&str] = &["enemy", "marker"];
+
+/// Spawns prefab `name` at `pos`. `None` if there is no such prefab.
+pub fn spawn(world: &mut World, name: &str, pos: Vec2) -> Option<Entity> {
+    let (scale, color) = match name {
+        "enemy" => (Vec2::splat(0.8), Vec4::new(1.0, 0.0, 0.0, 1.0)),
+        // Static visual cue for designers (waypoints, spawn points).
+        "marker" => (Vec2::splat(0.3), Vec4::new(1.0, 1.0, 0.0, 1.0)),
+        _ => return None,
+    };
+
+    let entity = world.spawn();
+    world.add_component(
+        entity,
+        CTransform {
+            pos,
+            scale,
+            rotation: 0.0,
+        },
+    );
+    world.add_component(entity, CSprite { color });
+    if name == "enemy" {
+        world.add_component(entity, CEnemy { speed: 100.0 });
+    }
+    Some(entity)
+}
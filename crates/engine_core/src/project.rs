@@ -0,0 +1,403 @@
+// crates/engine_core/src/project.rs
+//! Project manifest (`engine.toml`): everything the editor needs at startup.
+//!
+//! ```toml
+//! [plugins]
+//! # Bare names are platform library names: "game_plugin" loads
+//! # libgame_plugin.so / libgame_plugin.dylib / game_plugin.dll from `search_dirs`.
+//! load = ["game_plugin", "assets/scripts/markers.rhai"]
+//! search_dirs = ["target/debug"]
+//!
+//! [window]
+//! title = "My Game"
+//! width = 1280
+//! height = 720
+//!
+//! [simulation]
+//! rate = 60      # fixed steps per second
+//!
+//! [input]
+//! profile = "input.toml"
+//!
+//! [assets]
+//! roots = ["assets"]
+//!
+//! [replay]
+//! record = "last_session.replay"   # or: play = "bug_1234.replay"
+//! ```
+//!
+//! Every section is optional. Relative paths are resolved against the
+//! manifest's directory; command-line overrides (see `USAGE`) against the
+//! working directory.
+
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Manifest picked up from the working directory when `--project` isn't given.
+pub const DEFAULT_PROJECT_FILE: &str = "engine.toml";
+
+pub const USAGE: &str = "\
+Usage: editor [OPTIONS] [PLUGIN]...
+
+PLUGIN is a library (path or bare crate name), a plugin .toml manifest,
+a directory of manifests, a .wasm module or a .rhai script. Any PLUGIN
+given replaces the project's [plugins] load list.
+
+Options:
+  --project <FILE>        Project manifest (default: ./engine.toml if present)
+  --title <TEXT>          Window title
+  --width <PX>            Window width
+  --height <PX>           Window height
+  --sim-rate <HZ>         Fixed simulation steps per second
+  --input-profile <FILE>  Key binding profile
+  --plugin-dir <DIR>      Extra library search directory (searched first)
+  --asset-root <DIR>      Extra asset root (searched first)
+  --record <FILE>         Record every tick's input to a replay file
+  --replay <FILE>         Play a replay file back instead of live input
+  -h, --help              Show this message";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ProjectManifest {
+    pub plugins: PluginsSection,
+    pub window: WindowSection,
+    pub simulation: SimulationSection,
+    pub input: InputSection,
+    pub assets: AssetsSection,
+    pub replay: ReplaySection,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PluginsSection {
+    /// Plugin sources, in the forms `PluginManager::new` accepts plus bare library names.
+    pub load: Vec<String>,
+    /// Where bare library names are looked up, in order.
+    pub search_dirs: Vec<PathBuf>,
+}
+
+impl Default for PluginsSection {
+    fn default() -> Self {
+        Self {
+            load: vec!["game_plugin".to_string()],
+            search_dirs: vec![PathBuf::from("target/debug")],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WindowSection {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for WindowSection {
+    fn default() -> Self {
+        Self {
+            title: "Rust Engine: Modular Architecture".to_string(),
+            width: 1280,
+            height: 720,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SimulationSection {
+    /// Fixed simulation steps per second.
+    pub rate: f32,
+}
+
+impl Default for SimulationSection {
+    fn default() -> Self {
+        Self { rate: 60.0 }
+    }
+}
+
+impl SimulationSection {
+    pub fn dt(&self) -> f32 {
+        1.0 / self.rate
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct InputSection {
+    /// Key binding profile applied on top of the engine defaults.
+    pub profile: Option<PathBuf>,
+}
+
+/// See `replay`. At most one of the two may be set.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ReplaySection {
+    /// Replay file written on exit.
+    pub record: Option<PathBuf>,
+    /// Replay file whose input drives the simulation.
+    pub play: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AssetsSection {
+    /// Directories searched, in order, for relative asset paths.
+    pub roots: Vec<PathBuf>,
+}
+
+impl Default for AssetsSection {
+    fn default() -> Self {
+        Self {
+            roots: vec![PathBuf::from("assets")],
+        }
+    }
+}
+
+impl ProjectManifest {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read project {}: {e}", path.display()))?;
+        let mut project: ProjectManifest = toml::from_str(&text)
+            .map_err(|e| format!("Invalid project {}: {e}", path.display()))?;
+
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            project.rebase(dir);
+        }
+        project
+            .validate()
+            .map_err(|e| format!("Invalid project {}: {e}", path.display()))?;
+        Ok(project)
+    }
+
+    /// Loads the project named by `--project` (or `./engine.toml`, or the
+    /// defaults if there is none) and applies the remaining arguments on top.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let args: Vec<String> = args.into_iter().collect();
+
+        let explicit = args
+            .iter()
+            .position(|a| a == "--project")
+            .map(|i| args.get(i + 1).ok_or("--project needs a value"))
+            .transpose()?;
+
+        let mut project = match explicit {
+            Some(file) => Self::from_file(Path::new(file))?,
+            None if Path::new(DEFAULT_PROJECT_FILE).is_file() => {
+                Self::from_file(Path::new(DEFAULT_PROJECT_FILE))?
+            }
+            None => Self::default(),
+        };
+        project.apply_args(&args)?;
+        Ok(project)
+    }
+
+    /// Applies command-line overrides (`--project` itself is skipped).
+    pub fn apply_args<S: AsRef<str>>(&mut self, args: &[S]) -> Result<(), String> {
+        let mut plugins = Vec::new();
+        let mut args = args.iter().map(|a| a.as_ref());
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                plugins.push(arg.to_string());
+                continue;
+            }
+            let value = args.next().ok_or_else(|| format!("{arg} needs a value"))?;
+            let number = |what: &str| {
+                value
+                    .parse::<f64>()
+                    .map_err(|_| format!("{arg}: '{value}' is not a {what}"))
+            };
+
+            match arg {
+                "--project" => {}
+                "--title" => self.window.title = value.to_string(),
+                "--width" => self.window.width = number("size")? as u32,
+                "--height" => self.window.height = number("size")? as u32,
+                "--sim-rate" => self.simulation.rate = number("rate")? as f32,
+                "--input-profile" => self.input.profile = Some(PathBuf::from(value)),
+                "--plugin-dir" => self.plugins.search_dirs.insert(0, PathBuf::from(value)),
+                "--asset-root" => self.assets.roots.insert(0, PathBuf::from(value)),
+                // Either flag replaces whatever replay mode the manifest asked for.
+                "--record" => {
+                    self.replay.record = Some(PathBuf::from(value));
+                    self.replay.play = None;
+                }
+                "--replay" => {
+                    self.replay.play = Some(PathBuf::from(value));
+                    self.replay.record = None;
+                }
+                _ => return Err(format!("Unknown option '{arg}'\n\n{USAGE}")),
+            }
+        }
+
+        if !plugins.is_empty() {
+            self.plugins.load = plugins;
+        }
+        self.validate()
+    }
+
+    /// The `[plugins] load` list resolved to paths for `PluginManager::new`.
+    pub fn plugin_sources(&self) -> Vec<String> {
+        self.plugins
+            .load
+            .iter()
+            .map(|entry| self.resolve_plugin(entry).to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// First asset root containing `relative`, if any.
+    pub fn find_asset(&self, relative: &Path) -> Option<PathBuf> {
+        self.assets
+            .roots
+            .iter()
+            .map(|root| root.join(relative))
+            .find(|path| path.exists())
+    }
+
+    /// An existing path wins; otherwise library names are matched against the
+    /// search dirs in this platform's naming, and anything else against the asset
+    /// roots. Unresolved entries come back unchanged so loading reports them.
+    fn resolve_plugin(&self, entry: &str) -> PathBuf {
+        let path = Path::new(entry);
+        if path.exists() {
+            return path.to_path_buf();
+        }
+
+        if is_library_name(path) {
+            let platform = platform_library(path);
+            if path.parent().is_some_and(|p| !p.as_os_str().is_empty()) {
+                return platform;
+            }
+            return self
+                .plugins
+                .search_dirs
+                .iter()
+                .map(|dir| dir.join(&platform))
+                .find(|p| p.is_file())
+                .unwrap_or(platform);
+        }
+
+        self.find_asset(path).unwrap_or_else(|| path.to_path_buf())
+    }
+
+    fn rebase(&mut self, dir: &Path) {
+        let rebase = |p: &mut PathBuf| {
+            if p.is_relative() {
+                *p = dir.join(&*p);
+            }
+        };
+
+        self.plugins.search_dirs.iter_mut().for_each(rebase);
+        self.assets.roots.iter_mut().for_each(rebase);
+        self.input.profile.iter_mut().for_each(rebase);
+        self.replay.record.iter_mut().for_each(rebase);
+        self.replay.play.iter_mut().for_each(rebase);
+        for entry in &mut self.plugins.load {
+            // Bare library names stay bare: they are looked up in `search_dirs`.
+            let path = Path::new(entry.as_str());
+            if path.is_relative() && !(is_library_name(path) && path.parent() == Some(Path::new(""))) {
+                *entry = dir.join(path).to_string_lossy().into_owned();
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !(self.simulation.rate.is_finite() && self.simulation.rate > 0.0) {
+            return Err(format!("simulation rate must be positive, got {}", self.simulation.rate));
+        }
+        if self.window.width == 0 || self.window.height == 0 {
+            return Err(format!(
+                "window size must be non-zero, got {}x{}",
+                self.window.width, self.window.height
+            ));
+        }
+        if self.replay.record.is_some() && self.replay.play.is_some() {
+            return Err("cannot record and play a replay at the same time".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// `name` with this platform's library prefix and suffix (`libname.so`, `name.dll`, ...).
+pub fn library_file_name(name: &str) -> String {
+    format!("{DLL_PREFIX}{name}{DLL_SUFFIX}")
+}
+
+/// Rewrites a library path written for any platform to this platform's naming,
+/// keeping the directory: `target/debug/game_plugin.dll` → `target/debug/libgame_plugin.so`.
+pub fn platform_library(path: &Path) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let ext = path.extension().and_then(|e| e.to_str());
+    let name = match ext {
+        Some("so" | "dylib") => stem.strip_prefix("lib").unwrap_or(stem),
+        _ => stem,
+    };
+    path.with_file_name(library_file_name(name))
+}
+
+/// A native library path, or a bare name with no extension that stands for one.
+pub(crate) fn is_library_name(path: &Path) -> bool {
+    match path.extension().and_then(|e| e.to_str()) {
+        None => true,
+        Some(ext) => matches!(ext, "so" | "dylib" | "dll"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn library_names_follow_the_platform() {
+        let expected = Path::new("target/debug").join(library_file_name("game_plugin"));
+        for written in ["game_plugin.dll", "libgame_plugin.so", "libgame_plugin.dylib", "game_plugin"] {
+            assert_eq!(platform_library(&Path::new("target/debug").join(written)), expected);
+        }
+
+        let project = ProjectManifest::default();
+        assert_eq!(project.resolve_plugin("game_plugin"), PathBuf::from(library_file_name("game_plugin")));
+    }
+
+    #[test]
+    fn command_line_overrides_the_manifest() {
+        let mut project: ProjectManifest = toml::from_str(
+            "[window]\ntitle = \"Game\"\n[simulation]\nrate = 30\n[plugins]\nload = [\"a\", \"b\"]",
+        )
+        .unwrap();
+        assert_eq!(project.window.width, 1280);
+
+        project
+            .apply_args(&["--width", "800", "--sim-rate", "120", "--plugin-dir", "out", "c.rhai"])
+            .unwrap();
+        assert_eq!(project.window.title, "Game");
+        assert_eq!(project.window.width, 800);
+        assert_eq!(project.simulation.dt(), 1.0 / 120.0);
+        assert_eq!(project.plugins.load, ["c.rhai"]);
+        assert_eq!(project.plugins.search_dirs[0], PathBuf::from("out"));
+
+        assert!(project.apply_args(&["--sim-rate", "0"]).is_err());
+        assert!(project.apply_args(&["--bogus", "1"]).is_err());
+        assert!(project.apply_args(&["--title"]).is_err());
+    }
+
+    #[test]
+    fn replay_flags_replace_the_manifest_replay_mode() {
+        let mut project: ProjectManifest = toml::from_str("[replay]\nrecord = \"last.replay\"").unwrap();
+        project.apply_args(&["--replay", "bug.replay"]).unwrap();
+        assert_eq!(project.replay.play, Some(PathBuf::from("bug.replay")));
+        assert_eq!(project.replay.record, None);
+
+        project.apply_args(&["--record", "new.replay"]).unwrap();
+        assert_eq!(project.replay.record, Some(PathBuf::from("new.replay")));
+        assert_eq!(project.replay.play, None);
+
+        // Only a manifest can still ask for both.
+        let both: ProjectManifest =
+            toml::from_str("[replay]\nrecord = \"a.replay\"\nplay = \"b.replay\"").unwrap();
+        assert!(both.validate().is_err());
+    }
+}
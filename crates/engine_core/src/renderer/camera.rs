@@ -0,0 +1,80 @@
+// crates/engine_core/src/renderer/camera.rs
+//! The 2D camera transform, shared by `SpritePass` (drawing) and the input
+//! poller (cursor → world), so a click lands on what is drawn under it.
+
+use engine_ecs::World;
+use engine_shared::{CCamera, CTransform};
+use glam::{Mat4, Vec2, Vec3};
+
+/// The projection always spans this logical resolution, whatever the window
+/// size, so the game's "invisible wall" matches the edge of the screen exactly.
+pub const LOGICAL_WIDTH: f32 = 1280.0;
+pub const LOGICAL_HEIGHT: f32 = 720.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraView {
+    pub pos: Vec2,
+    pub zoom: f32,
+}
+
+impl Default for CameraView {
+    fn default() -> Self {
+        Self { pos: Vec2::ZERO, zoom: 1.0 }
+    }
+}
+
+impl CameraView {
+    /// The first `CCamera` with a transform, or the origin at zoom 1.
+    pub fn from_world(world: &World) -> Self {
+        let (Some(cameras), Some(transforms)) = (world.query::<CCamera>(), world.query::<CTransform>()) else {
+            return Self::default();
+        };
+        cameras
+            .iter()
+            .find_map(|(entity, camera)| {
+                let transform = transforms.get(*entity)?;
+                Some(Self { pos: transform.pos, zoom: camera.zoom })
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn view_proj(&self) -> Mat4 {
+        let half_w = (LOGICAL_WIDTH / 2.0) / self.zoom;
+        let half_h = (LOGICAL_HEIGHT / 2.0) / self.zoom;
+
+        let projection = Mat4::orthographic_rh(-half_w, half_w, -half_h, half_h, -100.0, 100.0);
+        let view = Mat4::from_translation(-Vec3::new(self.pos.x, self.pos.y, 0.0));
+        projection * view
+    }
+
+    /// Window pixel (origin top-left, +Y down) to world position, for a window of `window_size` pixels.
+    pub fn screen_to_world(&self, screen: Vec2, window_size: Vec2) -> Vec2 {
+        if window_size.x <= 0.0 || window_size.y <= 0.0 {
+            return self.pos;
+        }
+        let ndc = Vec3::new(
+            screen.x / window_size.x * 2.0 - 1.0,
+            1.0 - screen.y / window_size.y * 2.0,
+            0.0,
+        );
+        self.view_proj().inverse().project_point3(ndc).truncate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_maps_through_the_sprite_projection() {
+        let camera = CameraView { pos: Vec2::new(100.0, -50.0), zoom: 2.0 };
+        let window = Vec2::new(1920.0, 1080.0);
+
+        let center = camera.screen_to_world(window / 2.0, window);
+        assert!(center.distance(camera.pos) < 1e-3);
+
+        // Top-left corner: half the logical view (halved again by the zoom) up and left.
+        let corner = camera.screen_to_world(Vec2::ZERO, window);
+        assert!(corner.distance(Vec2::new(100.0 - 320.0, -50.0 + 180.0)) < 1e-3);
+    }
+}
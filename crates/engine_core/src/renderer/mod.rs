@@ -1,5 +1,6 @@
 // crates/engine_core/src/renderer/mod.rs
 
+pub mod camera;
 pub mod context;
 pub mod types;
 pub mod sprite_pass;
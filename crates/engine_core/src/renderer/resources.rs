@@ -1,8 +1,6 @@
 // crates/engine_core/src/renderer/resources.rs
 use std::num::NonZeroU64;
 
-use wgpu;
-
 use crate::renderer::types::CameraUniform;
 
 /// Centralized GPU resource definitions shared across passes.
@@ -3,9 +3,10 @@
 use std::num::NonZeroU64;
 use wgpu::util::{DeviceExt, StagingBelt};
 use engine_ecs::World;
-use engine_shared::{CTransform, CSprite, CCamera};
+use engine_shared::{CTransform, CSprite};
 use glam::{Mat4, Vec3};
 
+use super::camera::CameraView;
 use super::context::GraphicsContext;
 use super::resources::RenderResources;
 use super::types::{CameraUniform, InstanceRaw};
@@ -96,34 +97,10 @@ impl SpritePass {
         // [AUDIO FIX] Recycle memory (Stops "Sticky Fluid" memory leak)
         self.staging_belt.recall();
 
-        // [VISUAL FIX] FORCE LOGICAL RESOLUTION
-        // We ignore the physical window size (ctx.config.width) and force 
-        // the camera projection to match the Game Logic (1280x720).
-        // This ensures the "Invisible Wall" matches the edge of the screen exactly.
-        let width = 1280.0;
-        let height = 720.0;
-
         // --- CAMERA UPDATE ---
-        let mut view_pos = Vec3::ZERO;
-        let mut zoom = 1.0;
-
-        if let (Some(cameras), Some(transforms)) = (world.query::<CCamera>(), world.query::<CTransform>()) {
-            for (entity, cam_data) in cameras.iter() {
-                if let Some(transform) = transforms.get(*entity) {
-                    view_pos = Vec3::new(transform.pos.x, transform.pos.y, 0.0);
-                    zoom = cam_data.zoom;
-                    break;
-                }
-            }
-        }
-
-        let half_w = (width / 2.0) / zoom;
-        let half_h = (height / 2.0) / zoom;
-
-        let projection = Mat4::orthographic_rh(-half_w, half_w, -half_h, half_h, -100.0, 100.0);
-        let view_matrix = Mat4::from_translation(-view_pos);
+        // Fixed logical resolution, see `camera::LOGICAL_WIDTH`.
         let camera_data = CameraUniform {
-            view_proj: (projection * view_matrix).to_cols_array_2d(),
+            view_proj: CameraView::from_world(world).view_proj().to_cols_array_2d(),
         };
 
         ctx.queue.write_buffer(&self.camera_buffer, 0, bytemuck::cast_slice(&[camera_data]));
@@ -0,0 +1,232 @@
+// crates/engine_core/src/replay.rs
+//! Input recording and deterministic replay (`--record <FILE>`, `--replay <FILE>`).
+//!
+//! A recording starts with the World and every plugin's saved state, plus a
+//! seed, then holds one `FrameInputState` per simulation tick. Replaying
+//! restores that starting point and hands the plugins the recorded frames
+//! instead of the Arbiter's output, one per tick, until they run out.
+//!
+//! While recording, plugins also get each frame back as stored (movement at
+//! 1/1000 precision), so both runs see identical input. Console variables and
+//! bus messages in flight are not part of the recording.
+//!
+//! The seed is published on `SEED_TOPIC` when recording or playback starts, so
+//! plugins that subscribe to it can seed their RNG the same way in both runs.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use engine_ecs::World;
+use engine_shared::input_types::{FrameInputState, InputState};
+use engine_shared::plugin_api::HostInterface;
+use serde::{Deserialize, Serialize};
+
+use crate::message_bus;
+use crate::plugin_manager::PluginManager;
+use crate::world_snapshot::WorldSnapshot;
+
+/// Bumped whenever the file layout changes; older files are refused.
+pub const REPLAY_VERSION: u32 = 4;
+
+/// Bus topic carrying the seed (`u64`, little-endian) at the start of a recording or replay.
+pub const SEED_TOPIC: &str = "replay.seed";
+pub const SEED_SCHEMA: u64 = 0x5EED_0001;
+
+/// Sends `seed` to the plugins subscribed to `SEED_TOPIC`; they get it on the next tick.
+pub fn publish_seed(seed: u64) {
+    message_bus::publish("host", SEED_TOPIC, SEED_SCHEMA, &seed.to_le_bytes());
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    /// Simulation step the frames were recorded at.
+    pub sim_dt: f32,
+    pub world: WorldSnapshot,
+    /// `(plugin name, saved state)` of every plugin running when recording began.
+    pub plugin_states: Vec<(String, Vec<u8>)>,
+    pub frames: Vec<FrameInputState>,
+}
+
+impl Replay {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|e| format!("Cannot read replay {}: {e}", path.display()))?;
+        let replay: Self =
+            bincode::deserialize(&bytes).map_err(|e| format!("Invalid replay {}: {e}", path.display()))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(format!(
+                "Replay {} has version {} (expected {REPLAY_VERSION})",
+                path.display(),
+                replay.version
+            ));
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let bytes = bincode::serialize(self).map_err(|e| format!("Cannot serialize replay: {e}"))?;
+        fs::write(path, bytes).map_err(|e| format!("Cannot write replay {}: {e}", path.display()))
+    }
+}
+
+/// Collects one frame per simulation tick; the file is written by `finish`.
+pub struct Recorder {
+    path: PathBuf,
+    replay: Replay,
+}
+
+impl Recorder {
+    /// Snapshots the World and plugin states as the recording's starting point.
+    pub fn start(path: PathBuf, sim_dt: f32, world: &World, plugin_manager: &mut PluginManager) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        tracing::info!("⏺️ Recording input to {} (seed {seed})", path.display());
+
+        Self {
+            path,
+            replay: Replay {
+                version: REPLAY_VERSION,
+                seed,
+                sim_dt,
+                world: WorldSnapshot::capture(world),
+                plugin_states: plugin_manager.save_states(),
+                frames: Vec::new(),
+            },
+        }
+    }
+
+    /// Stores this tick's input and returns it as the plugins must see it.
+    pub fn record(&mut self, tick: u64, state: &InputState) -> InputState {
+        let frame = FrameInputState::from_state(tick, self.replay.seed, state);
+        self.replay.frames.push(frame);
+        frame.to_state()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    /// Frames recorded so far.
+    pub fn frames(&self) -> &[FrameInputState] {
+        &self.replay.frames
+    }
+
+    pub fn finish(&self) -> Result<(), String> {
+        self.replay.save(&self.path)?;
+        tracing::info!(
+            "💾 Recorded {} ticks to {}",
+            self.replay.frames.len(),
+            self.path.display()
+        );
+        Ok(())
+    }
+}
+
+/// Hands a recording's frames back, one per tick.
+pub struct Player {
+    replay: Replay,
+    next: usize,
+}
+
+impl Player {
+    pub fn new(replay: Replay) -> Self {
+        Self { replay, next: 0 }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let replay = Replay::from_file(path)?;
+        tracing::info!(
+            "▶️ Replaying {} ticks from {} (seed {})",
+            replay.frames.len(),
+            path.display(),
+            replay.seed
+        );
+        Ok(Self::new(replay))
+    }
+
+    pub fn sim_dt(&self) -> f32 {
+        self.replay.sim_dt
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    /// Puts the World and plugins back where the recording started.
+    pub fn restore(
+        &self,
+        world: &mut World,
+        host_interface: &HostInterface,
+        plugin_manager: &mut PluginManager,
+    ) {
+        self.replay.world.apply(world);
+        plugin_manager.restore_states(world, host_interface, &self.replay.plugin_states);
+    }
+
+    /// The next tick's input, or `None` once the recording is used up.
+    pub fn next_input(&mut self) -> Option<InputState> {
+        let frame = self.replay.frames.get(self.next)?;
+        self.next += 1;
+        Some(frame.to_state())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_bus::BusMessage;
+    use engine_shared::input_types::axes;
+
+    #[test]
+    fn recorded_input_replays_exactly_as_the_plugins_saw_it() {
+        let mut live = InputState {
+            digital_mask: [1, 3, 200].into_iter().collect(),
+            ..Default::default()
+        };
+        live.analog_axes[axes::MOVE_X] = 0.70710677;
+        live.analog_axes[axes::CURSOR_WORLD_X] = -123.25;
+        live.analog_axes[axes::SCROLL] = 2.0;
+
+        let mut recorder = Recorder {
+            path: PathBuf::new(),
+            replay: Replay {
+                version: REPLAY_VERSION,
+                seed: 7,
+                sim_dt: 1.0 / 60.0,
+                world: WorldSnapshot::default(),
+                plugin_states: vec![("game".to_string(), vec![1, 2, 3])],
+                frames: Vec::new(),
+            },
+        };
+        let seen: Vec<InputState> = (0..3).map(|tick| recorder.record(tick, &live)).collect();
+        assert_eq!(seen[0].analog_axes[axes::MOVE_X], 0.707);
+        assert_eq!(seen[0].cursor_world().x, -123.25);
+
+        let bytes = bincode::serialize(&recorder.replay).unwrap();
+        let mut player = Player::new(bincode::deserialize(&bytes).unwrap());
+        for expected in &seen {
+            let replayed = player.next_input().unwrap();
+            assert_eq!(replayed.digital_mask, expected.digital_mask);
+            assert_eq!(replayed.analog_axes, expected.analog_axes);
+        }
+        assert!(player.next_input().is_none());
+        assert_eq!(player.replay.frames[2].tick, 2);
+        assert_eq!(player.replay.plugin_states[0].1, [1, 2, 3]);
+    }
+
+    #[test]
+    fn seed_reaches_subscribed_plugins() {
+        message_bus::subscribe("replay_test", SEED_TOPIC);
+        publish_seed(0xDEAD_BEEF);
+        message_bus::advance();
+
+        let seeds: Vec<BusMessage> = message_bus::take_inbox("replay_test");
+        message_bus::remove_subscriber("replay_test");
+        assert_eq!(seeds.len(), 1);
+        assert_eq!((seeds[0].schema, seeds[0].sender.as_str()), (SEED_SCHEMA, "host"));
+        assert_eq!(seeds[0].payload, 0xDEAD_BEEFu64.to_le_bytes());
+    }
+}
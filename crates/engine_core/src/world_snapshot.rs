@@ -0,0 +1,76 @@
+// crates/engine_core/src/world_snapshot.rs
+
+use engine_ecs::{Entity, EntityState, SparseSet, World};
+use engine_shared::{CCamera, CEnemy, CPlayer, CSprite, CTransform, CWorldBounds};
+use serde::{Deserialize, Serialize};
+
+/// Serializable copy of every component registered by `scene::setup_default_world`.
+///
+/// Used to mirror the World into a sandboxed plugin process and back, and as a
+/// replay's starting World. Panic rollback uses `World::begin_checkpoint`
+/// instead, which covers every storage.
+/// Keep the column list in sync with `scene::setup_default_world`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    entities: Vec<u64>,
+    free_indices: Vec<u32>,
+    generations: Vec<u32>,
+
+    transforms: Vec<(u64, CTransform)>,
+    players: Vec<(u64, CPlayer)>,
+    enemies: Vec<(u64, CEnemy)>,
+    sprites: Vec<(u64, CSprite)>,
+    cameras: Vec<(u64, CCamera)>,
+    bounds: Vec<(u64, CWorldBounds)>,
+}
+
+impl WorldSnapshot {
+    pub fn capture(world: &World) -> Self {
+        let state = world.entity_state();
+        Self {
+            entities: state.entities.iter().map(Entity::to_bits).collect(),
+            free_indices: state.free_indices,
+            generations: state.generations,
+
+            transforms: export_column(world),
+            players: export_column(world),
+            enemies: export_column(world),
+            sprites: export_column(world),
+            cameras: export_column(world),
+            bounds: export_column(world),
+        }
+    }
+
+    /// Overwrites `world` with this snapshot. The target World must already
+    /// have the components registered (see `scene::setup_default_world`).
+    pub fn apply(&self, world: &mut World) {
+        world.restore_entity_state(EntityState {
+            entities: self.entities.iter().copied().map(Entity::from_bits).collect(),
+            free_indices: self.free_indices.clone(),
+            generations: self.generations.clone(),
+        });
+
+        import_column(world, &self.transforms);
+        import_column(world, &self.players);
+        import_column(world, &self.enemies);
+        import_column(world, &self.sprites);
+        import_column(world, &self.cameras);
+        import_column(world, &self.bounds);
+    }
+}
+
+fn export_column<T: Copy + 'static>(world: &World) -> Vec<(u64, T)> {
+    world
+        .query::<T>()
+        .map(|set| set.iter().map(|(e, c)| (e.to_bits(), *c)).collect())
+        .unwrap_or_default()
+}
+
+fn import_column<T: Copy + 'static>(world: &mut World, column: &[(u64, T)]) {
+    if let Some(set) = world.query_mut::<T>() {
+        *set = SparseSet::new();
+        for &(bits, component) in column {
+            set.insert(Entity::from_bits(bits), component);
+        }
+    }
+}
@@ -24,6 +24,15 @@ impl Entity {
     pub fn generation(&self) -> u32 {
         (self.id >> Self::GENERATION_SHIFT) as u32
     }
+
+    /// Packed index + generation, for serialization across process boundaries.
+    pub fn to_bits(&self) -> u64 {
+        self.id
+    }
+
+    pub fn from_bits(id: u64) -> Self {
+        Self { id }
+    }
 }
 
 impl fmt::Debug for Entity {
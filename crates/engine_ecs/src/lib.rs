@@ -4,4 +4,4 @@ mod world;
 
 pub use storage::{SparseSet, Storage};
 pub use entity::Entity;
-pub use world::World;
\ No newline at end of file
+pub use world::{EntityState, World};
\ No newline at end of file
@@ -5,14 +5,23 @@ use rayon::prelude::*;
 pub trait Storage {
     fn as_any(&self) -> &dyn std::any::Any;
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+    /// Deep copy, for World checkpoints and clones.
+    fn clone_box(&self) -> Box<dyn Storage>;
 }
 
+#[derive(Clone)]
 pub struct SparseSet<T> {
     pub dense: Vec<T>,          // Tightly packed data (Cache friendly!)
     pub entities: Vec<Entity>,  // The entity that owns the data at 'dense[i]'
     pub sparse: Vec<Option<usize>>, // Maps Entity Index -> Dense Index
 }
 
+impl<T: 'static> Default for SparseSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: 'static> SparseSet<T> {
     pub fn new() -> Self {
         Self {
@@ -45,13 +54,12 @@ impl<T: 'static> SparseSet<T> {
 
     pub fn get(&self, entity: Entity) -> Option<&T> {
         let index = entity.index();
-        if index < self.sparse.len() {
-            if let Some(dense_index) = self.sparse[index] {
-                // Check generation to ensure the entity is still alive!
-                if self.entities[dense_index].generation() == entity.generation() {
-                    return Some(&self.dense[dense_index]);
-                }
-            }
+        if index < self.sparse.len()
+            && let Some(dense_index) = self.sparse[index]
+            // Check generation to ensure the entity is still alive!
+            && self.entities[dense_index].generation() == entity.generation()
+        {
+            return Some(&self.dense[dense_index]);
         }
         None
     }
@@ -59,12 +67,11 @@ impl<T: 'static> SparseSet<T> {
     /// O(1) mutable lookup by Entity using the sparse index + generation check.
     pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
         let index = entity.index();
-        if index < self.sparse.len() {
-            if let Some(dense_index) = self.sparse[index] {
-                if self.entities[dense_index].generation() == entity.generation() {
-                    return self.dense.get_mut(dense_index);
-                }
-            }
+        if index < self.sparse.len()
+            && let Some(dense_index) = self.sparse[index]
+            && self.entities[dense_index].generation() == entity.generation()
+        {
+            return self.dense.get_mut(dense_index);
         }
         None
     }
@@ -105,7 +112,8 @@ impl<T: 'static + Sync + Send> SparseSet<T> {
 }
 
 // Boilerplate to allow dynamic typing of the storage
-impl<T: 'static> Storage for SparseSet<T> {
+impl<T: Clone + 'static> Storage for SparseSet<T> {
     fn as_any(&self) -> &dyn std::any::Any { self }
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn clone_box(&self) -> Box<dyn Storage> { Box::new(self.clone()) }
 }
\ No newline at end of file
@@ -6,12 +6,37 @@ use std::collections::HashMap;
 use crate::storage::{Storage, SparseSet};
 use crate::entity::Entity;
 
+/// Entity allocator state, exported so a World can be mirrored elsewhere
+/// (e.g. a sandboxed plugin process) and restored exactly.
+#[derive(Clone, Debug, Default)]
+pub struct EntityState {
+    pub entities: Vec<Entity>,
+    pub free_indices: Vec<u32>,
+    pub generations: Vec<u32>,
+}
+
+/// Pre-checkpoint copies of whatever changed since `World::begin_checkpoint`.
+/// A storage is copied on its first mutable access, so untouched ones cost nothing.
+#[derive(Default)]
+struct Checkpoint {
+    entity_state: Option<EntityState>,
+    /// `None`: the storage was registered after the checkpoint.
+    storages: HashMap<TypeId, Option<Box<dyn Storage>>>,
+}
+
 pub struct World {
     entities: Vec<Entity>,
     // Map Component Type -> Storage
     components: HashMap<TypeId, Box<dyn Storage>>,
     free_indices: Vec<u32>,
     generations: Vec<u32>,
+    checkpoint: Option<Checkpoint>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl World {
@@ -21,12 +46,13 @@ impl World {
             components: HashMap::new(),
             free_indices: Vec::new(),
             generations: Vec::new(),
+            checkpoint: None,
         }
     }
 
     /// Register a component type with the world.
     /// This MUST be called exactly once per component type.
-    pub fn register_component<T: 'static>(&mut self) {
+    pub fn register_component<T: Clone + 'static>(&mut self) {
         let type_id = TypeId::of::<T>();
 
         if self.components.contains_key(&type_id) {
@@ -38,11 +64,13 @@ impl World {
             );
         }
 
+        self.touch_storage(type_id);
         self.components
             .insert(type_id, Box::new(SparseSet::<T>::new()));
     }
 
     pub fn spawn(&mut self) -> Entity {
+        self.touch_entities();
         let index = if let Some(idx) = self.free_indices.pop() {
             idx
         } else {
@@ -56,11 +84,28 @@ impl World {
         entity
     }
 
+    pub fn entity_state(&self) -> EntityState {
+        EntityState {
+            entities: self.entities.clone(),
+            free_indices: self.free_indices.clone(),
+            generations: self.generations.clone(),
+        }
+    }
+
+    /// Replaces the allocator state. Component storages are left untouched.
+    pub fn restore_entity_state(&mut self, state: EntityState) {
+        self.touch_entities();
+        self.entities = state.entities;
+        self.free_indices = state.free_indices;
+        self.generations = state.generations;
+    }
+
     /// STRICT MODE: adding a component to an unregistered type is a hard error.
     pub fn add_component<T: 'static>(&mut self, entity: Entity, component: T) {
         use std::collections::hash_map::Entry;
 
         let type_id = TypeId::of::<T>();
+        self.touch_storage(type_id);
 
         match self.components.entry(type_id) {
             Entry::Occupied(mut occ) => {
@@ -93,10 +138,10 @@ impl World {
     /// Returns a shared reference to the component `T` for `entity`, or `None` if not present.
     pub fn get_component<T: 'static>(&self, entity: Entity) -> Option<&T> {
         let type_id = TypeId::of::<T>();
-        if let Some(storage) = self.components.get(&type_id) {
-            if let Some(sparse_set) = storage.as_any().downcast_ref::<SparseSet<T>>() {
-                return sparse_set.get(entity);
-            }
+        if let Some(storage) = self.components.get(&type_id)
+            && let Some(sparse_set) = storage.as_any().downcast_ref::<SparseSet<T>>()
+        {
+            return sparse_set.get(entity);
         }
         None
     }
@@ -112,8 +157,75 @@ impl World {
     /// Mutable access to the full storage of a component type.
     pub fn query_mut<T: 'static>(&mut self) -> Option<&mut SparseSet<T>> {
         let type_id = TypeId::of::<T>();
+        self.touch_storage(type_id);
         self.components
             .get_mut(&type_id)
             .and_then(|boxed| boxed.as_any_mut().downcast_mut::<SparseSet<T>>())
     }
+
+    // --- Checkpoints ---
+
+    /// Starts recording a rollback point (replacing any open one). Storages
+    /// and the entity allocator are copied when first mutated afterwards.
+    pub fn begin_checkpoint(&mut self) {
+        self.checkpoint = Some(Checkpoint::default());
+    }
+
+    /// Keeps every change since `begin_checkpoint` and drops the copies.
+    pub fn commit_checkpoint(&mut self) {
+        self.checkpoint = None;
+    }
+
+    /// Undoes every change since `begin_checkpoint`. False if none was open.
+    pub fn rollback_checkpoint(&mut self) -> bool {
+        let Some(checkpoint) = self.checkpoint.take() else {
+            return false;
+        };
+        if let Some(state) = checkpoint.entity_state {
+            self.entities = state.entities;
+            self.free_indices = state.free_indices;
+            self.generations = state.generations;
+        }
+        for (type_id, storage) in checkpoint.storages {
+            match storage {
+                Some(storage) => self.components.insert(type_id, storage),
+                None => self.components.remove(&type_id),
+            };
+        }
+        true
+    }
+
+    fn touch_entities(&mut self) {
+        if let Some(checkpoint) = &mut self.checkpoint
+            && checkpoint.entity_state.is_none()
+        {
+            checkpoint.entity_state = Some(EntityState {
+                entities: self.entities.clone(),
+                free_indices: self.free_indices.clone(),
+                generations: self.generations.clone(),
+            });
+        }
+    }
+
+    fn touch_storage(&mut self, type_id: TypeId) {
+        if let Some(checkpoint) = &mut self.checkpoint {
+            checkpoint
+                .storages
+                .entry(type_id)
+                .or_insert_with(|| self.components.get(&type_id).map(|s| s.clone_box()));
+        }
+    }
+}
+
+/// Deep copy of every storage; an open checkpoint is not carried over.
+impl Clone for World {
+    fn clone(&self) -> Self {
+        Self {
+            entities: self.entities.clone(),
+            components: self.components.iter().map(|(&id, s)| (id, s.clone_box())).collect(),
+            free_indices: self.free_indices.clone(),
+            generations: self.generations.clone(),
+            checkpoint: None,
+        }
+    }
 }
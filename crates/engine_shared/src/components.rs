@@ -1,8 +1,9 @@
 // crates/engine_shared/src/components.rs
 use glam::{Vec2, Vec4};
+use serde::{Deserialize, Serialize};
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct CTransform {
     pub pos: Vec2,
     pub scale: Vec2,
@@ -16,7 +17,7 @@ impl Default for CTransform {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct CSprite {
     pub color: Vec4,
 }
@@ -26,17 +27,17 @@ impl Default for CSprite {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct CPlayer;
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct CEnemy {
     pub speed: f32,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct CCamera {
     pub zoom: f32,
     pub smoothness: f32, 
@@ -54,7 +55,7 @@ impl Default for CCamera {
 // [AUDIO FIX] "Single Source of Truth" Component
 // This solves the "Invisible Prison" by ensuring Player & Camera share exact bounds.
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct CWorldBounds {
     pub width: f32,
     pub height: f32,
@@ -1,12 +1,19 @@
 // crates/engine_shared/src/input_types.rs
 //! Compact, FFI-friendly input types used by host <-> plugin and for networking/replay.
 
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
 use glam::Vec2;
+use serde::{Deserialize, Serialize};
 
 /// Stable Integer ID for Actions (FFI-safe)
 pub type ActionId = u32;
 pub const ACTION_NOT_FOUND: ActionId = u32::MAX;
 
+/// Index of a named axis in `InputState::analog_axes`; a 2D axis also uses the next one.
+pub type AxisId = u32;
+pub const AXIS_NOT_FOUND: AxisId = u32::MAX;
+
 /// Canonical Action IDs for core movement.
 /// App must register these first to ensure they get IDs 0..3.
 pub mod canonical_actions {
@@ -17,12 +24,135 @@ pub mod canonical_actions {
     pub const MOVE_RIGHT: ActionId = 3;
 }
 
+/// Digital actions an `InputState` can carry; the registry refuses IDs past this.
+pub const MAX_ACTIONS: usize = 256;
+
+const ACTION_WORDS: usize = MAX_ACTIONS / 64;
+
+/// Set of action IDs below `MAX_ACTIONS`: a fixed bitset, 64 actions per word,
+/// so it stays `Copy` and `repr(C)` across the FFI.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ActionSet {
+    pub words: [u64; ACTION_WORDS],
+}
+
+impl ActionSet {
+    pub const EMPTY: Self = Self { words: [0; ACTION_WORDS] };
+    pub const ALL: Self = Self { words: [!0; ACTION_WORDS] };
+
+    /// This set plus `id` (usable in constants). IDs past `MAX_ACTIONS` are ignored.
+    pub const fn with(mut self, id: ActionId) -> Self {
+        if (id as usize) < MAX_ACTIONS {
+            self.words[id as usize / 64] |= 1 << (id % 64);
+        }
+        self
+    }
+
+    pub fn contains(&self, id: ActionId) -> bool {
+        (id as usize) < MAX_ACTIONS && self.words[id as usize / 64] & (1 << (id % 64)) != 0
+    }
+
+    /// False (and nothing changes) if `id` is past `MAX_ACTIONS`.
+    pub fn insert(&mut self, id: ActionId) -> bool {
+        if (id as usize) >= MAX_ACTIONS {
+            return false;
+        }
+        self.words[id as usize / 64] |= 1 << (id % 64);
+        true
+    }
+
+    pub fn remove(&mut self, id: ActionId) {
+        if (id as usize) < MAX_ACTIONS {
+            self.words[id as usize / 64] &= !(1 << (id % 64));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// IDs in the set, ascending.
+    pub fn iter(&self) -> impl Iterator<Item = ActionId> + '_ {
+        (0..MAX_ACTIONS as ActionId).filter(|&id| self.contains(id))
+    }
+}
+
+impl FromIterator<ActionId> for ActionSet {
+    fn from_iter<I: IntoIterator<Item = ActionId>>(ids: I) -> Self {
+        let mut set = Self::EMPTY;
+        for id in ids {
+            set.insert(id);
+        }
+        set
+    }
+}
+
+impl BitAnd for ActionSet {
+    type Output = Self;
+    fn bitand(mut self, rhs: Self) -> Self {
+        self &= rhs;
+        self
+    }
+}
+
+impl BitOr for ActionSet {
+    type Output = Self;
+    fn bitor(mut self, rhs: Self) -> Self {
+        self |= rhs;
+        self
+    }
+}
+
+impl BitAndAssign for ActionSet {
+    fn bitand_assign(&mut self, rhs: Self) {
+        for (w, r) in self.words.iter_mut().zip(rhs.words) {
+            *w &= r;
+        }
+    }
+}
+
+impl BitOrAssign for ActionSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        for (w, r) in self.words.iter_mut().zip(rhs.words) {
+            *w |= r;
+        }
+    }
+}
+
+impl Not for ActionSet {
+    type Output = Self;
+    fn not(mut self) -> Self {
+        for w in &mut self.words {
+            *w = !*w;
+        }
+        self
+    }
+}
+
 /// Maximum number of analog axes we expose across FFI
-pub const MAX_AXES: usize = 8;
+pub const MAX_AXES: usize = 24;
+
+/// What the host writes into `InputState::analog_axes`.
+pub mod axes {
+    /// Resolved movement vector (stick or Arbiter winner), length <= 1.
+    pub const MOVE_X: usize = 0;
+    pub const MOVE_Y: usize = 1;
+    /// Cursor in window pixels, origin top-left, +Y down.
+    pub const CURSOR_SCREEN_X: usize = 2;
+    pub const CURSOR_SCREEN_Y: usize = 3;
+    /// Cursor in world units through the active `CCamera` (+Y up).
+    pub const CURSOR_WORLD_X: usize = 4;
+    pub const CURSOR_WORLD_Y: usize = 5;
+    /// Mouse wheel lines scrolled since the previous tick (+ = away from the user).
+    pub const SCROLL: usize = 6;
+    /// Named axes (input profile `[axes]`, looked up with `get_axis_id`) start here.
+    pub const FIRST_NAMED: usize = 8;
+}
 
 /// The Priority Stack for Subsumption Architecture
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum PriorityLayer {
     Reflex = 0,
     Cutscene = 1,
@@ -30,6 +160,20 @@ pub enum PriorityLayer {
     Ambient = 3,
 }
 
+impl PriorityLayer {
+    pub const ALL: [PriorityLayer; 4] = [
+        PriorityLayer::Reflex,
+        PriorityLayer::Cutscene,
+        PriorityLayer::Control,
+        PriorityLayer::Ambient,
+    ];
+
+    /// The layer with this discriminant (as passed across the FFI).
+    pub fn from_u32(value: u32) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct MovementSignal {
@@ -67,30 +211,50 @@ impl Default for ActionSignal {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct InputState {
-    /// Bitmask for up to 64 digital actions (result of arbitration).
-    pub digital_mask: u64,
+    /// Held digital actions (result of arbitration).
+    pub digital_mask: ActionSet,
 
     /// Fixed-size analog axes. Host maps an ActionId -> axis index.
     pub analog_axes: [f32; MAX_AXES],
+
+    /// Actions that went down since the previous simulation tick. A press that
+    /// starts and ends between two ticks still shows up here (and in `released_mask`).
+    pub pressed_mask: ActionSet,
+
+    /// Actions that went up since the previous simulation tick.
+    pub released_mask: ActionSet,
 }
 
 impl Default for InputState {
     fn default() -> Self {
         Self {
-            digital_mask: 0,
+            digital_mask: ActionSet::EMPTY,
             analog_axes: [0.0; MAX_AXES],
+            pressed_mask: ActionSet::EMPTY,
+            released_mask: ActionSet::EMPTY,
         }
     }
 }
 
 impl InputState {
     pub fn is_active(&self, action_id: ActionId) -> bool {
-        if (action_id as usize) >= 64 {
-            return false;
-        }
-        (self.digital_mask & (1u64 << action_id)) != 0
+        self.digital_mask.contains(action_id)
+    }
+
+    pub fn just_pressed(&self, action_id: ActionId) -> bool {
+        self.pressed_mask.contains(action_id)
+    }
+
+    pub fn just_released(&self, action_id: ActionId) -> bool {
+        self.released_mask.contains(action_id)
+    }
+
+    /// Fills the edge masks by comparing the held actions with `previous`.
+    pub fn set_edges_from(&mut self, previous: &InputState) {
+        self.pressed_mask = self.digital_mask & !previous.digital_mask;
+        self.released_mask = previous.digital_mask & !self.digital_mask;
     }
 
     pub fn get_axis(&self, axis_index: usize) -> f32 {
@@ -100,31 +264,76 @@ impl InputState {
             self.analog_axes[axis_index]
         }
     }
+
+    /// A 2D named axis: `axis` and the one after it.
+    pub fn get_axis_2d(&self, axis: AxisId) -> Vec2 {
+        let x = axis as usize;
+        Vec2::new(self.get_axis(x), self.get_axis(x.saturating_add(1)))
+    }
+
+    pub fn cursor_screen(&self) -> Vec2 {
+        Vec2::new(self.analog_axes[axes::CURSOR_SCREEN_X], self.analog_axes[axes::CURSOR_SCREEN_Y])
+    }
+
+    pub fn cursor_world(&self) -> Vec2 {
+        Vec2::new(self.analog_axes[axes::CURSOR_WORLD_X], self.analog_axes[axes::CURSOR_WORLD_Y])
+    }
+
+    pub fn scroll(&self) -> f32 {
+        self.analog_axes[axes::SCROLL]
+    }
 }
 
 /// Compact per-frame input snapshot for deterministic replay/netcode.
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct FrameInputState {
     pub tick: u64,
-    pub actions: u64,
+    pub actions: ActionSet,
+    pub pressed: ActionSet,
+    pub released: ActionSet,
+    /// Movement axes in thousandths.
     pub move_vector: [i16; 2],
+    /// The axes after the movement vector (cursor, scroll, named axes), unchanged.
+    pub pointer_axes: [f32; MAX_AXES - 2],
     pub rng_seed: u64,
 }
 
 impl FrameInputState {
     pub fn from_state(tick: u64, seed: u64, state: &InputState) -> Self {
         let scale = 1000.0_f32;
-        let raw_x = (state.analog_axes[0] * scale).round();
-        let raw_y = (state.analog_axes[1] * scale).round();
+        let raw_x = (state.analog_axes[axes::MOVE_X] * scale).round();
+        let raw_y = (state.analog_axes[axes::MOVE_Y] * scale).round();
+
+        let mut pointer_axes = [0.0; MAX_AXES - 2];
+        pointer_axes.copy_from_slice(&state.analog_axes[2..]);
 
         Self {
             tick,
             actions: state.digital_mask,
+            pressed: state.pressed_mask,
+            released: state.released_mask,
             move_vector: [clamp_i16(raw_x as i64), clamp_i16(raw_y as i64)],
+            pointer_axes,
             rng_seed: seed,
         }
     }
+
+    /// The input this frame stands for. Movement comes back at 1/1000
+    /// precision, so a recorded run has to be fed this too to match its replay.
+    pub fn to_state(&self) -> InputState {
+        let mut analog_axes = [0.0; MAX_AXES];
+        analog_axes[axes::MOVE_X] = self.move_vector[0] as f32 / 1000.0;
+        analog_axes[axes::MOVE_Y] = self.move_vector[1] as f32 / 1000.0;
+        analog_axes[2..].copy_from_slice(&self.pointer_axes);
+
+        InputState {
+            digital_mask: self.actions,
+            analog_axes,
+            pressed_mask: self.pressed,
+            released_mask: self.released,
+        }
+    }
 }
 
 fn clamp_i16(v: i64) -> i16 {
@@ -136,3 +345,57 @@ fn clamp_i16(v: i64) -> i16 {
         v as i16
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Word boundaries and the last valid ID.
+    const EDGE_IDS: [ActionId; 6] = [63, 64, 127, 128, 200, (MAX_ACTIONS - 1) as ActionId];
+
+    #[test]
+    fn high_action_ids_set_and_clear_independently() {
+        let mut input = InputState::default();
+        for id in EDGE_IDS {
+            assert!(input.digital_mask.insert(id));
+            assert!(input.is_active(id));
+        }
+        assert_eq!(input.digital_mask.iter().collect::<Vec<_>>(), EDGE_IDS);
+        assert_eq!(input.digital_mask.words, [1 << 63, 1 | 1 << 63, 1, 1 << 8 | 1 << 63]);
+
+        input.digital_mask.remove(64);
+        input.digital_mask.remove((MAX_ACTIONS - 1) as ActionId);
+        assert!(!input.is_active(64) && !input.is_active((MAX_ACTIONS - 1) as ActionId));
+        assert!(input.is_active(63) && input.is_active(128) && input.is_active(200));
+        assert_eq!(ActionSet::EMPTY.with(64).with(255), [64, 255].into_iter().collect());
+    }
+
+    #[test]
+    fn ids_past_max_actions_are_refused() {
+        let mut set = ActionSet::EMPTY;
+        assert!(!set.insert(MAX_ACTIONS as ActionId));
+        assert!(!set.insert(ActionId::MAX));
+        assert!(set.is_empty());
+        assert_eq!(ActionSet::EMPTY.with(MAX_ACTIONS as ActionId), ActionSet::EMPTY);
+
+        let input = InputState { digital_mask: ActionSet::ALL, ..Default::default() };
+        assert!(input.is_active((MAX_ACTIONS - 1) as ActionId));
+        assert!(!input.is_active(MAX_ACTIONS as ActionId));
+    }
+
+    #[test]
+    fn edges_and_replay_frames_keep_high_ids() {
+        let previous = InputState { digital_mask: ActionSet::EMPTY.with(64), ..Default::default() };
+        let mut input = InputState {
+            digital_mask: ActionSet::EMPTY.with((MAX_ACTIONS - 1) as ActionId),
+            ..Default::default()
+        };
+        input.set_edges_from(&previous);
+        assert!(input.just_pressed((MAX_ACTIONS - 1) as ActionId));
+        assert!(input.just_released(64));
+
+        let replayed = FrameInputState::from_state(0, 0, &input).to_state();
+        assert!(replayed.is_active((MAX_ACTIONS - 1) as ActionId));
+        assert!(replayed.just_pressed((MAX_ACTIONS - 1) as ActionId) && replayed.just_released(64));
+    }
+}
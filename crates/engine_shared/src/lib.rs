@@ -1,11 +1,12 @@
 // crates/engine_shared/src/lib.rs
 #![allow(dead_code)]
 
-pub const ENGINE_API_VERSION: u32 = 1;
+pub const ENGINE_API_VERSION: u32 = 12;
 // Logic Modules
 pub mod components;
 pub mod input_types; // <--- The new name
 pub mod plugin_api;
+pub mod wasm_abi;
 
 // Re-exports
 pub use components::*;
@@ -1,7 +1,9 @@
 // crates/engine_shared/src/plugin_api.rs
 
-use core::ffi::{c_char, c_void};
-use crate::input_types::{ActionId, InputState};
+use core::ffi::c_void;
+use crate::input_types::{ActionId, AxisId, InputState};
+use crate::ENGINE_API_VERSION;
+use serde::{Deserialize, Serialize};
 
 // ==================================================================================
 // 1. CONSTANTS & ENUMS
@@ -11,8 +13,12 @@ pub const SNAPSHOT_MAGIC_HEADER: u32 = 0xCAFEBABE;
 pub const CURRENT_SCHEMA_HASH: u64 = 0x0123_4567_89AB_CDEF;
 pub const CURRENT_STATE_VERSION: u32 = 1;
 
+/// Exported symbol names the host resolves in every plugin library.
+pub const CREATE_GAME_SYMBOL: &[u8] = b"_create_game";
+pub const API_VERSION_SYMBOL: &[u8] = b"_engine_api_version";
+
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FFIResult {
     Success        = 0,
     BufferTooSmall = 1,
@@ -21,6 +27,67 @@ pub enum FFIResult {
     Error          = 4,
 }
 
+impl FFIResult {
+    /// Decodes a result that crossed a non-Rust boundary; unknown values are `Error`.
+    pub fn from_u32(raw: u32) -> Self {
+        match raw {
+            0 => FFIResult::Success,
+            1 => FFIResult::BufferTooSmall,
+            2 => FFIResult::SchemaMismatch,
+            3 => FFIResult::PanicDetected,
+            _ => FFIResult::Error,
+        }
+    }
+}
+
+/// Severity passed to `HostInterface::log` (sent as a raw `u32`; unknown values map to Trace).
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error = 0,
+    Warn  = 1,
+    Info  = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+/// Value type of a console variable (sent as a raw `u32` to `HostInterface::cvar_register`).
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CVarKind {
+    Int    = 0,
+    Float  = 1,
+    Bool   = 2,
+    String = 3,
+}
+
+impl CVarKind {
+    pub fn from_u32(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(CVarKind::Int),
+            1 => Some(CVarKind::Float),
+            2 => Some(CVarKind::Bool),
+            3 => Some(CVarKind::String),
+            _ => None,
+        }
+    }
+}
+
+/// `cvar_register` flag: write non-default values to the cvar file.
+pub const CVAR_PERSIST: u32 = 1 << 0;
+
+impl LogLevel {
+    pub fn from_u32(raw: u32) -> Self {
+        match raw {
+            0 => LogLevel::Error,
+            1 => LogLevel::Warn,
+            2 => LogLevel::Info,
+            3 => LogLevel::Debug,
+            _ => LogLevel::Trace,
+        }
+    }
+}
+
 // ==================================================================================
 // 2. DATA STRUCTURES
 // ==================================================================================
@@ -41,6 +108,37 @@ pub struct StateEnvelope {
     pub payload_len: u64,
 }
 
+/// Sizes and schema tag of a bus message, filled in by `HostInterface::bus_receive`.
+/// All zero when the inbox is empty.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct BusMessageHeader {
+    pub schema: u64,
+    pub topic_len: usize,
+    pub payload_len: usize,
+}
+
+/// ABI fingerprint returned by the plugin's `_engine_api_version` export.
+/// The host compares it against its own before touching any vtable.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AbiInfo {
+    pub api_version: u32,
+    pub plugin_api_size: u32,
+    pub host_interface_size: u32,
+}
+
+impl AbiInfo {
+    /// Fingerprint of the `engine_shared` this crate was compiled against.
+    pub const fn current() -> Self {
+        Self {
+            api_version: ENGINE_API_VERSION,
+            plugin_api_size: core::mem::size_of::<PluginApi>() as u32,
+            host_interface_size: core::mem::size_of::<HostInterface>() as u32,
+        }
+    }
+}
+
 // ==================================================================================
 // 3. HOST TYPES
 // ==================================================================================
@@ -52,12 +150,164 @@ pub struct HostContext {
     _marker: core::marker::PhantomData<(*mut u8, core::marker::PhantomPinned)>,
 }
 
+/// Console command callback. `args` is everything after the command name (UTF-8).
+pub type ConsoleCommandFn = extern "C" fn(
+    user_data: *mut c_void,
+    host_ctx: *mut HostContext,
+    args_ptr: *const u8,
+    args_len: usize,
+);
+
 /// VTable of functions provided by the Host to the Plugin.
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct HostInterface {
     pub get_action_id: extern "C" fn(name_ptr: *const u8, name_len: usize) -> ActionId,
-    pub log: Option<extern "C" fn(msg: *const c_char)>,
+    /// Registers an action (or returns its existing ID). IDs are stable across hot reloads;
+    /// `ACTION_NOT_FOUND` if the registry is full.
+    pub register_action: extern "C" fn(name_ptr: *const u8, name_len: usize) -> ActionId,
+    /// Looks up a named axis from the input profile's `[axes]`; read it with
+    /// `InputState::get_axis` (`get_axis_2d` for 2D axes). `AXIS_NOT_FOUND` if unknown.
+    pub get_axis_id: extern "C" fn(name_ptr: *const u8, name_len: usize) -> AxisId,
+    /// Suggests a default key (winit name, e.g. "Space", "KeyF"), gamepad button
+    /// ("Pad.South", "Pad.DPadUp") or trigger ("Ctrl+KeyS", "KeyE:hold(500)").
+    /// Ignored if it is already bound.
+    pub bind_default_key:
+        extern "C" fn(action: ActionId, key_ptr: *const u8, key_len: usize) -> bool,
+    /// `bind_default_key` for the input context named by `context` ("Menu").
+    pub bind_context_key: extern "C" fn(
+        context_ptr: *const u8,
+        context_len: usize,
+        action: ActionId,
+        key_ptr: *const u8,
+        key_len: usize,
+    ) -> bool,
+    /// Pushes the named input context ("Menu", "Dialogue") on the context stack:
+    /// its bindings take their inputs from the contexts below and gameplay.
+    /// Applied at the start of the next frame.
+    pub push_input_context: extern "C" fn(name_ptr: *const u8, name_len: usize) -> bool,
+    /// Removes the topmost entry of the named context from the stack.
+    pub pop_input_context: extern "C" fn(name_ptr: *const u8, name_len: usize) -> bool,
+    /// Pushes `(x, y) * weight` as a movement signal on `layer` (`PriorityLayer as u32`)
+    /// for `duration` seconds, e.g. knockback on Reflex. The Arbiter's layer masks
+    /// and locks apply as for input. False for an unknown layer or non-finite values.
+    pub submit_movement: extern "C" fn(layer: u32, x: f32, y: f32, weight: f32, duration: f32) -> bool,
+    /// Holds `action` on `layer` for `duration` seconds, like `submit_movement`.
+    pub submit_action: extern "C" fn(layer: u32, action: ActionId, duration: f32) -> bool,
+    /// Levelled, target-tagged log line (UTF-8 slices, not NUL-terminated).
+    pub log: extern "C" fn(
+        level: u32,
+        target_ptr: *const u8,
+        target_len: usize,
+        msg_ptr: *const u8,
+        msg_len: usize,
+    ),
     pub spawn_enemy: extern "C" fn(ctx: *mut HostContext, x: f32, y: f32),
+
+    // Console variables. Names and values are UTF-8 slices; defaults are parsed per `kind`.
+    /// Registers (or re-registers) a cvar. An existing value survives re-registration.
+    pub cvar_register: extern "C" fn(
+        kind: u32,
+        flags: u32,
+        name_ptr: *const u8,
+        name_len: usize,
+        default_ptr: *const u8,
+        default_len: usize,
+        desc_ptr: *const u8,
+        desc_len: usize,
+    ) -> bool,
+    /// Numeric view of a cvar (bools read as 0/1); `fallback` if missing or a string.
+    pub cvar_get_f64: extern "C" fn(name_ptr: *const u8, name_len: usize, fallback: f64) -> f64,
+    /// Copies the cvar's text form into `buf` and returns its full length (0 if missing).
+    pub cvar_get_str:
+        extern "C" fn(name_ptr: *const u8, name_len: usize, buf: *mut u8, buf_len: usize) -> usize,
+    pub cvar_set: extern "C" fn(
+        name_ptr: *const u8,
+        name_len: usize,
+        value_ptr: *const u8,
+        value_len: usize,
+    ) -> bool,
+
+    /// Registers a console command. Only valid during `on_load`; the command is
+    /// removed when the plugin unloads, so `user_data` only has to live as long as it.
+    pub register_command: extern "C" fn(
+        name_ptr: *const u8,
+        name_len: usize,
+        desc_ptr: *const u8,
+        desc_len: usize,
+        callback: ConsoleCommandFn,
+        user_data: *mut c_void,
+    ) -> bool,
+
+    // Message bus between plugins. Topics are UTF-8; payloads are opaque bytes
+    // tagged with a `schema` id (0 = untagged). Messages published during a tick
+    // are delivered at the start of the next one. Subscriptions and undelivered
+    // messages belong to the plugin's name, so they outlive a hot reload.
+    pub bus_publish: extern "C" fn(
+        topic_ptr: *const u8,
+        topic_len: usize,
+        schema: u64,
+        payload_ptr: *const u8,
+        payload_len: usize,
+    ) -> bool,
+    /// Subscribing twice is harmless; `on_load` can subscribe on every load.
+    pub bus_subscribe: extern "C" fn(topic_ptr: *const u8, topic_len: usize) -> bool,
+    pub bus_unsubscribe: extern "C" fn(topic_ptr: *const u8, topic_len: usize) -> bool,
+    /// Pops the oldest delivered message, writing its topic then its payload into `buf`.
+    /// If `buf` is too small the message stays queued and `header` has the sizes
+    /// needed; an empty inbox leaves `header` zeroed. Returns true if a message was copied.
+    pub bus_receive:
+        extern "C" fn(header: *mut BusMessageHeader, buf: *mut u8, buf_len: usize) -> bool,
+}
+
+/// Opaque handle to the host's current UI container (an egui `Ui`).
+#[repr(C)]
+pub struct DebugUiContext {
+    _data: [u8; 0],
+    _marker: core::marker::PhantomData<(*mut u8, core::marker::PhantomPinned)>,
+}
+
+/// Immediate-mode UI builder handed to `PluginApi::on_debug_ui`.
+/// Only valid for the duration of that call. Strings are UTF-8 ptr+len;
+/// widgets that edit a value write through the pointer and return true on change.
+#[repr(C)]
+pub struct DebugUi {
+    pub ctx: *mut DebugUiContext,
+    pub label: extern "C" fn(ctx: *mut DebugUiContext, text_ptr: *const u8, text_len: usize),
+    pub heading: extern "C" fn(ctx: *mut DebugUiContext, text_ptr: *const u8, text_len: usize),
+    pub separator: extern "C" fn(ctx: *mut DebugUiContext),
+    pub button:
+        extern "C" fn(ctx: *mut DebugUiContext, text_ptr: *const u8, text_len: usize) -> bool,
+    pub checkbox: extern "C" fn(
+        ctx: *mut DebugUiContext,
+        text_ptr: *const u8,
+        text_len: usize,
+        value: *mut bool,
+    ) -> bool,
+    pub slider_f32: extern "C" fn(
+        ctx: *mut DebugUiContext,
+        text_ptr: *const u8,
+        text_len: usize,
+        value: *mut f32,
+        min: f32,
+        max: f32,
+    ) -> bool,
+    pub slider_i32: extern "C" fn(
+        ctx: *mut DebugUiContext,
+        text_ptr: *const u8,
+        text_len: usize,
+        value: *mut i32,
+        min: i32,
+        max: i32,
+    ) -> bool,
+    /// Line plot of `count` samples (oldest first).
+    pub plot_lines: extern "C" fn(
+        ctx: *mut DebugUiContext,
+        text_ptr: *const u8,
+        text_len: usize,
+        values: *const f32,
+        count: usize,
+    ),
 }
 
 // ==================================================================================
@@ -82,8 +332,18 @@ pub struct PluginApi {
         dt: f32,
     ) -> FFIResult,
 
+    /// Called once before the plugin's state is dropped and its library closed
+    /// (hot reload or shutdown).
     pub on_unload: extern "C" fn(state: *mut c_void, host_ctx: *mut HostContext) -> FFIResult,
 
+    // Hot reload hooks (optional). Order on F5: old `on_before_reload` → save_state →
+    // new library loaded → old `on_unload` → old library closed → new load_state →
+    // new `on_load` → new `on_after_reload`.
+    pub on_before_reload:
+        Option<extern "C" fn(state: *mut c_void, host_ctx: *mut HostContext) -> FFIResult>,
+    pub on_after_reload:
+        Option<extern "C" fn(state: *mut c_void, host_ctx: *mut HostContext) -> FFIResult>,
+
     // State management
     pub get_state_len: extern "C" fn(state: *mut c_void) -> usize,
     pub save_state: extern "C" fn(state: *mut c_void, buffer: FFIBuffer) -> FFIResult,
@@ -96,4 +356,10 @@ pub struct PluginApi {
 
     /// Exposes the current state version from the plugin.
     pub get_state_version: extern "C" fn() -> u32,
+
+    /// Optional debug panel, drawn into a host window titled after the plugin
+    /// whenever plugin panels are visible (F2). `None` if the plugin has no UI.
+    pub on_debug_ui: Option<
+        extern "C" fn(state: *mut c_void, host_ctx: *mut HostContext, ui: *const DebugUi) -> FFIResult,
+    >,
 }
@@ -0,0 +1,154 @@
+// crates/engine_shared/src/wasm_abi.rs
+//! Contract between the host and `.wasm` plugins (wasm32, no WASI).
+//!
+//! A wasm plugin is the `PluginApi` lifecycle expressed as module exports, with
+//! `HostInterface` as imports from the `"engine"` module. Pointers are offsets
+//! into the plugin's exported `memory`; strings are UTF-8 (ptr, len) pairs.
+//! The plugin keeps its own state in globals, so there is no `state` argument.
+//!
+//! Exports (`FFIResult` values are returned as `i32`):
+//! - `memory`
+//! - `engine_api_version() -> i32`, must equal `ENGINE_API_VERSION`
+//! - `plugin_alloc(len: i32) -> i32`, scratch memory for host → plugin data
+//! - `on_load() -> i32`
+//! - `on_update(input_ptr: i32, dt: f32) -> i32`, `input_ptr` holds an `InputState` (see `encode_input`)
+//! - `get_state_len() -> i32`, `save_state(ptr, len) -> i32`, `load_state(ptr, len) -> i32`
+//! - `get_schema_hash() -> i64`, `get_state_version() -> i32`
+//! - optional: `on_unload`, `on_before_reload`, `on_after_reload` (`() -> i32`)
+//! - optional: `on_command(command_id: i32, args_ptr: i32, args_len: i32)`
+//!
+//! The state exports deal in the plugin's raw bytes only; the host wraps them
+//! in a `StateEnvelope` (built from `get_schema_hash`/`get_state_version`).
+//!
+//! Imports (module `"engine"`) mirror `HostInterface`:
+//! - `get_action_id(name_ptr, name_len) -> i32`, `register_action(name_ptr, name_len) -> i32`
+//! - `bind_default_key(action, key_ptr, key_len) -> i32`,
+//!   `bind_context_key(context_ptr, context_len, action, key_ptr, key_len) -> i32`
+//! - `push_input_context(name_ptr, name_len) -> i32`, `pop_input_context(name_ptr, name_len) -> i32`
+//! - `get_axis_id(name_ptr, name_len) -> i32`
+//! - `submit_movement(layer, x: f32, y: f32, weight: f32, duration: f32) -> i32`,
+//!   `submit_action(layer, action, duration: f32) -> i32`
+//! - `log(level, target_ptr, target_len, msg_ptr, msg_len)`
+//! - `spawn_enemy(x: f32, y: f32)`
+//! - `cvar_register(kind, flags, name_ptr, name_len, default_ptr, default_len, desc_ptr, desc_len) -> i32`
+//! - `cvar_get_f64(name_ptr, name_len, fallback: f64) -> f64`
+//! - `cvar_get_str(name_ptr, name_len, buf_ptr, buf_len) -> i32`
+//! - `cvar_set(name_ptr, name_len, value_ptr, value_len) -> i32`
+//! - `register_command(name_ptr, name_len, desc_ptr, desc_len, command_id) -> i32`;
+//!   the host calls `on_command(command_id, ..)` when it runs
+//! - `bus_publish(topic_ptr, topic_len, schema: i64, payload_ptr, payload_len) -> i32`
+//! - `bus_subscribe(topic_ptr, topic_len) -> i32`, `bus_unsubscribe(topic_ptr, topic_len) -> i32`
+//! - `bus_receive(header_ptr, buf_ptr, buf_len) -> i32`: writes a `BUS_HEADER_LEN`
+//!   header (see `encode_bus_header`) and, if the message fits, its topic then
+//!   payload into `buf`; returns 1 if a message was taken
+//!
+//! plus World access, since a wasm plugin can't touch the host's `World` directly.
+//! Entities are `Entity::to_bits` as `i64`; components are `WasmComponent` ids
+//! whose data is a little-endian `f32` array (see `WasmComponent::float_count`):
+//! - `world_query(component, out_ptr, out_cap) -> i32`: writes up to `out_cap`
+//!   entity ids (`i64`) that have `component`, returns how many exist
+//! - `world_get(entity: i64, component, out_ptr) -> i32`: 1 if present (data written)
+//! - `world_set(entity: i64, component, data_ptr) -> i32`: inserts or overwrites
+//! - `world_spawn() -> i64`
+
+use crate::input_types::{ActionSet, InputState, MAX_ACTIONS, MAX_AXES};
+use crate::plugin_api::BusMessageHeader;
+
+pub const IMPORT_MODULE: &str = "engine";
+
+pub const EXPORT_MEMORY: &str = "memory";
+pub const EXPORT_API_VERSION: &str = "engine_api_version";
+pub const EXPORT_ALLOC: &str = "plugin_alloc";
+pub const EXPORT_ON_LOAD: &str = "on_load";
+pub const EXPORT_ON_UPDATE: &str = "on_update";
+pub const EXPORT_ON_UNLOAD: &str = "on_unload";
+pub const EXPORT_ON_BEFORE_RELOAD: &str = "on_before_reload";
+pub const EXPORT_ON_AFTER_RELOAD: &str = "on_after_reload";
+pub const EXPORT_GET_STATE_LEN: &str = "get_state_len";
+pub const EXPORT_SAVE_STATE: &str = "save_state";
+pub const EXPORT_LOAD_STATE: &str = "load_state";
+pub const EXPORT_SCHEMA_HASH: &str = "get_schema_hash";
+pub const EXPORT_STATE_VERSION: &str = "get_state_version";
+pub const EXPORT_ON_COMMAND: &str = "on_command";
+
+/// Components reachable through the `world_*` imports.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WasmComponent {
+    /// pos.x, pos.y, scale.x, scale.y, rotation
+    Transform = 0,
+    /// tag, no data
+    Player = 1,
+    /// speed
+    Enemy = 2,
+    /// r, g, b, a
+    Sprite = 3,
+    /// zoom, smoothness
+    Camera = 4,
+    /// width, height
+    WorldBounds = 5,
+}
+
+impl WasmComponent {
+    pub fn from_u32(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(WasmComponent::Transform),
+            1 => Some(WasmComponent::Player),
+            2 => Some(WasmComponent::Enemy),
+            3 => Some(WasmComponent::Sprite),
+            4 => Some(WasmComponent::Camera),
+            5 => Some(WasmComponent::WorldBounds),
+            _ => None,
+        }
+    }
+
+    /// Number of `f32`s in the component's wire form.
+    pub fn float_count(self) -> usize {
+        match self {
+            WasmComponent::Transform => 5,
+            WasmComponent::Player => 0,
+            WasmComponent::Enemy => 1,
+            WasmComponent::Sprite => 4,
+            WasmComponent::Camera => 2,
+            WasmComponent::WorldBounds => 2,
+        }
+    }
+}
+
+/// Wire size of an `ActionSet`: its words (u64) in order, little-endian.
+pub const ACTION_SET_LEN: usize = MAX_ACTIONS / 8;
+
+/// Wire size of an `InputState`: `digital_mask`, `MAX_AXES` f32, then
+/// `pressed_mask` and `released_mask`, little-endian.
+pub const INPUT_STATE_LEN: usize = ACTION_SET_LEN * 3 + 4 * MAX_AXES;
+
+pub fn encode_input(input: &InputState) -> [u8; INPUT_STATE_LEN] {
+    let mut out = [0u8; INPUT_STATE_LEN];
+    let axes = ACTION_SET_LEN;
+    let edges = axes + 4 * MAX_AXES;
+
+    encode_actions(&input.digital_mask, &mut out[..axes]);
+    for (i, axis) in input.analog_axes.iter().enumerate() {
+        out[axes + i * 4..axes + 4 + i * 4].copy_from_slice(&axis.to_le_bytes());
+    }
+    encode_actions(&input.pressed_mask, &mut out[edges..edges + ACTION_SET_LEN]);
+    encode_actions(&input.released_mask, &mut out[edges + ACTION_SET_LEN..]);
+    out
+}
+
+fn encode_actions(set: &ActionSet, out: &mut [u8]) {
+    for (chunk, word) in out.chunks_exact_mut(8).zip(set.words) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+}
+
+/// Wire size of a `BusMessageHeader`: `schema` (u64), `topic_len` (u32), `payload_len` (u32), little-endian.
+pub const BUS_HEADER_LEN: usize = 16;
+
+pub fn encode_bus_header(header: &BusMessageHeader) -> [u8; BUS_HEADER_LEN] {
+    let mut out = [0u8; BUS_HEADER_LEN];
+    out[..8].copy_from_slice(&header.schema.to_le_bytes());
+    out[8..12].copy_from_slice(&(header.topic_len as u32).to_le_bytes());
+    out[12..].copy_from_slice(&(header.payload_len as u32).to_le_bytes());
+    out
+}
@@ -0,0 +1,9 @@
+// crates/engine_test/src/bin/sandbox_child.rs
+//! Sandbox child for tests of sandboxed plugins: test binaries can't re-launch
+//! themselves as one, so they point `plugin_sandbox::set_child_executable` here.
+
+fn main() {
+    engine_core::plugin_sandbox::run_child_if_requested();
+    eprintln!("usage: sandbox_child {} <port> <library>", engine_core::plugin_sandbox::SANDBOX_CHILD_ARG);
+    std::process::exit(2);
+}
@@ -0,0 +1,305 @@
+// crates/engine_test/src/lib.rs
+//! Headless plugin test harness.
+//!
+//! Loads a plugin cdylib through the real `PluginManager`, builds the default
+//! World, and drives `on_update` with scripted `InputState`s at a fixed `dt`,
+//! without a window, renderer or event loop. Assertion helpers panic with a
+//! readable message, so they can be used directly in `#[test]` functions:
+//!
+//! ```ignore
+//! let mut h = Harness::game_plugin();
+//! h.run(&InputScript::new().hold(h.press(&["MoveRight"]), 60));
+//! h.assert_player_reached_x(300.0);
+//! ```
+
+mod script;
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use engine_core::plugin_manager::{PluginManager, PluginRuntimeState};
+use engine_core::message_bus::{self, BusMessage};
+use engine_core::replay::{self, Player, Recorder};
+use engine_core::{host, input, logging, scene};
+use engine_ecs::World;
+use engine_shared::input_types::{ActionId, InputState};
+use engine_shared::plugin_api::{FFIResult, HostInterface};
+use engine_shared::{CEnemy, CPlayer, CTransform};
+use glam::Vec2;
+
+pub use script::InputScript;
+
+/// Same fixed step as the editor's `EngineLoop`.
+pub const DEFAULT_DT: f32 = 1.0 / 60.0;
+
+/// Name the harness publishes and subscribes under on the message bus.
+pub const BUS_NAME: &str = "engine_test";
+
+/// Host services (action registry, cvars, console commands) are process-wide,
+/// so harnesses in the same test binary take turns.
+static EXCLUSIVE: Mutex<()> = Mutex::new(());
+
+pub struct Harness {
+    pub world: World,
+    manager: PluginManager,
+    host_interface: HostInterface,
+    pub dt: f32,
+    /// Ticks run so far.
+    pub tick: u64,
+    /// Input of the previous tick, for the edge masks.
+    last_input: InputState,
+    recorder: Option<Recorder>,
+    _exclusive: MutexGuard<'static, ()>,
+}
+
+impl Harness {
+    /// Loads `library` (a cdylib path or `.toml` manifest) into a fresh default World.
+    pub fn new(library: impl AsRef<Path>) -> Result<Self, String> {
+        // A test that panicked mid-run poisons the lock; the next harness starts over anyway.
+        let exclusive = EXCLUSIVE.lock().unwrap_or_else(|e| e.into_inner());
+        logging::init();
+
+        let source = library.as_ref().to_string_lossy().into_owned();
+        let mut manager = PluginManager::new(&[source]);
+        if let Some((name, msg)) = manager.errors().first() {
+            return Err(format!("Plugin '{name}' failed to load: {msg}"));
+        }
+        if manager.plugins.is_empty() {
+            return Err(format!("No plugin found at {}", library.as_ref().display()));
+        }
+
+        let mut world = World::new();
+        scene::setup_default_world(&mut world);
+        let host_interface = host::create_interface();
+        manager.initial_load(&mut world, &host_interface);
+        // Nothing applies suggested keys without a window; drop them so they don't pile up.
+        input::ffi::take_pending_bindings();
+
+        Ok(Self {
+            world,
+            manager,
+            host_interface,
+            dt: DEFAULT_DT,
+            tick: 0,
+            last_input: InputState::default(),
+            recorder: None,
+            _exclusive: exclusive,
+        })
+    }
+
+    /// Loads the workspace's `game_plugin`. Panics if it hasn't been built.
+    pub fn game_plugin() -> Self {
+        let library = locate_library("game_plugin").unwrap_or_else(|| {
+            panic!("game_plugin library not found; build it first (`cargo build -p game_plugin`)")
+        });
+        Self::new(&library).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// ID of a plugin-registered action. Panics if no plugin registered `name`.
+    pub fn action(&self, name: &str) -> ActionId {
+        input::GLOBAL_REGISTRY
+            .get()
+            .and_then(|reg| reg.lock().ok()?.get_id(name))
+            .unwrap_or_else(|| panic!("Action '{name}' is not registered"))
+    }
+
+    /// An `InputState` with the named actions held.
+    pub fn press(&self, actions: &[&str]) -> InputState {
+        let mut input = InputState::default();
+        for name in actions {
+            input.digital_mask.insert(self.action(name));
+        }
+        input
+    }
+
+    /// Runs one fixed-step tick. The edge masks (`just_pressed`) are filled in
+    /// from the previous tick's input.
+    pub fn step(&mut self, input: &InputState) {
+        let mut input = *input;
+        input.set_edges_from(&self.last_input);
+        if let Some(recorder) = &mut self.recorder {
+            input = recorder.record(self.tick, &input);
+        }
+        self.tick_with(&input);
+    }
+
+    /// One tick with `input` exactly as given, edge masks included.
+    fn tick_with(&mut self, input: &InputState) {
+        logging::begin_frame();
+        self.manager.update(&mut self.world, input, self.dt);
+        self.last_input = *input;
+        self.tick += 1;
+    }
+
+    /// Runs `frames` ticks with no input.
+    pub fn idle(&mut self, frames: u32) {
+        self.run(&InputScript::new().idle(frames));
+    }
+
+    pub fn run(&mut self, script: &InputScript) {
+        for input in script.iter() {
+            self.step(input);
+        }
+    }
+
+    /// Simulated seconds so far.
+    pub fn elapsed(&self) -> f32 {
+        self.tick as f32 * self.dt
+    }
+
+    /// Reloads every plugin from disk (as F5 does), keeping the World.
+    pub fn reload(&mut self) -> bool {
+        self.manager.try_hot_reload(&mut self.world, &self.host_interface)
+    }
+
+    // --- Replays ---
+
+    /// Records every following tick to `path`, as `--record` does, until `finish_recording`.
+    pub fn record(&mut self, path: impl Into<PathBuf>) {
+        let recorder = Recorder::start(path.into(), self.dt, &self.world, &mut self.manager);
+        replay::publish_seed(recorder.seed());
+        self.recorder = Some(recorder);
+    }
+
+    /// Writes the recording started by `record`.
+    pub fn finish_recording(&mut self) -> Result<(), String> {
+        let recorder = self.recorder.take().ok_or("Not recording")?;
+        recorder.finish()
+    }
+
+    /// Rewinds to the start of the recording at `path` and runs all of its
+    /// ticks, as `--replay` does. Returns the number of ticks replayed.
+    pub fn replay(&mut self, path: impl AsRef<Path>) -> Result<usize, String> {
+        let mut player = Player::load(path.as_ref())?;
+        self.dt = player.sim_dt();
+        player.restore(&mut self.world, &self.host_interface, &mut self.manager);
+        replay::publish_seed(player.seed());
+
+        let mut ticks = 0;
+        while let Some(input) = player.next_input() {
+            self.tick_with(&input);
+            ticks += 1;
+        }
+        Ok(ticks)
+    }
+
+    // --- Message bus ---
+
+    /// Publishes as `BUS_NAME`; plugins see it on the next tick.
+    pub fn publish(&self, topic: &str, schema: u64, payload: &[u8]) {
+        message_bus::publish(BUS_NAME, topic, schema, payload);
+    }
+
+    pub fn subscribe(&self, topic: &str) {
+        message_bus::subscribe(BUS_NAME, topic);
+    }
+
+    /// Everything delivered to the harness so far.
+    pub fn take_messages(&self) -> Vec<BusMessage> {
+        message_bus::take_inbox(BUS_NAME)
+    }
+
+    // --- Queries ---
+
+    pub fn player_position(&self) -> Option<Vec2> {
+        let (&player, _) = self.world.query::<CPlayer>()?.iter().next()?;
+        self.world.get_component::<CTransform>(player).map(|t| t.pos)
+    }
+
+    pub fn enemy_count(&self) -> usize {
+        self.world.query::<CEnemy>().map_or(0, |q| q.iter().count())
+    }
+
+    /// Current plugin state blob (envelope included), as the host would save it on reload.
+    pub fn save_state(&mut self) -> Vec<u8> {
+        let plugin = self.backend();
+        let mut buffer = vec![0u8; plugin.get_state_len()];
+        let res = plugin.save_state(&mut buffer);
+        assert_eq!(res, FFIResult::Success, "save_state failed");
+        buffer
+    }
+
+    pub fn load_state(&mut self, mut bytes: Vec<u8>) {
+        let res = self.backend().load_state(&mut bytes);
+        assert_eq!(res, FFIResult::Success, "load_state failed");
+    }
+
+    fn backend(&mut self) -> &mut dyn engine_core::plugin_manager::PluginBackend {
+        self.manager.plugins[0]
+            .plugin
+            .as_deref_mut()
+            .expect("plugin is not loaded")
+    }
+
+    // --- Assertions ---
+
+    /// Every plugin is still running (no panic, no failed reload).
+    pub fn assert_running(&self) {
+        for plugin in &self.manager.plugins {
+            if let PluginRuntimeState::PausedError(msg) = &plugin.runtime_state {
+                panic!(
+                    "Plugin '{}' paused at tick {}: {msg}",
+                    plugin.manifest.name, self.tick
+                );
+            }
+        }
+    }
+
+    /// Why the first plugin is paused, if it is (panic, crashed sandbox, failed reload).
+    pub fn paused_error(&self) -> Option<String> {
+        match &self.manager.plugins.first()?.runtime_state {
+            PluginRuntimeState::PausedError(msg) => Some(msg.clone()),
+            PluginRuntimeState::Running => None,
+        }
+    }
+
+    /// The player is at or beyond `x` (to the right for positive `x`, left for negative).
+    pub fn assert_player_reached_x(&self, x: f32) {
+        self.assert_running();
+        let pos = self.player_position().expect("World has no player");
+        let reached = if x >= 0.0 { pos.x >= x } else { pos.x <= x };
+        assert!(reached, "Player at x = {} after {} ticks, expected to reach {x}", pos.x, self.tick);
+    }
+
+    /// At least `n` enemies exist.
+    pub fn assert_enemies_spawned(&self, n: usize) {
+        self.assert_running();
+        let count = self.enemy_count();
+        assert!(
+            count >= n,
+            "{count} enemies after {:.2}s ({} ticks), expected at least {n}",
+            self.elapsed(),
+            self.tick
+        );
+    }
+
+    /// Saving, loading the blob back and saving again yields the same bytes.
+    pub fn assert_state_survives_save_load(&mut self) {
+        self.assert_running();
+        let saved = self.save_state();
+        self.load_state(saved.clone());
+        let resaved = self.save_state();
+        assert_eq!(saved, resaved, "Plugin state changed across a save/load round trip");
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        self.manager.shutdown(&mut self.world);
+        message_bus::remove_subscriber(BUS_NAME);
+    }
+}
+
+/// Finds the cdylib built for `crate_name` next to the running test binary
+/// (`target/<profile>/deps`) or one level up (`target/<profile>`).
+pub fn locate_library(crate_name: &str) -> Option<PathBuf> {
+    use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+
+    let file = format!("{DLL_PREFIX}{crate_name}{DLL_SUFFIX}");
+    let exe = std::env::current_exe().ok()?;
+    exe.ancestors()
+        .skip(1)
+        .take(2)
+        .map(|dir| dir.join(&file))
+        .find(|path| path.is_file())
+}
@@ -0,0 +1,42 @@
+// crates/engine_test/src/script.rs
+//! Scripted input: a list of `InputState`s, one per tick.
+
+use engine_shared::input_types::InputState;
+
+#[derive(Debug, Clone, Default)]
+pub struct InputScript {
+    /// `(input, frames)` segments, played in order.
+    segments: Vec<(InputState, u32)>,
+}
+
+impl InputScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Holds `input` for `frames` ticks.
+    pub fn hold(mut self, input: InputState, frames: u32) -> Self {
+        self.segments.push((input, frames));
+        self
+    }
+
+    /// No input for `frames` ticks.
+    pub fn idle(self, frames: u32) -> Self {
+        self.hold(InputState::default(), frames)
+    }
+
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|&(_, n)| n as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// One `InputState` per tick.
+    pub fn iter(&self) -> impl Iterator<Item = &InputState> {
+        self.segments
+            .iter()
+            .flat_map(|(input, n)| std::iter::repeat_n(input, *n as usize))
+    }
+}
@@ -0,0 +1,140 @@
+// crates/engine_test/tests/game_plugin.rs
+//! Gameplay checks against the real `game_plugin` cdylib, run headless.
+
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use engine_core::input;
+use engine_shared::input_types::InputState;
+use engine_shared::CTransform;
+use engine_test::{locate_library, Harness, InputScript};
+use glam::Vec2;
+
+#[test]
+fn player_moves_right_while_held() {
+    let mut h = Harness::game_plugin();
+    let start = h.player_position().expect("plugin spawns a player");
+
+    let right = h.press(&["MoveRight"]);
+    h.run(&InputScript::new().hold(right, 30).idle(10));
+
+    // 0.5s at the default 600 units/s, clamped by the 2000x2000 bounds.
+    h.assert_player_reached_x(start.x + 250.0);
+}
+
+#[test]
+fn dash_outruns_walking() {
+    let mut walk = Harness::game_plugin();
+    let right = walk.press(&["MoveRight"]);
+    walk.run(&InputScript::new().hold(right, 20));
+    let walked = walk.player_position().unwrap().x;
+    drop(walk);
+
+    let mut dash = Harness::game_plugin();
+    let right_dash = dash.press(&["MoveRight", "Dash"]);
+    dash.run(&InputScript::new().hold(right_dash, 20));
+    dash.assert_player_reached_x(walked + 1.0);
+}
+
+#[test]
+fn enemies_spawn_over_time() {
+    let mut h = Harness::game_plugin();
+    assert_eq!(h.enemy_count(), 0);
+
+    // `enemy.spawn_interval` defaults to 2s: spawns at ~2s, ~4s and ~6s.
+    h.idle(60 * 7);
+    h.assert_enemies_spawned(3);
+}
+
+#[test]
+fn state_survives_save_load() {
+    let mut h = Harness::game_plugin();
+    h.idle(45);
+    h.assert_state_survives_save_load();
+}
+
+#[test]
+fn action_ids_survive_hot_reload() {
+    const ACTIONS: [&str; 7] = ["MoveUp", "MoveDown", "MoveLeft", "MoveRight", "Dash", "ZoomIn", "ZoomOut"];
+
+    // A private copy, so touching it (F5 only reloads a changed library) leaves the build alone.
+    let built = locate_library("game_plugin").expect("game_plugin library not built");
+    let dir = std::env::temp_dir().join(format!("engine_test_reload_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let library = dir.join(built.file_name().unwrap());
+    fs::copy(&built, &library).unwrap();
+
+    let mut h = Harness::new(&library).unwrap_or_else(|e| panic!("{e}"));
+    let before = ACTIONS.map(|name| h.action(name));
+    h.idle(1);
+    let touched = SystemTime::now() + Duration::from_secs(2);
+    fs::File::options().write(true).open(&library).unwrap().set_modified(touched).unwrap();
+    assert!(h.reload());
+    h.assert_running();
+    assert_eq!(ACTIONS.map(|name| h.action(name)), before);
+
+    // The reloaded plugin suggests its keys again, for the same IDs.
+    let move_right = before[3];
+    let suggested = input::ffi::take_pending_bindings();
+    let key_d = suggested
+        .iter()
+        .find(|p| p.binding.name().as_deref() == Some("KeyD"))
+        .expect("reloaded plugin suggests KeyD again");
+    assert_eq!(key_d.action, move_right);
+
+    // And reads input by them: the ID seen before the reload still moves right.
+    let start = h.player_position().unwrap();
+    let mut right = InputState::default();
+    right.digital_mask.insert(move_right);
+    h.run(&InputScript::new().hold(right, 30));
+    h.assert_player_reached_x(start.x + 250.0);
+}
+
+#[test]
+fn other_plugins_can_grant_score() {
+    const SCORE_SCHEMA: u64 = 0x5C0E_0001;
+
+    let mut h = Harness::game_plugin();
+    h.subscribe("gameplay.score_changed");
+    h.publish("gameplay.grant_score", SCORE_SCHEMA, &5u32.to_le_bytes());
+
+    // Delivered to the plugin on the next tick; its reply on the one after.
+    h.idle(2);
+    h.assert_running();
+    let replies = h.take_messages();
+    assert_eq!(replies.len(), 1, "expected one score_changed, got {replies:?}");
+    assert_eq!(replies[0].schema, SCORE_SCHEMA);
+    assert_eq!(replies[0].payload, 5u32.to_le_bytes());
+    assert_eq!(replies[0].sender, "game_plugin");
+}
+
+/// Every entity with a transform and where it is, by entity.
+fn transforms(h: &Harness) -> Vec<(u64, Vec2)> {
+    let mut all: Vec<_> = h
+        .world
+        .query::<CTransform>()
+        .map(|set| set.iter().map(|(e, t)| (e.to_bits(), t.pos)).collect())
+        .unwrap_or_default();
+    all.sort_by_key(|&(bits, _)| bits);
+    all
+}
+
+#[test]
+fn replay_reproduces_the_recorded_world() {
+    let path = std::env::temp_dir().join(format!("engine_test_replay_{}.replay", std::process::id()));
+    let mut h = Harness::game_plugin();
+    h.idle(30);
+
+    // Long enough for the 2s enemy spawner to fire, with movement first.
+    h.record(&path);
+    let (right, up) = (h.press(&["MoveRight"]), h.press(&["MoveUp", "Dash"]));
+    h.run(&InputScript::new().hold(right, 40).hold(up, 20).idle(100));
+    h.finish_recording().unwrap();
+    let recorded = transforms(&h);
+    assert!(h.enemy_count() > 0);
+
+    assert_eq!(h.replay(&path), Ok(160));
+    h.assert_running();
+    assert_eq!(transforms(&h), recorded);
+    fs::remove_file(&path).unwrap();
+}
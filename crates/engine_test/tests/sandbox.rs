@@ -0,0 +1,80 @@
+// crates/engine_test/tests/sandbox.rs
+//! `game_plugin` in a sandbox child process: a killed child pauses the plugin,
+//! and a reload (F5) restarts it from its last checkpoint.
+#![cfg(target_os = "linux")]
+
+use std::fs;
+use std::path::PathBuf;
+
+use engine_core::plugin_sandbox::{self, SANDBOX_CHILD_ARG};
+use engine_test::{locate_library, Harness};
+
+const SCORE_SCHEMA: u64 = 0x5C0E_0001;
+
+/// A manifest loading the workspace's `game_plugin` with `sandbox = true`.
+fn sandboxed_manifest() -> PathBuf {
+    let library = locate_library("game_plugin").expect("game_plugin library not built");
+    let dir = std::env::temp_dir().join(format!("engine_test_sandbox_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let manifest = dir.join("game_plugin.toml");
+    let text = format!("name = \"game_plugin\"\nlibrary = '{}'\nsandbox = true\n", library.display());
+    fs::write(&manifest, text).unwrap();
+    manifest
+}
+
+/// SIGKILLs every sandbox child of this process, as a segfault in the plugin would end it.
+fn kill_sandbox_children() -> usize {
+    let me = std::process::id().to_string();
+    let mut killed = 0;
+    for entry in fs::read_dir("/proc").unwrap().flatten() {
+        let pid = entry.file_name().to_string_lossy().into_owned();
+        let (Ok(stat), Ok(cmdline)) = (
+            fs::read_to_string(entry.path().join("stat")),
+            fs::read_to_string(entry.path().join("cmdline")),
+        ) else {
+            continue;
+        };
+        // `pid (comm) state ppid ...`; comm may contain spaces, so split after ')'.
+        let ppid = stat.rsplit(')').next().and_then(|rest| rest.split_whitespace().nth(1));
+        if ppid == Some(me.as_str()) && cmdline.split('\0').any(|arg| arg == SANDBOX_CHILD_ARG) {
+            let status = std::process::Command::new("kill").args(["-9", &pid]).status().unwrap();
+            killed += status.success() as usize;
+        }
+    }
+    killed
+}
+
+fn score_replies(h: &Harness) -> Vec<u32> {
+    h.take_messages()
+        .into_iter()
+        .filter(|m| m.schema == SCORE_SCHEMA)
+        .map(|m| u32::from_le_bytes(m.payload[..4].try_into().unwrap()))
+        .collect()
+}
+
+#[test]
+fn killed_child_pauses_the_plugin_and_reload_restores_its_state() {
+    plugin_sandbox::set_child_executable(env!("CARGO_BIN_EXE_sandbox_child"));
+    let mut h = Harness::new(sandboxed_manifest()).unwrap_or_else(|e| panic!("{e}"));
+    h.subscribe("gameplay.score_changed");
+
+    h.publish("gameplay.grant_score", SCORE_SCHEMA, &5u32.to_le_bytes());
+    h.idle(3);
+    h.assert_running();
+    assert_eq!(score_replies(&h), [5]);
+    let position = h.player_position();
+
+    assert_eq!(kill_sandbox_children(), 1);
+    h.idle(1);
+    let error = h.paused_error().expect("plugin keeps running after its process died");
+    assert!(error.contains("crashed"), "unexpected error: {error}");
+    // The failed tick never reached the host World.
+    assert_eq!(h.player_position(), position);
+
+    // F5: a new child, with the score from the last tick before the crash.
+    assert!(h.reload());
+    h.publish("gameplay.grant_score", SCORE_SCHEMA, &5u32.to_le_bytes());
+    h.idle(3);
+    h.assert_running();
+    assert_eq!(score_replies(&h), [10]);
+}
@@ -0,0 +1,61 @@
+// crates/game_plugin/src/actions.rs
+//
+// Actions this plugin reads, registered with the host on every load together
+// with their suggested default keys and gamepad buttons. The host keeps IDs stable across hot
+// reloads and never overrides a key the player (or engine) already bound.
+
+use engine_shared::{
+    input_types::{ActionId, ACTION_NOT_FOUND},
+    plugin_api::HostInterface,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct GameActions {
+    pub move_up: ActionId,
+    pub move_down: ActionId,
+    pub move_left: ActionId,
+    pub move_right: ActionId,
+    /// Held: move at `player.dash_multiplier` times normal speed.
+    pub dash: ActionId,
+    /// One wheel step: zoom the camera in / out.
+    pub zoom_in: ActionId,
+    pub zoom_out: ActionId,
+}
+
+impl Default for GameActions {
+    fn default() -> Self {
+        Self {
+            move_up: ACTION_NOT_FOUND,
+            move_down: ACTION_NOT_FOUND,
+            move_left: ACTION_NOT_FOUND,
+            move_right: ACTION_NOT_FOUND,
+            dash: ACTION_NOT_FOUND,
+            zoom_in: ACTION_NOT_FOUND,
+            zoom_out: ACTION_NOT_FOUND,
+        }
+    }
+}
+
+impl GameActions {
+    pub fn register(host: &HostInterface) -> Self {
+        let register = |name: &str, defaults: &[&str]| {
+            let id = (host.register_action)(name.as_ptr(), name.len());
+            if id != ACTION_NOT_FOUND {
+                for default in defaults {
+                    (host.bind_default_key)(id, default.as_ptr(), default.len());
+                }
+            }
+            id
+        };
+
+        Self {
+            move_up: register("MoveUp", &["KeyW"]),
+            move_down: register("MoveDown", &["KeyS"]),
+            move_left: register("MoveLeft", &["KeyA"]),
+            move_right: register("MoveRight", &["KeyD"]),
+            dash: register("Dash", &["Space", "Pad.South"]),
+            zoom_in: register("ZoomIn", &["Mouse.WheelUp"]),
+            zoom_out: register("ZoomOut", &["Mouse.WheelDown"]),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// crates/game_plugin/src/commands.rs
+//
+// Console commands contributed by this plugin. `user_data` is the plugin's
+// `MyGame` state pointer; the host drops the commands before unloading us.
+
+use std::ffi::c_void;
+
+use engine_shared::plugin_api::{HostContext, HostInterface};
+
+use crate::log::log_warn;
+use crate::state::MyGame;
+
+pub fn register(host: &HostInterface, state: *mut c_void) {
+    let name = "spawn_enemy";
+    let desc = "spawn_enemy [x y] - spawn an enemy (default: world origin)";
+    (host.register_command)(
+        name.as_ptr(),
+        name.len(),
+        desc.as_ptr(),
+        desc.len(),
+        spawn_enemy,
+        state,
+    );
+}
+
+extern "C" fn spawn_enemy(
+    user_data: *mut c_void,
+    ctx: *mut HostContext,
+    args_ptr: *const u8,
+    args_len: usize,
+) {
+    let _ = std::panic::catch_unwind(|| {
+        if user_data.is_null() || ctx.is_null() {
+            return;
+        }
+        let game = unsafe { &*(user_data as *const MyGame) };
+        let args = unsafe { std::slice::from_raw_parts(args_ptr, args_len) };
+        let args = std::str::from_utf8(args).unwrap_or("");
+
+        let coords: Vec<f32> = args
+            .split_whitespace()
+            .filter_map(|a| a.parse().ok())
+            .collect();
+        let (x, y) = match coords[..] {
+            [] => (0.0, 0.0),
+            [x, y] => (x, y),
+            _ => {
+                log_warn!("usage: spawn_enemy [x y]");
+                return;
+            }
+        };
+
+        if let Some(spawn_fn) = game.spawn_fn {
+            spawn_fn(ctx, x, y);
+        }
+    });
+}
@@ -0,0 +1,77 @@
+// crates/game_plugin/src/debug_ui.rs
+//
+// The plugin's debug panel (host window "🔌 game_plugin", toggled with F2),
+// plus a small safe wrapper over the host's `DebugUi` vtable.
+
+use engine_ecs::World;
+use engine_shared::{plugin_api::DebugUi, CEnemy, CPlayer, CTransform, HostContext};
+
+use crate::state::MyGame;
+
+/// Samples kept for the enemy-count plot.
+pub const HISTORY_LEN: usize = 240;
+
+pub struct Ui<'a>(&'a DebugUi);
+
+impl<'a> Ui<'a> {
+    pub fn new(raw: &'a DebugUi) -> Self {
+        Self(raw)
+    }
+
+    pub fn label(&self, text: &str) {
+        (self.0.label)(self.0.ctx, text.as_ptr(), text.len());
+    }
+
+    pub fn heading(&self, text: &str) {
+        (self.0.heading)(self.0.ctx, text.as_ptr(), text.len());
+    }
+
+    pub fn separator(&self) {
+        (self.0.separator)(self.0.ctx);
+    }
+
+    pub fn button(&self, text: &str) -> bool {
+        (self.0.button)(self.0.ctx, text.as_ptr(), text.len())
+    }
+
+    pub fn checkbox(&self, text: &str, value: &mut bool) -> bool {
+        (self.0.checkbox)(self.0.ctx, text.as_ptr(), text.len(), value)
+    }
+
+    pub fn slider_f32(&self, text: &str, value: &mut f32, min: f32, max: f32) -> bool {
+        (self.0.slider_f32)(self.0.ctx, text.as_ptr(), text.len(), value, min, max)
+    }
+
+    pub fn plot_lines(&self, text: &str, values: &[f32]) {
+        (self.0.plot_lines)(self.0.ctx, text.as_ptr(), text.len(), values.as_ptr(), values.len());
+    }
+}
+
+pub fn draw(game: &mut MyGame, world: &mut World, ui: &Ui) {
+    let enemies = world.query::<CEnemy>().map_or(0, |q| q.iter().count());
+    let player_pos = world
+        .query::<CPlayer>()
+        .and_then(|q| q.iter().next().map(|(e, _)| *e))
+        .and_then(|e| world.get_component::<CTransform>(e))
+        .map(|t| t.pos);
+
+    ui.heading("Gameplay");
+    ui.label(&format!("Score: {}", game.score));
+    match player_pos {
+        Some(pos) => ui.label(&format!("Player: ({:.0}, {:.0})", pos.x, pos.y)),
+        None => ui.label("Player: <none>"),
+    }
+    ui.separator();
+
+    ui.label(&format!("Enemies: {enemies}"));
+    let history: Vec<f32> = game.enemy_history.iter().copied().collect();
+    ui.plot_lines("Enemy count", &history);
+
+    ui.checkbox("Pause spawning", &mut game.spawn_paused);
+    ui.slider_f32("Next spawn in", &mut game.spawn_timer, 0.0, 10.0);
+    if ui.button("Spawn enemy at player") {
+        if let (Some(spawn_fn), Some(pos)) = (game.spawn_fn, player_pos) {
+            spawn_fn(world as *mut World as *mut HostContext, pos.x, pos.y);
+        }
+    }
+}
@@ -1,13 +1,26 @@
 // crates/game_plugin/src/lib.rs
 
+mod actions;
+mod commands;
+mod debug_ui;
+mod log;
+mod messages;
 mod systems;
 mod state;
 mod shims; // <--- The new module
+mod tuning;
 
 use std::ffi::c_void;
-use engine_shared::plugin_api::PluginApi;
+use engine_shared::plugin_api::{AbiInfo, PluginApi};
 use crate::state::MyGame;
 
+// ABI handshake: the engine refuses to call `_create_game` unless this
+// matches its own version and vtable sizes.
+#[no_mangle]
+pub extern "C" fn _engine_api_version() -> AbiInfo {
+    AbiInfo::current()
+}
+
 // The main entry point required by the engine.
 // It maps the PluginApi vtable to the functions in 'shims.rs'.
 #[no_mangle]
@@ -19,11 +32,14 @@ pub extern "C" fn _create_game() -> PluginApi {
         on_load: shims::on_load,
         on_update: shims::on_update,
         on_unload: shims::on_unload,
+        on_before_reload: Some(shims::on_before_reload),
+        on_after_reload: Some(shims::on_after_reload),
         get_state_len: shims::get_state_len,
         save_state: shims::save_state,
         load_state: shims::load_state,
         drop_state: shims::drop_state,
         get_schema_hash: shims::get_hash,
         get_state_version: shims::get_state_version,
+        on_debug_ui: Some(shims::on_debug_ui),
     }
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+// crates/game_plugin/src/log.rs
+//
+// Thin wrapper over `HostInterface::log` so gameplay code can log without
+// carrying the host vtable around. Falls back to stdout before the host is bound.
+
+use std::sync::Mutex;
+
+use engine_shared::plugin_api::LogLevel;
+
+type HostLogFn = extern "C" fn(u32, *const u8, usize, *const u8, usize);
+
+static HOST_LOG: Mutex<Option<HostLogFn>> = Mutex::new(None);
+
+const TARGET: &str = "game_plugin";
+
+pub fn bind(log_fn: HostLogFn) {
+    *HOST_LOG.lock().unwrap_or_else(|e| e.into_inner()) = Some(log_fn);
+}
+
+pub fn write(level: LogLevel, msg: &str) {
+    let log_fn = *HOST_LOG.lock().unwrap_or_else(|e| e.into_inner());
+    match log_fn {
+        Some(f) => f(level as u32, TARGET.as_ptr(), TARGET.len(), msg.as_ptr(), msg.len()),
+        None => println!("[{level:?}] {TARGET}: {msg}"),
+    }
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => { $crate::log::write(engine_shared::plugin_api::LogLevel::Debug, &format!($($arg)*)) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::log::write(engine_shared::plugin_api::LogLevel::Warn, &format!($($arg)*)) };
+}
+
+pub(crate) use {log_debug, log_warn};
@@ -0,0 +1,83 @@
+// crates/game_plugin/src/messages.rs
+//
+// Message bus topics this plugin speaks. Other plugins (a debug tool, AI, a
+// script) grant score by publishing `gameplay.grant_score` with a u32 amount;
+// we answer with `gameplay.score_changed` carrying the new total. Both payloads
+// are little-endian u32s tagged with `SCORE_SCHEMA`.
+
+use engine_shared::plugin_api::{BusMessageHeader, HostInterface};
+
+use crate::log::log_warn;
+use crate::state::MyGame;
+
+pub const GRANT_SCORE: &str = "gameplay.grant_score";
+pub const SCORE_CHANGED: &str = "gameplay.score_changed";
+pub const SCORE_SCHEMA: u64 = 0x5C0E_0001;
+
+type PublishFn = extern "C" fn(*const u8, usize, u64, *const u8, usize) -> bool;
+type ReceiveFn = extern "C" fn(*mut BusMessageHeader, *mut u8, usize) -> bool;
+
+/// The bus functions we use, bound on every load.
+#[derive(Clone, Copy)]
+pub struct Bus {
+    publish: PublishFn,
+    receive: ReceiveFn,
+}
+
+pub struct Message {
+    pub topic: String,
+    pub schema: u64,
+    pub payload: Vec<u8>,
+}
+
+impl Bus {
+    /// Binds the bus and subscribes to our topics (the host keeps subscriptions
+    /// across reloads, so subscribing again is harmless).
+    pub fn bind(host: &HostInterface) -> Self {
+        (host.bus_subscribe)(GRANT_SCORE.as_ptr(), GRANT_SCORE.len());
+        Self {
+            publish: host.bus_publish,
+            receive: host.bus_receive,
+        }
+    }
+
+    pub fn publish(&self, topic: &str, schema: u64, payload: &[u8]) {
+        (self.publish)(topic.as_ptr(), topic.len(), schema, payload.as_ptr(), payload.len());
+    }
+
+    /// Everything delivered to us this tick.
+    pub fn drain(&self) -> Vec<Message> {
+        let mut messages = Vec::new();
+        let mut buf = vec![0u8; 256];
+        loop {
+            let mut header = BusMessageHeader::default();
+            if (self.receive)(&mut header, buf.as_mut_ptr(), buf.len()) {
+                let (topic, payload) = buf[..header.topic_len + header.payload_len].split_at(header.topic_len);
+                messages.push(Message {
+                    topic: String::from_utf8_lossy(topic).into_owned(),
+                    schema: header.schema,
+                    payload: payload.to_vec(),
+                });
+            } else if header.topic_len == 0 {
+                return messages;
+            } else {
+                buf.resize(header.topic_len + header.payload_len, 0);
+            }
+        }
+    }
+}
+
+pub fn handle(game: &mut MyGame, bus: &Bus) {
+    for message in bus.drain() {
+        match (message.topic.as_str(), message.schema, message.payload.as_slice()) {
+            (GRANT_SCORE, SCORE_SCHEMA, &[a, b, c, d]) => {
+                game.score = game.score.saturating_add(u32::from_le_bytes([a, b, c, d]));
+                bus.publish(SCORE_CHANGED, SCORE_SCHEMA, &game.score.to_le_bytes());
+            }
+            (topic, schema, payload) => log_warn!(
+                "Ignoring bus message '{topic}' (schema {schema:#x}, {} bytes)",
+                payload.len()
+            ),
+        }
+    }
+}
@@ -3,18 +3,22 @@
 use std::ffi::c_void;
 use std::io::Cursor;
 
-use bincode;
 use engine_ecs::World;
 use engine_shared::{
+    CEnemy,
     input_types::InputState,
     plugin_api::{
-        FFIResult, FFIBuffer, HostContext, HostInterface, StateEnvelope,
+        DebugUi, FFIResult, FFIBuffer, HostContext, HostInterface, StateEnvelope,
         SNAPSHOT_MAGIC_HEADER, CURRENT_SCHEMA_HASH, CURRENT_STATE_VERSION,
     },
 };
 
 use crate::state::{MyGame, setup_scene};
 use crate::systems;
+use crate::commands;
+use crate::debug_ui;
+use crate::messages;
+use crate::tuning::Tuning;
 
 fn catch_ffi_panic<F>(f: F) -> FFIResult
 where
@@ -42,6 +46,7 @@ pub extern "C" fn on_load(
             let world = &mut *(ctx as *mut World);
 
             game.bind_host_resources(host);
+            commands::register(host, state);
 
             if !game.scene_initialized {
                 setup_scene(world);
@@ -69,12 +74,31 @@ pub extern "C" fn on_update(
             let world = &mut *(ctx as *mut World);
             let input = &*input;
 
-            systems::player::update_player(world, input, dt, &game.actions);
-            systems::camera::update_camera(world, dt);
+            let tuning = game.cvar_get.map(Tuning::read).unwrap_or_default();
 
-            if let Some(spawn_fn) = game.spawn_fn {
+            if let Some(bus) = game.bus {
+                messages::handle(game, &bus);
+            }
+
+            systems::player::update_player(world, input, dt, &game.actions, &tuning);
+            systems::camera::zoom_camera(world, input, &game.actions);
+            systems::camera::update_camera(world, dt, tuning.camera_deadzone);
+
+            if let Some(spawn_fn) = game.spawn_fn.filter(|_| !game.spawn_paused) {
                 let ctx_ptr = world as *mut World as *mut HostContext;
-                systems::enemy::spawn_enemies(spawn_fn, ctx_ptr, &mut game.spawn_timer, dt);
+                systems::enemy::spawn_enemies(
+                    spawn_fn,
+                    ctx_ptr,
+                    &mut game.spawn_timer,
+                    tuning.spawn_interval,
+                    dt,
+                );
+            }
+
+            let enemies = world.query::<CEnemy>().map_or(0, |q| q.iter().count());
+            game.enemy_history.push_back(enemies as f32);
+            if game.enemy_history.len() > debug_ui::HISTORY_LEN {
+                game.enemy_history.pop_front();
             }
         }
 
@@ -82,10 +106,56 @@ pub extern "C" fn on_update(
     })
 }
 
+pub extern "C" fn on_debug_ui(
+    state: *mut c_void,
+    ctx: *mut HostContext,
+    ui: *const DebugUi,
+) -> FFIResult {
+    catch_ffi_panic(|| {
+        if state.is_null() || ctx.is_null() || ui.is_null() {
+            return FFIResult::Error;
+        }
+
+        unsafe {
+            let game = &mut *(state as *mut MyGame);
+            let world = &mut *(ctx as *mut World);
+            debug_ui::draw(game, world, &debug_ui::Ui::new(&*ui));
+        }
+
+        FFIResult::Success
+    })
+}
+
 pub extern "C" fn on_unload(_state: *mut c_void, _ctx: *mut HostContext) -> FFIResult {
+    crate::log::log_debug!("Unloading game plugin");
     FFIResult::Success
 }
 
+pub extern "C" fn on_before_reload(state: *mut c_void, _ctx: *mut HostContext) -> FFIResult {
+    catch_ffi_panic(|| {
+        if state.is_null() {
+            return FFIResult::Error;
+        }
+        let game = unsafe { &*(state as *mut MyGame) };
+        crate::log::log_debug!(
+            "Reloading (spawn timer {:.2}s, spawning {})",
+            game.spawn_timer,
+            if game.spawn_paused { "paused" } else { "active" }
+        );
+        FFIResult::Success
+    })
+}
+
+pub extern "C" fn on_after_reload(state: *mut c_void, _ctx: *mut HostContext) -> FFIResult {
+    catch_ffi_panic(|| {
+        if state.is_null() {
+            return FFIResult::Error;
+        }
+        crate::log::log_debug!("Reload complete");
+        FFIResult::Success
+    })
+}
+
 pub extern "C" fn get_state_len(state: *mut c_void) -> usize {
     if state.is_null() {
         return 0;
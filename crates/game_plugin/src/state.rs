@@ -1,12 +1,17 @@
 // crates/game_plugin/src/state.rs
 
+use std::collections::VecDeque;
+
 use engine_ecs::World;
 use engine_shared::{
     CCamera, CPlayer, CSprite, CTransform, CWorldBounds,
-    input_types::{ActionId, ACTION_NOT_FOUND},
     plugin_api::HostInterface,
 };
 use glam::Vec2;
+
+use crate::actions::GameActions;
+use crate::messages::Bus;
+use crate::tuning::{CvarGetFn, Tuning};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -15,11 +20,21 @@ pub struct MyGame {
     #[serde(default)]
     pub score: u32,
     #[serde(skip)]
-    pub actions: [ActionId; 4],
+    pub actions: GameActions,
     #[serde(skip)]
     pub spawn_fn: Option<extern "C" fn(*mut engine_shared::plugin_api::HostContext, f32, f32)>,
     #[serde(skip)]
+    pub cvar_get: Option<CvarGetFn>,
+    #[serde(skip)]
+    pub bus: Option<Bus>,
+    #[serde(skip)]
     pub scene_initialized: bool, 
+    /// Debug panel toggle: skip enemy spawning while set.
+    #[serde(skip)]
+    pub spawn_paused: bool,
+    /// Recent enemy counts for the debug panel plot (one sample per tick).
+    #[serde(skip)]
+    pub enemy_history: VecDeque<f32>,
 }
 
 impl Default for MyGame {
@@ -27,20 +42,25 @@ impl Default for MyGame {
         Self {
             spawn_timer: 2.0,
             score: 0,
-            actions: [ACTION_NOT_FOUND; 4],
+            actions: GameActions::default(),
             spawn_fn: None,
+            cvar_get: None,
+            bus: None,
             scene_initialized: false,
+            spawn_paused: false,
+            enemy_history: VecDeque::new(),
         }
     }
 }
 
 impl MyGame {
     pub fn bind_host_resources(&mut self, host: &HostInterface) {
-        self.actions[0] = (host.get_action_id)(b"MoveUp".as_ptr(), b"MoveUp".len());
-        self.actions[1] = (host.get_action_id)(b"MoveDown".as_ptr(), b"MoveDown".len());
-        self.actions[2] = (host.get_action_id)(b"MoveLeft".as_ptr(), b"MoveLeft".len());
-        self.actions[3] = (host.get_action_id)(b"MoveRight".as_ptr(), b"MoveRight".len());
+        crate::log::bind(host.log);
+        self.actions = GameActions::register(host);
         self.spawn_fn = Some(host.spawn_enemy);
+        self.cvar_get = Some(host.cvar_get_f64);
+        self.bus = Some(Bus::bind(host));
+        Tuning::register(host);
     }
 }
 
@@ -51,7 +71,7 @@ pub fn setup_scene(world: &mut World) {
     let has_bounds = world.query::<CWorldBounds>().map(|q| q.iter().count() > 0).unwrap_or(false);
     
     if !has_bounds {
-        println!("[DEBUG] Spawning CWorldBounds (2000x2000)");
+        crate::log::log_debug!("Spawning CWorldBounds (2000x2000)");
         let settings_entity = world.spawn();
         world.add_component(settings_entity, CWorldBounds {
             width: 2000.0,
@@ -62,7 +82,7 @@ pub fn setup_scene(world: &mut World) {
     // 2. Check for Player
     let has_player = world.query::<CPlayer>().map(|q| q.iter().count() > 0).unwrap_or(false);
     if !has_player {
-        println!("[DEBUG] Spawning Player");
+        crate::log::log_debug!("Spawning Player");
         let player = world.spawn();
         world.add_component(
             player,
@@ -78,7 +98,7 @@ pub fn setup_scene(world: &mut World) {
     // 3. Check for Camera
     let has_camera = world.query::<CCamera>().map(|q| q.iter().count() > 0).unwrap_or(false);
     if !has_camera {
-        println!("[DEBUG] Spawning Camera");
+        crate::log::log_debug!("Spawning Camera");
         let camera = world.spawn();
         world.add_component(camera, CTransform::default());
         world.add_component(camera, CCamera {
@@ -1,29 +1,32 @@
 // crates/game_plugin/src/systems/camera.rs
 
 use engine_ecs::World;
-use engine_shared::{CCamera, CPlayer, CTransform, CWorldBounds};
+use engine_shared::{CCamera, CPlayer, CTransform, CWorldBounds, InputState};
 use glam::Vec2;
 
+use super::{MAP_HEIGHT, MAP_WIDTH};
+use crate::actions::GameActions;
+
+const ZOOM_STEP: f32 = 1.1;
+const ZOOM_MIN: f32 = 0.5;
+const ZOOM_MAX: f32 = 3.0;
+
 const VIEWPORT_W: f32 = 1280.0;
 const VIEWPORT_H: f32 = 720.0;
 
-// [NEW] DEADZONE SETTINGS
-// The camera will NOT move as long as the player is within this box.
+// DEADZONE: the camera will NOT move as long as the player is within this box
+// (half-extents, from the `camera.deadzone_w` / `camera.deadzone_h` cvars).
 // This cures the "Treadmill Effect" because you can see yourself move.
-const DEADZONE_W: f32 = 100.0; // Player can move 100px left/right before camera follows
-const DEADZONE_H: f32 = 80.0;  // Player can move 80px up/down before camera follows
+pub fn update_camera(world: &mut World, dt: f32, deadzone: Vec2) {
+    let (deadzone_w, deadzone_h) = (deadzone.x, deadzone.y);
 
-pub fn update_camera(world: &mut World, dt: f32) {
     // 1. Fetch Map Bounds (Centered)
-    let mut half_map_w = 1000.0;
-    let mut half_map_h = 1000.0;
+    let mut half_map_w = MAP_WIDTH / 2.0;
+    let mut half_map_h = MAP_HEIGHT / 2.0;
     
-    if let Some(bounds) = world.query::<CWorldBounds>() {
-        for (_, b) in bounds.iter() {
-            half_map_w = b.width / 2.0;
-            half_map_h = b.height / 2.0;
-            break;
-        }
+    if let Some((_, b)) = world.query::<CWorldBounds>().and_then(|q| q.iter().next()) {
+        half_map_w = b.width / 2.0;
+        half_map_h = b.height / 2.0;
     }
 
     // 2. Find Target (Player)
@@ -59,21 +62,21 @@ pub fn update_camera(world: &mut World, dt: f32) {
                 let mut desired_y = cam_transform.pos.y;
 
                 // X-Axis Deadzone
-                if delta.x > DEADZONE_W {
+                if delta.x > deadzone_w {
                     // Player pushed the Right edge -> Move Camera Right
-                    desired_x = player_pos.x - DEADZONE_W;
-                } else if delta.x < -DEADZONE_W {
+                    desired_x = player_pos.x - deadzone_w;
+                } else if delta.x < -deadzone_w {
                     // Player pushed the Left edge -> Move Camera Left
-                    desired_x = player_pos.x + DEADZONE_W;
+                    desired_x = player_pos.x + deadzone_w;
                 }
 
                 // Y-Axis Deadzone
-                if delta.y > DEADZONE_H {
+                if delta.y > deadzone_h {
                     // Player pushed Top edge
-                    desired_y = player_pos.y - DEADZONE_H;
-                } else if delta.y < -DEADZONE_H {
+                    desired_y = player_pos.y - deadzone_h;
+                } else if delta.y < -deadzone_h {
                     // Player pushed Bottom edge
-                    desired_y = player_pos.y + DEADZONE_H;
+                    desired_y = player_pos.y + deadzone_h;
                 }
 
                 let target_cam_pos = Vec2::new(desired_x, desired_y);
@@ -102,4 +105,21 @@ pub fn update_camera(world: &mut World, dt: f32) {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Mouse wheel zoom: one step per tick that carries a wheel action.
+pub fn zoom_camera(world: &mut World, input: &InputState, actions: &GameActions) {
+    let factor = if input.is_active(actions.zoom_in) {
+        ZOOM_STEP
+    } else if input.is_active(actions.zoom_out) {
+        1.0 / ZOOM_STEP
+    } else {
+        return;
+    };
+
+    if let Some(cameras) = world.query_mut::<CCamera>() {
+        for (_, camera) in cameras.iter_mut() {
+            camera.zoom = (camera.zoom * factor).clamp(ZOOM_MIN, ZOOM_MAX);
+        }
+    }
+}
@@ -10,6 +10,7 @@ use engine_shared::HostContext;
 /// - `spawn_fn`  : extern "C" fn(*mut HostContext, f32, f32) provided by the host.
 /// - `world_ctx` : opaque pointer to host context (actually a World on the host side).
 /// - `timer`     : spawn timer (mutable reference owned by plugin instance).
+/// - `interval`  : seconds between spawns (`enemy.spawn_interval` cvar).
 /// - `dt`        : delta time this frame.
 ///
 /// NOTE: The actual allocation / ECS mutation happens inside the host implementation
@@ -18,14 +19,15 @@ pub fn spawn_enemies(
     spawn_fn: extern "C" fn(*mut HostContext, f32, f32),
     world_ctx: *mut HostContext,
     timer: &mut f32,
+    interval: f32,
     dt: f32,
 ) {
     // Decrement timer
     *timer -= dt;
 
     if *timer <= 0.0 {
-        // reset timer (example cadence)
-        *timer = 2.0;
+        // reset timer
+        *timer = interval;
 
         // pseudo-random position based on dt (placeholder)
         let rx = (dt * 12345.0).rem_euclid(1280.0);
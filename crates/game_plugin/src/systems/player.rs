@@ -1,26 +1,34 @@
 // crates/game_plugin/src/systems/player.rs
 
 use engine_ecs::World;
-use engine_shared::{InputState, CPlayer, CTransform, ActionId, CWorldBounds};
+use engine_shared::{InputState, CPlayer, CTransform, CWorldBounds};
 use glam::Vec2;
 
-pub fn update_player(world: &mut World, input: &InputState, dt: f32, actions: &[ActionId; 4]) {
-    let [up, down, left, right] = *actions;
+use super::{MAP_HEIGHT, MAP_WIDTH};
+use crate::actions::GameActions;
+use crate::tuning::Tuning;
+
+pub fn update_player(
+    world: &mut World,
+    input: &InputState,
+    dt: f32,
+    actions: &GameActions,
+    tuning: &Tuning,
+) {
+    let (up, down, left, right) =
+        (actions.move_up, actions.move_down, actions.move_left, actions.move_right);
 
     // 1. Fetch Map Bounds (CENTERED LOGIC)
     // We convert the 2000.0 size into a range of -1000.0 to +1000.0
     // This removes the "Plus Sign" wall at 0,0.
-    let mut min_bound = Vec2::new(-1000.0, -1000.0);
-    let mut max_bound = Vec2::new(1000.0, 1000.0);
+    let mut max_bound = Vec2::new(MAP_WIDTH, MAP_HEIGHT) / 2.0;
+    let mut min_bound = -max_bound;
     
-    if let Some(bounds) = world.query::<CWorldBounds>() {
-        for (_, b) in bounds.iter() {
-            let half_w = b.width / 2.0;
-            let half_h = b.height / 2.0;
-            min_bound = Vec2::new(-half_w, -half_h);
-            max_bound = Vec2::new(half_w, half_h);
-            break; 
-        }
+    if let Some((_, b)) = world.query::<CWorldBounds>().and_then(|q| q.iter().next()) {
+        let half_w = b.width / 2.0;
+        let half_h = b.height / 2.0;
+        min_bound = Vec2::new(-half_w, -half_h);
+        max_bound = Vec2::new(half_w, half_h);
     }
 
     // 2. Identify Inputs
@@ -44,10 +52,21 @@ pub fn update_player(world: &mut World, input: &InputState, dt: f32, actions: &[
         pressed_buttons.push("RIGHT");
     }
 
-    let speed = 600.0;
+    let mut speed = tuning.player_speed;
+    if input.is_active(actions.dash) {
+        speed *= tuning.dash_multiplier;
+    }
+
+    // Analog movement (gamepad stick, already deadzoned and at most length 1)
+    // when no movement key is held; partial deflection walks slower.
+    let stick = Vec2::new(input.get_axis(0), input.get_axis(1));
+
     // This is the move we INTEND to make
     let expected_velocity = if direction.length_squared() > 0.0 {
         direction.normalize() * speed * dt
+    } else if stick != Vec2::ZERO {
+        pressed_buttons.push("STICK");
+        stick.clamp_length_max(1.0) * speed * dt
     } else {
         Vec2::ZERO
     };
@@ -81,7 +100,7 @@ pub fn update_player(world: &mut World, input: &InputState, dt: f32, actions: &[
 
                     // If we wanted to move (expected > 0) but moved less than 0.001 units
                     if expected_dist > 0.001 && actual_dist < 0.001 {
-                        println!(
+                        crate::log::log_warn!(
                             "[STUCK] Buttons: {:?} | Pos: {} | Wall Limit: {} to {}", 
                             pressed_buttons, start_pos, min_bound, max_bound
                         );
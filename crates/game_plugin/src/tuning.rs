@@ -0,0 +1,79 @@
+// crates/game_plugin/src/tuning.rs
+//
+// Gameplay tuning values, exposed as host cvars so they can be tweaked from the
+// in-game console. Registered on every load; the host keeps the current values
+// across hot reloads.
+
+use engine_shared::plugin_api::{CVarKind, HostInterface, CVAR_PERSIST};
+use glam::Vec2;
+
+pub type CvarGetFn = extern "C" fn(*const u8, usize, f64) -> f64;
+
+const PLAYER_SPEED: &str = "player.speed";
+const DASH_MULTIPLIER: &str = "player.dash_multiplier";
+const SPAWN_INTERVAL: &str = "enemy.spawn_interval";
+const DEADZONE_W: &str = "camera.deadzone_w";
+const DEADZONE_H: &str = "camera.deadzone_h";
+
+#[derive(Debug, Clone, Copy)]
+pub struct Tuning {
+    pub player_speed: f32,
+    /// Speed factor while the Dash action is held.
+    pub dash_multiplier: f32,
+    /// Seconds between enemy spawns.
+    pub spawn_interval: f32,
+    /// Half-extents of the box the player can move in before the camera follows.
+    pub camera_deadzone: Vec2,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            player_speed: 600.0,
+            dash_multiplier: 2.5,
+            spawn_interval: 2.0,
+            camera_deadzone: Vec2::new(100.0, 80.0),
+        }
+    }
+}
+
+impl Tuning {
+    pub fn register(host: &HostInterface) {
+        let d = Tuning::default();
+        let vars = [
+            (PLAYER_SPEED, d.player_speed, "Player movement speed (units/s)"),
+            (DASH_MULTIPLIER, d.dash_multiplier, "Speed factor while Dash is held"),
+            (SPAWN_INTERVAL, d.spawn_interval, "Seconds between enemy spawns"),
+            (DEADZONE_W, d.camera_deadzone.x, "Camera deadzone half-width"),
+            (DEADZONE_H, d.camera_deadzone.y, "Camera deadzone half-height"),
+        ];
+        for (name, default, desc) in vars {
+            let default = default.to_string();
+            (host.cvar_register)(
+                CVarKind::Float as u32,
+                CVAR_PERSIST,
+                name.as_ptr(),
+                name.len(),
+                default.as_ptr(),
+                default.len(),
+                desc.as_ptr(),
+                desc.len(),
+            );
+        }
+    }
+
+    /// Current values from the host (defaults for anything missing).
+    pub fn read(get: CvarGetFn) -> Self {
+        let d = Tuning::default();
+        let get = |name: &str, fallback: f32| get(name.as_ptr(), name.len(), fallback as f64) as f32;
+        Self {
+            player_speed: get(PLAYER_SPEED, d.player_speed),
+            dash_multiplier: get(DASH_MULTIPLIER, d.dash_multiplier),
+            spawn_interval: get(SPAWN_INTERVAL, d.spawn_interval),
+            camera_deadzone: Vec2::new(
+                get(DEADZONE_W, d.camera_deadzone.x),
+                get(DEADZONE_H, d.camera_deadzone.y),
+            ),
+        }
+    }
+}